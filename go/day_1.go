@@ -1,9 +1,8 @@
 package main
 
 import (
-	"bufio"
 	"fmt"
-	"os"
+	"sort"
 	"strconv"
 	"strings"
 )
@@ -12,25 +11,12 @@ type elf struct {
 	calories int
 }
 
-func main() {
-	path := "./day_1_input.txt"
-
-	f, err := os.Open(path)
-
-	if err != nil {
-		fmt.Println("Cannot open input file")
-		return
-	}
-
+func parseElves(input string) ([]elf, error) {
 	var elves []elf
 	elves = append(elves, elf{calories: 0})
 	index := 0
 
-	scanner := bufio.NewScanner(f)
-
-	for scanner.Scan() {
-		line := scanner.Text()
-
+	for _, line := range lines(input) {
 		line = strings.TrimSpace(line)
 
 		if line == "" {
@@ -39,14 +25,22 @@ func main() {
 		} else {
 			calories, err := strconv.Atoi(line)
 			if err != nil {
-				fmt.Println("Could not format calorie number")
-				return
+				return nil, fmt.Errorf("could not format calorie number %q", line)
 			}
 
 			elves[index].calories += calories
 		}
 	}
 
+	return elves, nil
+}
+
+func day1Part1(input string) (string, error) {
+	elves, err := parseElves(input)
+	if err != nil {
+		return "", err
+	}
+
 	maxCalories := 0
 
 	for elfIndex := range elves {
@@ -55,6 +49,24 @@ func main() {
 		}
 	}
 
-	fmt.Println(maxCalories)
+	return strconv.Itoa(maxCalories), nil
+}
+
+func day1Part2(input string) (string, error) {
+	elves, err := parseElves(input)
+	if err != nil {
+		return "", err
+	}
+
+	sort.Slice(elves, func(i, j int) bool {
+		return elves[i].calories > elves[j].calories
+	})
+
+	topCalories := 0
+
+	for elfIndex := 0; elfIndex < 3 && elfIndex < len(elves); elfIndex++ {
+		topCalories += elves[elfIndex].calories
+	}
 
+	return strconv.Itoa(topCalories), nil
 }
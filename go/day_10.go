@@ -0,0 +1,40 @@
+package main
+
+import (
+	"fmt"
+	"strconv"
+	"strings"
+)
+
+func day10Part1(input string) (string, error) {
+	register, cycle, strengths := 1, 0, 0
+
+	// The signal strength is read during the 20th cycle and every 40 after it, up to the 220th
+	tick := func() {
+		cycle++
+
+		if cycle%40 == 20 && cycle <= 220 {
+			strengths += cycle * register
+		}
+	}
+
+	for _, line := range nonEmptyLines(input) {
+		switch {
+		case line == "noop":
+			tick()
+		case strings.HasPrefix(line, "addx "):
+			value, err := strconv.Atoi(strings.TrimPrefix(line, "addx "))
+			if err != nil {
+				return "", fmt.Errorf("invalid addx value in %q", line)
+			}
+
+			tick()
+			tick()
+			register += value
+		default:
+			return "", fmt.Errorf("unknown operation %q", line)
+		}
+	}
+
+	return strconv.Itoa(strengths), nil
+}
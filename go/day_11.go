@@ -0,0 +1,173 @@
+package main
+
+import (
+	"errors"
+	"fmt"
+	"sort"
+	"strconv"
+	"strings"
+)
+
+type monkey struct {
+	items       []int
+	multiply    bool
+	squared     bool
+	operand     int
+	divisor     int
+	throwTrue   int
+	throwFalse  int
+	inspections int
+}
+
+func (m *monkey) inspect(worry int) int {
+	operand := m.operand
+	if m.squared {
+		operand = worry
+	}
+
+	if m.multiply {
+		return worry * operand
+	}
+
+	return worry + operand
+}
+
+// afterPrefix is the rest of the line once prefix is taken off the front
+func afterPrefix(line, prefix string) (string, error) {
+	if !strings.HasPrefix(line, prefix) {
+		return "", fmt.Errorf("expected a line starting with %q, not %q", prefix, line)
+	}
+
+	return strings.TrimSpace(strings.TrimPrefix(line, prefix)), nil
+}
+
+func parseNumber(line, prefix string) (int, error) {
+	rest, err := afterPrefix(line, prefix)
+	if err != nil {
+		return 0, err
+	}
+
+	number, err := strconv.Atoi(rest)
+	if err != nil {
+		return 0, fmt.Errorf("invalid number in %q", line)
+	}
+
+	return number, nil
+}
+
+func parseMonkey(notes []string) (monkey, error) {
+	var m monkey
+
+	items, err := afterPrefix(notes[1], "Starting items:")
+	if err != nil {
+		return m, err
+	}
+
+	for _, item := range strings.Split(items, ",") {
+		if strings.TrimSpace(item) == "" {
+			continue
+		}
+
+		worry, err := strconv.Atoi(strings.TrimSpace(item))
+		if err != nil {
+			return m, fmt.Errorf("invalid item in %q", notes[1])
+		}
+
+		m.items = append(m.items, worry)
+	}
+
+	operation, err := afterPrefix(notes[2], "Operation: new = old")
+	if err != nil {
+		return m, err
+	}
+
+	operator, operand, found := strings.Cut(operation, " ")
+	if !found || (operator != "*" && operator != "+") {
+		return m, fmt.Errorf("invalid operation %q", notes[2])
+	}
+
+	m.multiply = operator == "*"
+	m.squared = operand == "old"
+
+	if !m.squared {
+		if m.operand, err = strconv.Atoi(operand); err != nil {
+			return m, fmt.Errorf("invalid operation %q", notes[2])
+		}
+	}
+
+	if m.divisor, err = parseNumber(notes[3], "Test: divisible by"); err != nil {
+		return m, err
+	}
+	if m.throwTrue, err = parseNumber(notes[4], "If true: throw to monkey"); err != nil {
+		return m, err
+	}
+	if m.throwFalse, err = parseNumber(notes[5], "If false: throw to monkey"); err != nil {
+		return m, err
+	}
+
+	if m.divisor <= 0 {
+		return m, fmt.Errorf("invalid divisor in %q", notes[3])
+	}
+
+	return m, nil
+}
+
+func day11Part1(input string) (string, error) {
+	notes := nonEmptyLines(input)
+	if len(notes)%6 != 0 {
+		return "", errors.New("each monkey's notes should be six lines long")
+	}
+
+	var monkeys []monkey
+	for start := 0; start < len(notes); start += 6 {
+		if _, err := afterPrefix(notes[start], "Monkey"); err != nil {
+			return "", err
+		}
+
+		m, err := parseMonkey(notes[start : start+6])
+		if err != nil {
+			return "", err
+		}
+
+		monkeys = append(monkeys, m)
+	}
+
+	if len(monkeys) < 2 {
+		return "", errors.New("expected at least two monkeys")
+	}
+
+	for _, m := range monkeys {
+		if m.throwTrue < 0 || m.throwTrue >= len(monkeys) || m.throwFalse < 0 || m.throwFalse >= len(monkeys) {
+			return "", errors.New("a monkey throws to a monkey that doesn't exist")
+		}
+	}
+
+	for round := 0; round < 20; round++ {
+		for i := range monkeys {
+			m := &monkeys[i]
+
+			for _, item := range m.items {
+				worry := m.inspect(item) / 3
+				m.inspections++
+
+				target := m.throwFalse
+				if worry%m.divisor == 0 {
+					target = m.throwTrue
+				}
+
+				monkeys[target].items = append(monkeys[target].items, worry)
+			}
+
+			m.items = m.items[:0]
+		}
+	}
+
+	inspections := make([]int, len(monkeys))
+	for i, m := range monkeys {
+		inspections[i] = m.inspections
+	}
+
+	sort.Sort(sort.Reverse(sort.IntSlice(inspections)))
+
+	return strconv.Itoa(inspections[0] * inspections[1]), nil
+}
@@ -0,0 +1,33 @@
+package main
+
+import (
+	"fmt"
+	"strconv"
+	"strings"
+)
+
+// Points for a draw, a win and a loss, in order of how far my shape is ahead of
+// the opponent's: rock, paper and scissors each beat the shape before them
+var outcomeScores = [3]int{3, 6, 0}
+
+func day2Part1(input string) (string, error) {
+	total := 0
+
+	for _, line := range nonEmptyLines(input) {
+		fields := strings.Fields(line)
+		if len(fields) != 2 || len(fields[0]) != 1 || len(fields[1]) != 1 {
+			return "", fmt.Errorf("invalid round %q", line)
+		}
+
+		opponent := int(fields[0][0]) - 'A'
+		me := int(fields[1][0]) - 'X'
+
+		if opponent < 0 || opponent > 2 || me < 0 || me > 2 {
+			return "", fmt.Errorf("invalid round %q", line)
+		}
+
+		total += me + 1 + outcomeScores[(me-opponent+3)%3]
+	}
+
+	return strconv.Itoa(total), nil
+}
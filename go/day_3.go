@@ -0,0 +1,46 @@
+package main
+
+import (
+	"fmt"
+	"strconv"
+	"strings"
+)
+
+func priority(item byte) int {
+	switch {
+	case item >= 'a' && item <= 'z':
+		return int(item-'a') + 1
+	case item >= 'A' && item <= 'Z':
+		return int(item-'A') + 27
+	default:
+		return 0
+	}
+}
+
+func day3Part1(input string) (string, error) {
+	total := 0
+
+	for _, line := range nonEmptyLines(input) {
+		if len(line)%2 != 0 {
+			return "", fmt.Errorf("rucksack %q can't be split in half", line)
+		}
+
+		first, second := line[:len(line)/2], line[len(line)/2:]
+		shared := -1
+
+		for i := 0; i < len(first); i++ {
+			if strings.IndexByte(second, first[i]) >= 0 {
+				shared = i
+				break
+			}
+		}
+
+		if shared < 0 || priority(first[shared]) == 0 {
+			return "", fmt.Errorf("rucksack %q has no shared item", line)
+		}
+
+		total += priority(first[shared])
+	}
+
+	return strconv.Itoa(total), nil
+}
@@ -0,0 +1,62 @@
+package main
+
+import (
+	"fmt"
+	"strconv"
+	"strings"
+)
+
+type assignment struct {
+	start int
+	end   int
+}
+
+func parseAssignment(text string) (assignment, error) {
+	startText, endText, found := strings.Cut(text, "-")
+	if !found {
+		return assignment{}, fmt.Errorf("invalid assignment %q", text)
+	}
+
+	start, err := strconv.Atoi(startText)
+	if err != nil {
+		return assignment{}, fmt.Errorf("invalid assignment %q", text)
+	}
+
+	end, err := strconv.Atoi(endText)
+	if err != nil {
+		return assignment{}, fmt.Errorf("invalid assignment %q", text)
+	}
+
+	return assignment{start: start, end: end}, nil
+}
+
+func (outer assignment) contains(inner assignment) bool {
+	return outer.start <= inner.start && inner.end <= outer.end
+}
+
+func day4Part1(input string) (string, error) {
+	count := 0
+
+	for _, line := range nonEmptyLines(input) {
+		leftText, rightText, found := strings.Cut(line, ",")
+		if !found {
+			return "", fmt.Errorf("invalid pair %q", line)
+		}
+
+		left, err := parseAssignment(leftText)
+		if err != nil {
+			return "", err
+		}
+
+		right, err := parseAssignment(rightText)
+		if err != nil {
+			return "", err
+		}
+
+		if left.contains(right) || right.contains(left) {
+			count++
+		}
+	}
+
+	return strconv.Itoa(count), nil
+}
@@ -0,0 +1,79 @@
+package main
+
+import (
+	"errors"
+	"fmt"
+	"strings"
+)
+
+func day5Part1(input string) (string, error) {
+	allLines := lines(input)
+
+	// The drawing ends with the row of stack numbers and the moves follow it
+	numbers := -1
+	for i, line := range allLines {
+		trimmed := strings.TrimLeft(line, " ")
+
+		if trimmed != "" && trimmed[0] >= '0' && trimmed[0] <= '9' {
+			numbers = i
+			break
+		}
+	}
+
+	if numbers < 0 {
+		return "", errors.New("drawing has no row of stack numbers")
+	}
+
+	// Each stack is four characters wide, read from the bottom up
+	var stacks [][]byte
+	for i := numbers - 1; i >= 0; i-- {
+		line := allLines[i]
+
+		for stack := 0; stack*4 < len(line); stack++ {
+			for len(stacks) <= stack {
+				stacks = append(stacks, nil)
+			}
+
+			if line[stack*4] != '[' {
+				continue
+			}
+
+			if stack*4+1 >= len(line) {
+				return "", fmt.Errorf("unfinished crate in %q", line)
+			}
+
+			stacks[stack] = append(stacks[stack], line[stack*4+1])
+		}
+	}
+
+	for _, line := range nonEmptyLines(strings.Join(allLines[numbers+1:], "\n")) {
+		var quantity, from, to int
+		if _, err := fmt.Sscanf(line, "move %d from %d to %d", &quantity, &from, &to); err != nil {
+			return "", fmt.Errorf("invalid move %q", line)
+		}
+
+		if from < 1 || from > len(stacks) || to < 1 || to > len(stacks) {
+			return "", fmt.Errorf("no stack with that number in %q", line)
+		}
+
+		if quantity < 0 || quantity > len(stacks[from-1]) {
+			return "", fmt.Errorf("not enough crates for %q", line)
+		}
+
+		for i := 0; i < quantity; i++ {
+			top := len(stacks[from-1]) - 1
+
+			stacks[to-1] = append(stacks[to-1], stacks[from-1][top])
+			stacks[from-1] = stacks[from-1][:top]
+		}
+	}
+
+	var tops strings.Builder
+	for _, stack := range stacks {
+		if len(stack) > 0 {
+			tops.WriteByte(stack[len(stack)-1])
+		}
+	}
+
+	return tops.String(), nil
+}
@@ -0,0 +1,31 @@
+package main
+
+import (
+	"errors"
+	"strconv"
+	"strings"
+)
+
+func allDifferent(window string) bool {
+	for i := 0; i < len(window); i++ {
+		if strings.IndexByte(window[i+1:], window[i]) >= 0 {
+			return false
+		}
+	}
+
+	return true
+}
+
+func day6Part1(input string) (string, error) {
+	const markerLength = 4
+
+	datastream := strings.TrimSpace(input)
+
+	for end := markerLength; end <= len(datastream); end++ {
+		if allDifferent(datastream[end-markerLength : end]) {
+			return strconv.Itoa(end), nil
+		}
+	}
+
+	return "", errors.New("datastream has no start-of-packet marker")
+}
@@ -0,0 +1,49 @@
+package main
+
+import (
+	"fmt"
+	"strconv"
+	"strings"
+)
+
+func day7Part1(input string) (string, error) {
+	// Total size of everything under each directory, keyed by its path
+	sizes := map[string]int{}
+	var path []string
+
+	for _, line := range nonEmptyLines(input) {
+		switch {
+		case line == "$ cd /":
+			path = path[:0]
+		case line == "$ cd ..":
+			if len(path) > 0 {
+				path = path[:len(path)-1]
+			}
+		case strings.HasPrefix(line, "$ cd "):
+			path = append(path, strings.TrimPrefix(line, "$ cd "))
+		case line == "$ ls", strings.HasPrefix(line, "dir "):
+			// Directories only count once something is found inside them
+		default:
+			sizeText, _, found := strings.Cut(line, " ")
+
+			size, err := strconv.Atoi(sizeText)
+			if !found || err != nil || size < 0 {
+				return "", fmt.Errorf("invalid file %q", line)
+			}
+
+			// A file counts towards every directory above it
+			for depth := 0; depth <= len(path); depth++ {
+				sizes[strings.Join(path[:depth], "/")] += size
+			}
+		}
+	}
+
+	total := 0
+	for _, size := range sizes {
+		if size <= 100000 {
+			total += size
+		}
+	}
+
+	return strconv.Itoa(total), nil
+}
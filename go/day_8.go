@@ -0,0 +1,54 @@
+package main
+
+import (
+	"fmt"
+	"strconv"
+)
+
+// visibleFrom reports whether every tree from (row, column) to the edge in the
+// direction (rowStep, columnStep) is shorter than the tree there
+func visibleFrom(trees []string, row, column, rowStep, columnStep int) bool {
+	height := trees[row][column]
+
+	row, column = row+rowStep, column+columnStep
+	for row >= 0 && row < len(trees) && column >= 0 && column < len(trees[row]) {
+		if trees[row][column] >= height {
+			return false
+		}
+
+		row, column = row+rowStep, column+columnStep
+	}
+
+	return true
+}
+
+func day8Part1(input string) (string, error) {
+	trees := nonEmptyLines(input)
+
+	for _, row := range trees {
+		if len(row) != len(trees[0]) {
+			return "", fmt.Errorf("every row of trees should be the same length, not %q", row)
+		}
+
+		for i := 0; i < len(row); i++ {
+			if row[i] < '0' || row[i] > '9' {
+				return "", fmt.Errorf("invalid tree in %q", row)
+			}
+		}
+	}
+
+	visible := 0
+
+	for row := range trees {
+		for column := 0; column < len(trees[row]); column++ {
+			if visibleFrom(trees, row, column, -1, 0) ||
+				visibleFrom(trees, row, column, 1, 0) ||
+				visibleFrom(trees, row, column, 0, -1) ||
+				visibleFrom(trees, row, column, 0, 1) {
+				visible++
+			}
+		}
+	}
+
+	return strconv.Itoa(visible), nil
+}
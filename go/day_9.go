@@ -0,0 +1,64 @@
+package main
+
+import (
+	"fmt"
+	"strconv"
+	"strings"
+)
+
+type position struct {
+	x int
+	y int
+}
+
+var directions = map[string]position{
+	"R": {1, 0},
+	"L": {-1, 0},
+	"U": {0, 1},
+	"D": {0, -1},
+}
+
+// step is -1, 0 or 1, whichever moves a knot one square towards the difference
+func step(difference int) int {
+	switch {
+	case difference > 0:
+		return 1
+	case difference < 0:
+		return -1
+	default:
+		return 0
+	}
+}
+
+func touching(a, b position) bool {
+	return a.x-b.x <= 1 && b.x-a.x <= 1 && a.y-b.y <= 1 && b.y-a.y <= 1
+}
+
+func day9Part1(input string) (string, error) {
+	var head, tail position
+	visited := map[position]bool{tail: true}
+
+	for _, line := range nonEmptyLines(input) {
+		directionText, stepsText, found := strings.Cut(line, " ")
+		direction, ok := directions[directionText]
+		steps, err := strconv.Atoi(stepsText)
+
+		if !found || !ok || err != nil || steps < 0 {
+			return "", fmt.Errorf("invalid move %q", line)
+		}
+
+		for i := 0; i < steps; i++ {
+			head.x += direction.x
+			head.y += direction.y
+
+			if !touching(head, tail) {
+				tail.x += step(head.x - tail.x)
+				tail.y += step(head.y - tail.y)
+			}
+
+			visited[tail] = true
+		}
+	}
+
+	return strconv.Itoa(len(visited)), nil
+}
@@ -0,0 +1,105 @@
+package main
+
+import (
+	"fmt"
+	"os"
+	"strconv"
+	"strings"
+)
+
+// solver answers one part of one day from the whole puzzle input
+type solver func(input string) (string, error)
+
+type puzzle struct {
+	day  int
+	part int
+}
+
+// Every ported part. The Rust parity checker runs each day through both
+// implementations and compares what they print.
+var solvers = map[puzzle]solver{
+	{1, 1}:  day1Part1,
+	{1, 2}:  day1Part2,
+	{2, 1}:  day2Part1,
+	{3, 1}:  day3Part1,
+	{4, 1}:  day4Part1,
+	{5, 1}:  day5Part1,
+	{6, 1}:  day6Part1,
+	{7, 1}:  day7Part1,
+	{8, 1}:  day8Part1,
+	{9, 1}:  day9Part1,
+	{10, 1}: day10Part1,
+	{11, 1}: day11Part1,
+}
+
+const usage = "Usage: go run . [<day> <part> <input path>]"
+
+func main() {
+	// Running with no arguments keeps the old behaviour of solving day 1 part 1
+	day, part, path := 1, 1, "./day_1_input.txt"
+
+	if len(os.Args) > 1 {
+		if len(os.Args) != 4 {
+			fail(2, usage)
+		}
+
+		var err error
+		if day, err = strconv.Atoi(os.Args[1]); err != nil {
+			fail(2, "Invalid day: "+os.Args[1])
+		}
+		if part, err = strconv.Atoi(os.Args[2]); err != nil {
+			fail(2, "Invalid part: "+os.Args[2])
+		}
+		path = os.Args[3]
+	}
+
+	solve, ok := solvers[puzzle{day, part}]
+	if !ok {
+		// Printed the same way the Rust solvers show an unsolved part
+		fmt.Println("not implemented")
+		return
+	}
+
+	input, err := os.ReadFile(path)
+	if err != nil {
+		fail(1, "Cannot open input file")
+	}
+
+	answer, err := solve(string(input))
+	if err != nil {
+		fail(1, err.Error())
+	}
+
+	fmt.Println(answer)
+}
+
+func fail(code int, message string) {
+	fmt.Fprintln(os.Stderr, message)
+	os.Exit(code)
+}
+
+// lines splits the input the way Rust's str::lines does, without the line endings
+func lines(input string) []string {
+	split := strings.Split(strings.TrimSuffix(input, "\n"), "\n")
+
+	for i := range split {
+		split[i] = strings.TrimSuffix(split[i], "\r")
+	}
+
+	return split
+}
+
+// nonEmptyLines is every line with surrounding whitespace trimmed, skipping blank ones
+func nonEmptyLines(input string) []string {
+	var trimmed []string
+
+	for _, line := range lines(input) {
+		line = strings.TrimSpace(line)
+
+		if line != "" {
+			trimmed = append(trimmed, line)
+		}
+	}
+
+	return trimmed
+}
@@ -0,0 +1,100 @@
+//! Turns every puzzle example in `examples/day_N/NAME.txt` into a test, checked
+//! against the answers in the `NAME.toml` next to it. See `src/examples.rs`.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+fn main() {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let examples_dir = manifest_dir.join("examples");
+
+    println!("cargo:rerun-if-changed=examples");
+
+    let mut days = Vec::new();
+    let mut tests = String::new();
+
+    for (day, day_dir) in day_dirs(&examples_dir) {
+        days.push(day);
+
+        for input_path in files_with_extension(&day_dir, "txt") {
+            let name = input_path.file_stem().unwrap().to_string_lossy();
+            let answers_path = input_path.with_extension("toml");
+
+            let answers = if answers_path.is_file() {
+                format!(
+                    "Some(include_str!({:?}))",
+                    answers_path.display().to_string()
+                )
+            } else {
+                "None".to_string()
+            };
+
+            tests.push_str(&format!(
+                "#[test]\nfn day_{}_{}() {{\n    check_example({}, include_str!({:?}), {});\n}}\n\n",
+                day,
+                test_name(&name),
+                day,
+                input_path.display().to_string(),
+                answers,
+            ));
+        }
+    }
+
+    let generated = format!(
+        "/// Days with at least one example\nconst EXAMPLE_DAYS: &[u8] = &{:?};\n\n{}",
+        days, tests
+    );
+
+    let out_path = PathBuf::from(env::var("OUT_DIR").unwrap()).join("examples.rs");
+    fs::write(out_path, generated).unwrap();
+}
+
+/// `examples/day_N` directories sorted by day
+fn day_dirs(examples_dir: &Path) -> Vec<(u8, PathBuf)> {
+    let Ok(entries) = fs::read_dir(examples_dir) else {
+        return Vec::new();
+    };
+
+    let mut day_dirs = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let day = path
+                .file_name()?
+                .to_str()?
+                .strip_prefix("day_")?
+                .parse::<u8>()
+                .ok()?;
+
+            path.is_dir().then_some((day, path))
+        })
+        .collect::<Vec<_>>();
+
+    day_dirs.sort();
+    day_dirs
+}
+
+fn files_with_extension(dir: &Path, extension: &str) -> Vec<PathBuf> {
+    let mut files = fs::read_dir(dir)
+        .unwrap()
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == extension))
+        .collect::<Vec<_>>();
+
+    files.sort();
+    files
+}
+
+/// Example names can be anything a file can be called, test names can't
+fn test_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,27 @@
+//! What every fuzz target runs: a day's parser on the raw bytes and, if they
+//! parse, both parts. Bad input has to come back as a `ParseError`, so any
+//! panic, overflow or out of bounds index is a crash for libFuzzer to report.
+
+use advent::day_9::{total_steps, MoveAction, MAX_FUZZED_STEPS};
+use advent::{registry, Part};
+
+pub fn run(day: u8, data: &[u8]) {
+    let solver = registry()
+        .into_iter()
+        .find(|solver| solver.day() == day)
+        .expect("Every fuzz target is for a solved day");
+
+    let Ok(parsed) = solver.read(&mut &data[..]) else {
+        return;
+    };
+
+    if let Some(actions) = parsed.downcast_ref::<Vec<MoveAction>>() {
+        if total_steps(actions) > MAX_FUZZED_STEPS {
+            return;
+        }
+    }
+
+    for part in Part::all() {
+        solver.solve(parsed.as_ref(), part);
+    }
+}
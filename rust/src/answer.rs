@@ -0,0 +1,162 @@
+use std::fmt;
+
+use crate::solution::Part;
+
+/// What a part of a day solves to. Every solver returns one, so the runner,
+/// the verifier and the JSON output all treat answers the same way.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+    /// A picture to be read by eye, one string per row
+    Grid(Vec<String>),
+    /// The part hasn't been solved yet
+    NotImplemented,
+}
+
+impl Answer {
+    /// The name used for the answer's kind in JSON output
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Answer::Integer(_) => "integer",
+            Answer::Text(_) => "text",
+            Answer::Grid(_) => "grid",
+            Answer::NotImplemented => "not_implemented",
+        }
+    }
+
+    /// Rebuilds an answer from its kind and how it was displayed, the
+    /// reverse of [`Answer::kind`] and [`fmt::Display`]
+    pub fn from_kind(kind: &str, text: Option<&str>) -> Option<Answer> {
+        match (kind, text) {
+            ("integer", Some(text)) => text.parse().ok().map(Answer::Integer),
+            ("text", Some(text)) => Some(Answer::Text(text.to_string())),
+            ("grid", Some(text)) => {
+                Some(Answer::Grid(text.split('\n').map(str::to_string).collect()))
+            }
+            ("not_implemented", None) => Some(Answer::NotImplemented),
+            _ => None,
+        }
+    }
+
+    /// How the answer is shown in a table cell, where a grid doesn't fit
+    pub fn single_line(&self) -> String {
+        match self {
+            Answer::Grid(rows) => format!("({} rows, see below)", rows.len()),
+            answer => answer.to_string(),
+        }
+    }
+}
+
+/// The grid answers that tables leave out, each under the day and part it
+/// answers, to be printed after the table
+pub fn format_grids<'a>(answers: impl IntoIterator<Item = (u8, Part, &'a Answer)>) -> String {
+    answers
+        .into_iter()
+        .filter(|(_, _, answer)| matches!(answer, Answer::Grid(_)))
+        .map(|(day, part, answer)| format!("\n\nDay {} part {}:\n{}", day, part, answer))
+        .collect()
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Integer(integer) => write!(f, "{}", integer),
+            Answer::Text(text) => write!(f, "{}", text),
+            Answer::Grid(rows) => write!(f, "{}", rows.join("\n")),
+            Answer::NotImplemented => write!(f, "not implemented"),
+        }
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(integer: i32) -> Self {
+        Answer::Integer(i128::from(integer))
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(integer: i64) -> Self {
+        Answer::Integer(i128::from(integer))
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(integer: usize) -> Self {
+        Answer::Integer(integer as i128)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Answer::from(-72602).to_string(), "-72602");
+        assert_eq!(Answer::from("VCTFTJQCG").to_string(), "VCTFTJQCG");
+        assert_eq!(
+            Answer::Grid(vec!["#..".to_string(), ".#.".to_string()]).to_string(),
+            "#..\n.#."
+        );
+        assert_eq!(Answer::NotImplemented.to_string(), "not implemented");
+    }
+
+    #[test]
+    fn test_single_line() {
+        let grid = Answer::Grid(vec!["#..".to_string(), ".#.".to_string()]);
+
+        assert_eq!(grid.single_line(), "(2 rows, see below)");
+        assert_eq!(Answer::from(24000).single_line(), "24000");
+    }
+
+    #[test]
+    fn test_format_grids() {
+        let grid = Answer::Grid(vec!["#..".to_string(), ".#.".to_string()]);
+        let integer = Answer::from(13140);
+
+        assert_eq!(
+            format_grids([(10, Part::One, &integer), (10, Part::Two, &grid)]),
+            "\n\nDay 10 part 2:\n#..\n.#."
+        );
+        assert_eq!(format_grids([(10, Part::One, &integer)]), "");
+    }
+
+    #[test]
+    fn test_from_kind_round_trip() {
+        let answers = [
+            Answer::from(24000),
+            Answer::from(""),
+            Answer::from("GCFGLDNJZ"),
+            Answer::Grid(vec!["##..".to_string(), "..##".to_string()]),
+            Answer::NotImplemented,
+        ];
+
+        for answer in answers {
+            let text = match answer {
+                Answer::NotImplemented => None,
+                _ => Some(answer.to_string()),
+            };
+
+            assert_eq!(
+                Answer::from_kind(answer.kind(), text.as_deref()),
+                Some(answer)
+            );
+        }
+
+        assert_eq!(Answer::from_kind("integer", Some("twelve")), None);
+        assert_eq!(Answer::from_kind("colour", Some("red")), None);
+    }
+}
@@ -0,0 +1,818 @@
+use std::{fmt, path::PathBuf};
+
+use crate::output::Format;
+use advent::day_1::stats::DEFAULT_HISTOGRAM_BUCKETS;
+use advent::day_2::strategy::DEFAULT_ROUNDS;
+use advent::loader::InputSource;
+use advent::Part;
+
+pub const USAGE: &str = "Usage:
+    advent run --all [--format text|json|lines] [--jobs <N>]
+    advent run --day <DAY> [--part <PART>] [--input <PATH>|-] [--format text|json|lines] [--jobs <N>]
+    advent bench --all [--runs <N>]
+    advent bench --day <DAY> [--part <PART>] [--input <PATH>|-] [--runs <N>]
+    advent watch --day <DAY> [--input <PATH>]
+    advent verify [--answers <PATH>]
+    advent parity --all [--go <DIR>]
+    advent parity --day <DAY> [--part <PART>] [--input <PATH>|-] [--go <DIR>]
+    advent gen --day <DAY> --size <N> [--seed <SEED>]
+    advent day1 stats [--input <PATH>|-] [--buckets <N>]
+    advent day1 rebalance [--input <PATH>|-] [--elves <N>]
+    advent day2 tournament [--input <PATH>|-] [--rounds <N>] [--seed <SEED>]
+    advent day2 decrypt [--input <PATH>|-]
+    advent new <DAY>
+    advent list
+    advent help
+
+Any command can be preceded by --root <DIR>, the crate directory that inputs/,
+answers.toml, the day sources and ../go are found under. It is the current
+directory unless given.";
+
+#[derive(Debug, PartialEq)]
+pub enum Selection {
+    All,
+    Day {
+        day: u8,
+        part: Option<Part>,
+        input: InputSource,
+    },
+}
+
+pub const DEFAULT_BENCH_RUNS: usize = 10;
+
+/// Days are run one at a time unless more threads are asked for
+pub const DEFAULT_JOBS: usize = 1;
+
+pub const DEFAULT_GEN_SEED: u64 = 1;
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run {
+        selection: Selection,
+        format: Format,
+        jobs: usize,
+    },
+    Bench {
+        selection: Selection,
+        runs: usize,
+    },
+    Watch {
+        day: u8,
+        input: InputSource,
+    },
+    Verify {
+        /// The accepted answers, `answers.toml` under the root unless given
+        answers: Option<PathBuf>,
+    },
+    /// Compares the Go translations with the Rust solvers
+    Parity {
+        selection: Selection,
+        /// The Go module to build, `go` next to the root unless given
+        go: Option<PathBuf>,
+    },
+    /// Writes a synthetic input to stdout, for seeing how a solver scales
+    Gen {
+        day: u8,
+        size: usize,
+        seed: u64,
+    },
+    /// Describes how much food day 1's elves carry
+    Day1Stats {
+        input: InputSource,
+        buckets: usize,
+    },
+    /// Shares day 1's food out so nobody carries much more than anyone else
+    Day1Rebalance {
+        input: InputSource,
+        /// How many elves to share between, as many as there are unless given
+        elves: Option<usize>,
+    },
+    /// Plays rock paper scissors strategies against each other, one of them
+    /// following day 2's guide
+    Day2Tournament {
+        input: InputSource,
+        rounds: usize,
+        seed: u64,
+    },
+    /// Scores day 2's guide under every meaning its second column could have
+    Day2Decrypt {
+        input: InputSource,
+    },
+    New(u8),
+    List,
+    Help,
+}
+
+/// A command and the directory it works under
+#[derive(Debug, PartialEq)]
+pub struct Invocation {
+    pub root: Option<PathBuf>,
+    pub command: Command,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CliError(String);
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Reads an optional `--root <DIR>` and then the command
+pub fn parse_invocation<I>(args: I) -> Result<Invocation, CliError>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter().peekable();
+
+    let root = match args.next_if(|arg| arg == "--root") {
+        Some(_) => Some(PathBuf::from(flag_value(&mut args, "--root")?)),
+        None => None,
+    };
+
+    Ok(Invocation {
+        root,
+        command: parse_args(args)?,
+    })
+}
+
+pub fn parse_args<I>(args: I) -> Result<Command, CliError>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter();
+
+    match args.next().as_deref() {
+        // Running with no arguments keeps the old behaviour of solving everything
+        None => Ok(Command::Run {
+            selection: Selection::All,
+            format: Format::Text,
+            jobs: DEFAULT_JOBS,
+        }),
+        Some("run") => parse_run_args(args),
+        Some("bench") => parse_bench_args(args),
+        Some("watch") => parse_watch_args(args),
+        Some("verify") => parse_verify_args(args),
+        Some("parity") => parse_parity_args(args),
+        Some("gen") => parse_gen_args(args),
+        Some("day1") => parse_day_1_args(args),
+        Some("day2") => parse_day_2_args(args),
+        Some("new") => parse_new_args(args),
+        Some("list") => no_more_args(args, Command::List),
+        Some("help") | Some("--help") | Some("-h") => Ok(Command::Help),
+        Some(other) => Err(CliError(format!("Unknown command: {}", other))),
+    }
+}
+
+fn parse_run_args(mut args: impl Iterator<Item = String>) -> Result<Command, CliError> {
+    let mut format = Format::Text;
+    let mut jobs = DEFAULT_JOBS;
+    let mut selection_args = Vec::new();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => format = parse_format(&flag_value(&mut args, "--format")?)?,
+            "--jobs" => jobs = parse_count(&flag_value(&mut args, "--jobs")?, "jobs")?,
+            _ => selection_args.push(arg),
+        }
+    }
+
+    let selection = parse_selection(selection_args.into_iter(), "run")?;
+
+    Ok(Command::Run {
+        selection,
+        format,
+        jobs,
+    })
+}
+
+fn parse_bench_args(mut args: impl Iterator<Item = String>) -> Result<Command, CliError> {
+    let mut runs = DEFAULT_BENCH_RUNS;
+    let mut selection_args = Vec::new();
+
+    // Pull out the flags only bench understands and leave the rest to the selection
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--runs" => runs = parse_count(&flag_value(&mut args, "--runs")?, "runs")?,
+            _ => selection_args.push(arg),
+        }
+    }
+
+    let selection = parse_selection(selection_args.into_iter(), "bench")?;
+
+    Ok(Command::Bench { selection, runs })
+}
+
+fn parse_selection(
+    mut args: impl Iterator<Item = String>,
+    command: &str,
+) -> Result<Selection, CliError> {
+    let mut all = false;
+    let mut day: Option<u8> = None;
+    let mut part: Option<Part> = None;
+    let mut input: Option<InputSource> = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--all" => all = true,
+            "--day" => day = Some(parse_day(&flag_value(&mut args, "--day")?)?),
+            "--part" => part = Some(parse_part(&flag_value(&mut args, "--part")?)?),
+            "--input" => input = Some(InputSource::from_arg(&flag_value(&mut args, "--input")?)),
+            other => {
+                return Err(CliError(format!(
+                    "Unknown argument to {}: {}",
+                    command, other
+                )))
+            }
+        }
+    }
+
+    match (all, day) {
+        (true, None) if part.is_none() && input.is_none() => Ok(Selection::All),
+        (true, _) => Err(CliError(
+            "--all cannot be combined with --day, --part or --input".to_string(),
+        )),
+        (false, Some(day)) => Ok(Selection::Day {
+            day,
+            part,
+            input: input.unwrap_or(InputSource::Default),
+        }),
+        (false, None) if part.is_some() || input.is_some() => {
+            Err(CliError("--part and --input require --day".to_string()))
+        }
+        (false, None) => Err(CliError(format!("{} requires --all or --day", command))),
+    }
+}
+
+fn parse_watch_args(mut args: impl Iterator<Item = String>) -> Result<Command, CliError> {
+    let mut day: Option<u8> = None;
+    let mut input = InputSource::Default;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" => day = Some(parse_day(&flag_value(&mut args, "--day")?)?),
+            "--input" => input = InputSource::from_arg(&flag_value(&mut args, "--input")?),
+            other => return Err(CliError(format!("Unknown argument to watch: {}", other))),
+        }
+    }
+
+    // Stdin can only be read once, so there would be nothing to watch
+    if input == InputSource::Stdin {
+        return Err(CliError("watch needs an input file, not stdin".to_string()));
+    }
+
+    match day {
+        Some(day) => Ok(Command::Watch { day, input }),
+        None => Err(CliError("watch requires --day".to_string())),
+    }
+}
+
+fn parse_verify_args(mut args: impl Iterator<Item = String>) -> Result<Command, CliError> {
+    let mut answers = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--answers" => answers = Some(PathBuf::from(flag_value(&mut args, "--answers")?)),
+            other => return Err(CliError(format!("Unknown argument to verify: {}", other))),
+        }
+    }
+
+    Ok(Command::Verify { answers })
+}
+
+fn parse_parity_args(mut args: impl Iterator<Item = String>) -> Result<Command, CliError> {
+    let mut go = None;
+    let mut selection_args = Vec::new();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--go" => go = Some(PathBuf::from(flag_value(&mut args, "--go")?)),
+            _ => selection_args.push(arg),
+        }
+    }
+
+    let selection = parse_selection(selection_args.into_iter(), "parity")?;
+
+    Ok(Command::Parity { selection, go })
+}
+
+fn parse_gen_args(mut args: impl Iterator<Item = String>) -> Result<Command, CliError> {
+    let mut day: Option<u8> = None;
+    let mut size: Option<usize> = None;
+    let mut seed = DEFAULT_GEN_SEED;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" => day = Some(parse_day(&flag_value(&mut args, "--day")?)?),
+            "--size" => size = Some(parse_count(&flag_value(&mut args, "--size")?, "size")?),
+            "--seed" => seed = parse_seed(&flag_value(&mut args, "--seed")?)?,
+            other => return Err(CliError(format!("Unknown argument to gen: {}", other))),
+        }
+    }
+
+    match (day, size) {
+        (Some(day), Some(size)) => Ok(Command::Gen { day, size, seed }),
+        _ => Err(CliError("gen requires --day and --size".to_string())),
+    }
+}
+
+fn parse_day_1_args(mut args: impl Iterator<Item = String>) -> Result<Command, CliError> {
+    let report = match args.next() {
+        Some(report) if report == "stats" || report == "rebalance" => report,
+        Some(other) => return Err(CliError(format!("Unknown day1 report: {}", other))),
+        None => return Err(CliError("day1 requires a report".to_string())),
+    };
+
+    let mut input = InputSource::Default;
+    let mut buckets = DEFAULT_HISTOGRAM_BUCKETS;
+    let mut elves = None;
+
+    while let Some(arg) = args.next() {
+        match (report.as_str(), arg.as_str()) {
+            (_, "--input") => input = InputSource::from_arg(&flag_value(&mut args, "--input")?),
+            ("stats", "--buckets") => {
+                buckets = parse_count(&flag_value(&mut args, "--buckets")?, "buckets")?
+            }
+            ("rebalance", "--elves") => {
+                elves = Some(parse_count(&flag_value(&mut args, "--elves")?, "elves")?)
+            }
+            (_, other) => {
+                return Err(CliError(format!(
+                    "Unknown argument to day1 {}: {}",
+                    report, other
+                )))
+            }
+        }
+    }
+
+    match report.as_str() {
+        "stats" => Ok(Command::Day1Stats { input, buckets }),
+        _ => Ok(Command::Day1Rebalance { input, elves }),
+    }
+}
+
+fn parse_day_2_args(mut args: impl Iterator<Item = String>) -> Result<Command, CliError> {
+    let report = match args.next() {
+        Some(report) if report == "tournament" || report == "decrypt" => report,
+        Some(other) => return Err(CliError(format!("Unknown day2 report: {}", other))),
+        None => return Err(CliError("day2 requires a report".to_string())),
+    };
+
+    let mut input = InputSource::Default;
+    let mut rounds = DEFAULT_ROUNDS;
+    let mut seed = DEFAULT_GEN_SEED;
+
+    while let Some(arg) = args.next() {
+        match (report.as_str(), arg.as_str()) {
+            (_, "--input") => input = InputSource::from_arg(&flag_value(&mut args, "--input")?),
+            ("tournament", "--rounds") => {
+                rounds = parse_count(&flag_value(&mut args, "--rounds")?, "rounds")?
+            }
+            ("tournament", "--seed") => seed = parse_seed(&flag_value(&mut args, "--seed")?)?,
+            (_, other) => {
+                return Err(CliError(format!(
+                    "Unknown argument to day2 {}: {}",
+                    report, other
+                )))
+            }
+        }
+    }
+
+    match report.as_str() {
+        "tournament" => Ok(Command::Day2Tournament {
+            input,
+            rounds,
+            seed,
+        }),
+        _ => Ok(Command::Day2Decrypt { input }),
+    }
+}
+
+fn parse_new_args(mut args: impl Iterator<Item = String>) -> Result<Command, CliError> {
+    let day = args
+        .next()
+        .ok_or_else(|| CliError("new requires a day".to_string()))?;
+
+    no_more_args(args, Command::New(parse_day(&day)?))
+}
+
+fn no_more_args(
+    mut args: impl Iterator<Item = String>,
+    command: Command,
+) -> Result<Command, CliError> {
+    match args.next() {
+        Some(arg) => Err(CliError(format!("Unexpected argument: {}", arg))),
+        None => Ok(command),
+    }
+}
+
+fn flag_value(args: &mut impl Iterator<Item = String>, flag: &str) -> Result<String, CliError> {
+    args.next()
+        .ok_or_else(|| CliError(format!("{} requires a value", flag)))
+}
+
+fn parse_day(value: &str) -> Result<u8, CliError> {
+    match value.parse::<u8>() {
+        Ok(day) if (1..=25).contains(&day) => Ok(day),
+        _ => Err(CliError(format!("Invalid day: {}", value))),
+    }
+}
+
+fn parse_format(value: &str) -> Result<Format, CliError> {
+    Format::from_arg(value).ok_or_else(|| CliError(format!("Invalid format: {}", value)))
+}
+
+fn parse_count(value: &str, name: &str) -> Result<usize, CliError> {
+    match value.parse::<usize>() {
+        Ok(count) if count > 0 => Ok(count),
+        _ => Err(CliError(format!("Invalid number of {}: {}", name, value))),
+    }
+}
+
+fn parse_seed(value: &str) -> Result<u64, CliError> {
+    value
+        .parse::<u64>()
+        .map_err(|_| CliError(format!("Invalid seed: {}", value)))
+}
+
+fn parse_part(value: &str) -> Result<Part, CliError> {
+    match value {
+        "1" => Ok(Part::One),
+        "2" => Ok(Part::Two),
+        _ => Err(CliError(format!("Invalid part: {}", value))),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Command, CliError> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_parse_run() {
+        let run_all = Ok(Command::Run {
+            selection: Selection::All,
+            format: Format::Text,
+            jobs: DEFAULT_JOBS,
+        });
+
+        assert_eq!(parse(&[]), run_all);
+
+        assert_eq!(parse(&["run", "--all"]), run_all);
+
+        assert_eq!(
+            parse(&["run", "--day", "7", "--part", "2"]),
+            Ok(Command::Run {
+                selection: Selection::Day {
+                    day: 7,
+                    part: Some(Part::Two),
+                    input: InputSource::Default,
+                },
+                format: Format::Text,
+                jobs: DEFAULT_JOBS,
+            })
+        );
+
+        assert_eq!(
+            parse(&["run", "--day", "3"]),
+            Ok(Command::Run {
+                selection: Selection::Day {
+                    day: 3,
+                    part: None,
+                    input: InputSource::Default,
+                },
+                format: Format::Text,
+                jobs: DEFAULT_JOBS,
+            })
+        );
+
+        assert_eq!(
+            parse(&["run", "--day", "3", "--input", "-"]),
+            Ok(Command::Run {
+                selection: Selection::Day {
+                    day: 3,
+                    part: None,
+                    input: InputSource::Stdin,
+                },
+                format: Format::Text,
+                jobs: DEFAULT_JOBS,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!(
+            parse(&["run", "--all", "--format", "json"]),
+            Ok(Command::Run {
+                selection: Selection::All,
+                format: Format::Json,
+                jobs: DEFAULT_JOBS,
+            })
+        );
+
+        assert_eq!(
+            parse(&["run", "--all", "--format", "lines"]),
+            Ok(Command::Run {
+                selection: Selection::All,
+                format: Format::Lines,
+                jobs: DEFAULT_JOBS,
+            })
+        );
+
+        assert_eq!(
+            parse(&["run", "--format", "text", "--day", "2"]),
+            Ok(Command::Run {
+                selection: Selection::Day {
+                    day: 2,
+                    part: None,
+                    input: InputSource::Default,
+                },
+                format: Format::Text,
+                jobs: DEFAULT_JOBS,
+            })
+        );
+
+        assert!(parse(&["run", "--all", "--format", "xml"]).is_err());
+        assert!(parse(&["run", "--all", "--format"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_jobs() {
+        assert_eq!(
+            parse(&["run", "--all", "--jobs", "4"]),
+            Ok(Command::Run {
+                selection: Selection::All,
+                format: Format::Text,
+                jobs: 4,
+            })
+        );
+
+        assert_eq!(
+            parse(&["run", "--day", "3", "--jobs", "2"]),
+            Ok(Command::Run {
+                selection: Selection::Day {
+                    day: 3,
+                    part: None,
+                    input: InputSource::Default,
+                },
+                format: Format::Text,
+                jobs: 2,
+            })
+        );
+
+        assert!(parse(&["run", "--all", "--jobs", "0"]).is_err());
+        assert!(parse(&["run", "--all", "--jobs", "many"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_bench() {
+        assert_eq!(
+            parse(&["bench", "--all"]),
+            Ok(Command::Bench {
+                selection: Selection::All,
+                runs: DEFAULT_BENCH_RUNS,
+            })
+        );
+
+        assert_eq!(
+            parse(&["bench", "--runs", "50", "--day", "11", "--part", "2"]),
+            Ok(Command::Bench {
+                selection: Selection::Day {
+                    day: 11,
+                    part: Some(Part::Two),
+                    input: InputSource::Default,
+                },
+                runs: 50,
+            })
+        );
+
+        assert!(parse(&["bench"]).is_err());
+        assert!(parse(&["bench", "--all", "--runs", "0"]).is_err());
+        assert!(parse(&["bench", "--all", "--runs"]).is_err());
+        assert!(parse(&["run", "--all", "--runs", "5"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_watch() {
+        assert_eq!(
+            parse(&["watch", "--day", "12"]),
+            Ok(Command::Watch {
+                day: 12,
+                input: InputSource::Default,
+            })
+        );
+
+        assert_eq!(
+            parse(&["watch", "--day", "12", "--input", "day_12.txt"]),
+            Ok(Command::Watch {
+                day: 12,
+                input: InputSource::from_arg("day_12.txt"),
+            })
+        );
+
+        assert!(parse(&["watch"]).is_err());
+        assert!(parse(&["watch", "--day", "12", "--input", "-"]).is_err());
+        assert!(parse(&["watch", "--day", "12", "--part", "1"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_root() {
+        let invocation = |args: &[&str]| parse_invocation(args.iter().map(|arg| arg.to_string()));
+
+        assert_eq!(
+            invocation(&["--root", "../rust", "verify"]),
+            Ok(Invocation {
+                root: Some(PathBuf::from("../rust")),
+                command: Command::Verify { answers: None },
+            })
+        );
+
+        assert_eq!(
+            invocation(&["list"]),
+            Ok(Invocation {
+                root: None,
+                command: Command::List,
+            })
+        );
+
+        assert!(invocation(&["--root"]).is_err());
+        assert!(invocation(&["list", "--root", "."]).is_err());
+    }
+
+    #[test]
+    fn test_parse_verify() {
+        assert_eq!(parse(&["verify"]), Ok(Command::Verify { answers: None }));
+
+        assert_eq!(
+            parse(&["verify", "--answers", "other.toml"]),
+            Ok(Command::Verify {
+                answers: Some(PathBuf::from("other.toml")),
+            })
+        );
+
+        assert!(parse(&["verify", "--day", "1"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_parity() {
+        assert_eq!(
+            parse(&["parity", "--all"]),
+            Ok(Command::Parity {
+                selection: Selection::All,
+                go: None,
+            })
+        );
+
+        assert_eq!(
+            parse(&["parity", "--go", "../go", "--day", "5", "--part", "1"]),
+            Ok(Command::Parity {
+                selection: Selection::Day {
+                    day: 5,
+                    part: Some(Part::One),
+                    input: InputSource::Default,
+                },
+                go: Some(PathBuf::from("../go")),
+            })
+        );
+
+        assert!(parse(&["parity"]).is_err());
+        assert!(parse(&["parity", "--all", "--go"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_gen() {
+        assert_eq!(
+            parse(&["gen", "--day", "8", "--size", "5000"]),
+            Ok(Command::Gen {
+                day: 8,
+                size: 5000,
+                seed: DEFAULT_GEN_SEED,
+            })
+        );
+
+        assert_eq!(
+            parse(&["gen", "--seed", "42", "--size", "1000000", "--day", "1"]),
+            Ok(Command::Gen {
+                day: 1,
+                size: 1_000_000,
+                seed: 42,
+            })
+        );
+
+        assert!(parse(&["gen", "--day", "1"]).is_err());
+        assert!(parse(&["gen", "--size", "10"]).is_err());
+        assert!(parse(&["gen", "--day", "1", "--size", "0"]).is_err());
+        assert!(parse(&["gen", "--day", "1", "--size", "10", "--seed", "-1"]).is_err());
+        assert!(parse(&["gen", "--all", "--size", "10"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_day_1() {
+        assert_eq!(
+            parse(&["day1", "stats"]),
+            Ok(Command::Day1Stats {
+                input: InputSource::Default,
+                buckets: DEFAULT_HISTOGRAM_BUCKETS,
+            })
+        );
+
+        assert_eq!(
+            parse(&["day1", "stats", "--buckets", "20", "--input", "-"]),
+            Ok(Command::Day1Stats {
+                input: InputSource::Stdin,
+                buckets: 20,
+            })
+        );
+
+        assert_eq!(
+            parse(&["day1", "rebalance", "--elves", "4"]),
+            Ok(Command::Day1Rebalance {
+                input: InputSource::Default,
+                elves: Some(4),
+            })
+        );
+
+        assert_eq!(
+            parse(&["day1", "rebalance", "--input", "-"]),
+            Ok(Command::Day1Rebalance {
+                input: InputSource::Stdin,
+                elves: None,
+            })
+        );
+
+        assert!(parse(&["day1"]).is_err());
+        assert!(parse(&["day1", "rebalance", "--elves", "0"]).is_err());
+        assert!(parse(&["day1", "rebalance", "--buckets", "4"]).is_err());
+        assert!(parse(&["day1", "stats", "--elves", "4"]).is_err());
+        assert!(parse(&["day1", "plot"]).is_err());
+        assert!(parse(&["day1", "stats", "--buckets", "0"]).is_err());
+        assert!(parse(&["day1", "stats", "--day", "1"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_day_2() {
+        assert_eq!(
+            parse(&["day2", "tournament"]),
+            Ok(Command::Day2Tournament {
+                input: InputSource::Default,
+                rounds: DEFAULT_ROUNDS,
+                seed: DEFAULT_GEN_SEED,
+            })
+        );
+
+        assert_eq!(
+            parse(&["day2", "tournament", "--rounds", "50", "--seed", "9"]),
+            Ok(Command::Day2Tournament {
+                input: InputSource::Default,
+                rounds: 50,
+                seed: 9,
+            })
+        );
+
+        assert_eq!(
+            parse(&["day2", "decrypt", "--input", "-"]),
+            Ok(Command::Day2Decrypt {
+                input: InputSource::Stdin,
+            })
+        );
+
+        assert!(parse(&["day2"]).is_err());
+        assert!(parse(&["day2", "decrypt", "--rounds", "50"]).is_err());
+        assert!(parse(&["day2", "tournament", "--rounds", "0"]).is_err());
+        assert!(parse(&["day2", "tournament", "--elves", "3"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_new() {
+        assert_eq!(parse(&["new", "12"]), Ok(Command::New(12)));
+
+        assert!(parse(&["new"]).is_err());
+        assert!(parse(&["new", "0"]).is_err());
+        assert!(parse(&["new", "12", "13"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_list() {
+        assert_eq!(parse(&["list"]), Ok(Command::List));
+
+        assert!(parse(&["list", "--day", "1"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(parse(&["run"]).is_err());
+        assert!(parse(&["run", "--day"]).is_err());
+        assert!(parse(&["run", "--day", "26"]).is_err());
+        assert!(parse(&["run", "--day", "1", "--part", "3"]).is_err());
+        assert!(parse(&["run", "--part", "1"]).is_err());
+        assert!(parse(&["run", "--all", "--day", "1"]).is_err());
+        assert!(parse(&["run", "--all", "--input", "day_1.txt"]).is_err());
+        assert!(parse(&["run", "--input", "day_1.txt"]).is_err());
+        assert!(parse(&["solve"]).is_err());
+    }
+}
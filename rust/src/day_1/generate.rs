@@ -0,0 +1,42 @@
+use std::io::{self, Write};
+
+use crate::rng::Rng;
+
+/// Each elf's snacks, in the order they're listed
+pub fn generate(rng: &mut Rng) -> Vec<Vec<i32>> {
+    (0..rng.count(1..=20))
+        .map(|_| {
+            (0..rng.count(1..=8))
+                .map(|_| rng.range(1..=99_999) as i32)
+                .collect()
+        })
+        .collect()
+}
+
+pub fn serialize(elves: &[Vec<i32>]) -> String {
+    elves
+        .iter()
+        .map(|snacks| {
+            snacks
+                .iter()
+                .map(|calories| format!("{}\n", calories))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// `size` elves carrying up to eight snacks each, as large as [`generate`]'s
+pub fn stress(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    for elf in 0..size {
+        if elf > 0 {
+            writeln!(out)?;
+        }
+
+        for _ in 0..rng.count(1..=8) {
+            writeln!(out, "{}", rng.range(1..=99_999))?;
+        }
+    }
+
+    Ok(())
+}
@@ -1,5 +1,4 @@
-pub static INPUT_DAY_1: &str = 
-r#"5118
+pub static INPUT_DAY_1: &str = r#"5118
 5554
 4186
 4729
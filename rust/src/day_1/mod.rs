@@ -1,18 +1,39 @@
+//! Day 1: Calorie Counting
+
+use std::{cmp::Reverse, collections::BinaryHeap, io::BufRead};
+
+use crate::answer::Answer;
+use crate::error::{FragmentError, ParseError};
+use crate::normalize::LineReader;
+use crate::solution::Solution;
+
+pub mod generate;
 pub mod input;
+pub mod rebalance;
+pub mod stats;
 
-#[derive(Debug, Ord, Eq)]
-struct Elf {
-    #[allow(dead_code)]
+/// An elf and the food they carry. Elves compare by calories alone.
+#[derive(Debug, Clone, Eq)]
+pub struct Elf {
+    /// Position in the list, counting from 0
     pub num: usize,
+    /// The calories of every food item, in the order they're listed
+    pub items: Vec<i32>,
+    /// The calories of all the items together
     pub calories: i32,
 }
 
 impl Elf {
     pub fn new(num: usize) -> Self {
-        Self { num, calories: 0 }
+        Self {
+            num,
+            items: Vec::new(),
+            calories: 0,
+        }
     }
 
     pub fn eat(&mut self, calories: i32) {
+        self.items.push(calories);
         self.calories += calories
     }
 }
@@ -25,66 +46,121 @@ impl PartialEq for Elf {
 
 impl PartialOrd for Elf {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.calories.partial_cmp(&other.calories)
+        Some(self.cmp(other))
     }
 }
 
-fn input_to_elves(input: &'static str) -> Vec<Elf> {
+impl Ord for Elf {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.calories.cmp(&other.calories)
+    }
+}
+
+/// Reads one calorie count per line, with a blank line between elves
+pub fn input_to_elves(input: impl BufRead) -> Result<Vec<Elf>, ParseError> {
     let mut elf_index: usize = 0;
     let mut elves: Vec<Elf> = vec![Elf::new(elf_index)];
 
-    for line in input.lines() {
-        if line.trim() == "" {
+    let mut lines = LineReader::new(Day1::DAY, input);
+
+    while let Some(line) = lines.next_line()? {
+        if line.is_blank() {
             elf_index += 1;
             elves.push(Elf::new(elf_index));
         } else {
             let current_elf = elves
                 .get_mut(elf_index)
                 .expect("You forgot to push a new elf to this index");
-            let calories = line.trim().parse::<i32>().expect("Not a number");
+            let error = |message| {
+                FragmentError::new(line.text, message).at(Day1::DAY, line.index, line.raw)
+            };
+
+            let calories = match line.text.parse::<i32>() {
+                Ok(calories) if calories < 0 => return Err(error("Calories cannot be negative")),
+                Ok(calories) => calories,
+                Err(_) => return Err(error("Not a number")),
+            };
+
+            // Part 2 adds up a few elves in an i64, so only each elf's own
+            // total has to fit in an i32
+            if current_elf.calories.checked_add(calories).is_none() {
+                return Err(error("Too many calories for one elf"));
+            }
+
             current_elf.eat(calories);
         }
     }
 
-    elves
+    Ok(elves)
 }
 
-fn find_best_fed_elf(elves: &Vec<Elf>) -> &Elf {
-    let mut most_cals: i32 = 0;
-    let mut most_cals_index: usize = 0;
-    elves.iter().enumerate().for_each(|(idx, elf)| {
-        if elf.calories > most_cals {
-            most_cals = elf.calories;
-            most_cals_index = idx;
+/// The `k` elves carrying the most calories, most first, or every elf if there
+/// are fewer. Elves carrying the same amount keep the order they're listed in.
+///
+/// Only the best `k` seen so far are kept in a heap, so this takes
+/// O(n log k) rather than sorting every elf.
+pub fn top_k(elves: &[Elf], k: usize) -> Vec<&Elf> {
+    if k == 0 {
+        return Vec::new();
+    }
+
+    // A min-heap of the best so far, so the weakest is the one to replace.
+    // Between equal elves the later one is weaker.
+    let mut best = BinaryHeap::with_capacity(k + 1);
+
+    for (idx, elf) in elves.iter().enumerate() {
+        best.push(Reverse((elf.calories, Reverse(idx))));
+
+        if best.len() > k {
+            best.pop();
         }
-    });
-    elves.get(most_cals_index).unwrap()
+    }
+
+    best.into_sorted_vec()
+        .into_iter()
+        .map(|Reverse((_, Reverse(idx)))| &elves[idx])
+        .collect()
+}
+
+/// The elf carrying the most calories, or the first elf if none carry any.
+/// Panics if there are no elves, which parsing never produces.
+pub fn find_best_fed_elf(elves: &[Elf]) -> &Elf {
+    top_k(elves, 1)
+        .first()
+        .expect("There should be at least one elf")
 }
 
-pub fn day_1_part_1(input: &'static str) -> i32 {
-    let elves = input_to_elves(input);
+pub struct Day1;
 
-    let best_fed_elf = find_best_fed_elf(&elves);
+impl Solution for Day1 {
+    type Parsed = Vec<Elf>;
 
-    best_fed_elf.calories
-}
+    const DAY: u8 = 1;
+    const INPUT: &'static str = input::INPUT_DAY_1;
 
-pub fn day_1_part_2(input: &'static str) -> i32 {
-    let mut elves = input_to_elves(input);
-    elves.sort();
+    fn read(&self, input: &mut dyn BufRead) -> Result<Self::Parsed, ParseError> {
+        input_to_elves(input)
+    }
+
+    fn part_1(&self, elves: &Self::Parsed) -> Answer {
+        let best_fed_elf = find_best_fed_elf(elves);
 
-    elves.reverse();
+        best_fed_elf.calories.into()
+    }
 
-    let top_3_cals = elves
-        .iter()
-        .take(3)
-        .fold(0, |acc, elem| acc + elem.calories);
+    fn part_2(&self, elves: &Self::Parsed) -> Answer {
+        let top_3_cals = top_k(elves, 3)
+            .iter()
+            .fold(0, |acc, elem| acc + elem.calories as i64);
 
-    top_3_cals
+        top_3_cals.into()
+    }
 }
 
 #[cfg(test)]
 mod test {
+    use super::*;
+
     static TEST_INPUT: &str = r#"
     1000
     2000
@@ -104,15 +180,125 @@ mod test {
 
     #[test]
     fn part_1() {
-        let calories = super::day_1_part_1(TEST_INPUT);
+        let calories = Day1.solve_part_1(TEST_INPUT);
 
-        assert_eq!(calories, 24000);
+        assert_eq!(calories, Answer::from(24000));
     }
 
     #[test]
     fn part_2() {
-        let calories = super::day_1_part_2(TEST_INPUT);
+        let calories = Day1.solve_part_2(TEST_INPUT);
+
+        assert_eq!(calories, Answer::from(45000));
+    }
+
+    #[test]
+    fn test_top_k() {
+        let elves = Day1.parse(TEST_INPUT).unwrap();
+        let top = |k| {
+            top_k(&elves, k)
+                .iter()
+                .map(|elf| (elf.num, elf.calories))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(top(2), vec![(3, 24000), (2, 11000)]);
+        assert_eq!(
+            top(5),
+            vec![(3, 24000), (2, 11000), (4, 10000), (0, 6000), (1, 4000)]
+        );
+        assert_eq!(top(9), top(5));
+        assert!(top(0).is_empty());
+    }
+
+    #[test]
+    fn test_top_k_ties() {
+        let elves = input_to_elves("5\n\n7\n\n5\n\n7\n\n1\n".as_bytes()).unwrap();
+        let nums = top_k(&elves, 3)
+            .iter()
+            .map(|elf| elf.num)
+            .collect::<Vec<_>>();
+
+        assert_eq!(nums, vec![1, 3, 0]);
+        assert_eq!(find_best_fed_elf(&elves).num, 1);
+
+        let hungry = input_to_elves("0\n\n0\n".as_bytes()).unwrap();
+
+        assert_eq!(find_best_fed_elf(&hungry).num, 0);
+    }
+
+    #[test]
+    fn test_top_k_matches_sorting() {
+        crate::property::check(generate::generate, |snacks| {
+            let elves = Day1.parse(&generate::serialize(snacks)).unwrap();
+
+            let mut sorted = elves.iter().collect::<Vec<_>>();
+            sorted.sort_by_key(|elf| (Reverse(elf.calories), elf.num));
+
+            for k in 0..=elves.len() {
+                let nums = |elves: &[&Elf]| elves.iter().map(|elf| elf.num).collect::<Vec<_>>();
+
+                assert_eq!(nums(&top_k(&elves, k)), nums(&sorted[..k]));
+            }
+        });
+    }
+
+    #[test]
+    fn test_invalid_calories() {
+        let err = input_to_elves("1000\n\n  20o0\n".as_bytes()).unwrap_err();
+
+        assert_eq!(err, ParseError::new(1, 3, 3, "20o0", "Not a number"));
+
+        let err = input_to_elves("1000\n-20\n".as_bytes()).unwrap_err();
+
+        assert_eq!(err.message, "Calories cannot be negative");
+
+        let err = input_to_elves("2147483647\n1\n".as_bytes()).unwrap_err();
+
+        assert_eq!(
+            err,
+            ParseError::new(1, 2, 1, "1", "Too many calories for one elf")
+        );
+    }
+
+    #[test]
+    fn test_totals_past_i32() {
+        // Together the elves carry far more than an i32 holds
+        let input = "100000000\n\n".repeat(20);
+        let elves = Day1.parse(&input).unwrap();
+
+        assert_eq!(Day1.part_1(&elves), Answer::from(100_000_000));
+        assert_eq!(Day1.part_2(&elves), Answer::from(300_000_000i64));
+
+        let input = "2000000000\n\n2000000000\n\n2000000000\n";
+
+        assert_eq!(Day1.solve_part_2(input), Answer::from(6_000_000_000i64));
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        crate::property::check(generate::generate, |elves| {
+            let parsed = Day1.parse(&generate::serialize(elves)).unwrap();
+
+            let expected = elves
+                .iter()
+                .enumerate()
+                .map(|(num, snacks)| (num, snacks.iter().sum::<i32>()))
+                .collect::<Vec<_>>();
+
+            let actual = parsed
+                .iter()
+                .map(|elf| (elf.num, elf.calories))
+                .collect::<Vec<_>>();
+
+            assert_eq!(actual, expected);
+
+            let items = parsed
+                .iter()
+                .map(|elf| elf.items.clone())
+                .collect::<Vec<_>>();
 
-        assert_eq!(calories, 45000);
+            assert_eq!(&items, elves);
+        });
     }
 }
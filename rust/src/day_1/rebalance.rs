@@ -0,0 +1,362 @@
+//! Shares the food items out again so that the elf carrying the most carries
+//! as little as possible. That is multiway number partitioning, which is
+//! NP-hard, so only small inputs are solved exactly and larger ones get the
+//! longest-processing-time heuristic, which is never worse than 4/3 of the best.
+
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use super::Elf;
+use crate::table::{format_table, Align};
+
+/// Inputs with at most this many items are solved exactly
+pub const MAX_EXACT_ITEMS: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    /// Branch and bound over every way to share the items
+    Exact,
+    /// Each item in turn, largest first, goes to whoever carries least
+    Greedy,
+}
+
+/// The items one elf carries after rebalancing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Load {
+    /// Largest first
+    pub items: Vec<i32>,
+    pub calories: i64,
+}
+
+/// How the items are shared out, heaviest load first
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan {
+    pub method: Method,
+    pub loads: Vec<Load>,
+}
+
+impl Plan {
+    /// The most any elf carries, which the plan keeps as low as it can
+    pub fn max_load(&self) -> i64 {
+        self.loads.first().map_or(0, |load| load.calories)
+    }
+}
+
+/// No plan can put less on its heaviest elf than this: the largest item, or
+/// the total shared evenly
+pub fn lower_bound(items: &[i32], elves: usize) -> i64 {
+    let total = items.iter().map(|item| *item as i64).sum::<i64>();
+    let largest = items.iter().copied().max().unwrap_or(0) as i64;
+
+    largest.max((total + elves as i64 - 1) / elves as i64)
+}
+
+/// Every item the elves carry, shared between `elf_count` elves. Solved
+/// exactly when there are few enough items, and greedily otherwise.
+///
+/// Panics if `elf_count` is 0.
+pub fn rebalance(elves: &[Elf], elf_count: usize) -> Plan {
+    let items = elves
+        .iter()
+        .flat_map(|elf| elf.items.iter().copied())
+        .collect::<Vec<_>>();
+
+    if items.len() <= MAX_EXACT_ITEMS {
+        exact_partition(&items, elf_count)
+    } else {
+        greedy_partition(&items, elf_count)
+    }
+}
+
+/// Gives each item, largest first, to the elf carrying least so far, in
+/// O(n log n)
+///
+/// Panics if `elves` is 0.
+pub fn greedy_partition(items: &[i32], elves: usize) -> Plan {
+    assert!(elves > 0, "Items can't be shared between no elves");
+
+    let items = sorted_largest_first(items);
+    let assignment = lpt_assignment(&items, elves);
+
+    to_plan(Method::Greedy, &items, &assignment, elves)
+}
+
+/// The best possible plan, found by trying every way to share the items and
+/// cutting short any that can't beat the best so far. Takes exponential time
+/// in the worst case, so is only meant for small inputs.
+///
+/// Panics if `elves` is 0.
+pub fn exact_partition(items: &[i32], elves: usize) -> Plan {
+    assert!(elves > 0, "Items can't be shared between no elves");
+
+    let items = sorted_largest_first(items);
+
+    // Starting from the greedy plan means only better ones need searching for
+    let mut search = Search {
+        items: &items,
+        lower_bound: lower_bound(&items, elves),
+        loads: vec![0; elves],
+        assignment: vec![0; items.len()],
+        best_assignment: lpt_assignment(&items, elves),
+        best: 0,
+    };
+
+    search.best = max_load(&items, &search.best_assignment, elves);
+    search.assign(0);
+
+    to_plan(Method::Exact, &items, &search.best_assignment, elves)
+}
+
+struct Search<'a> {
+    /// Largest first, so the big decisions are made early and pruning bites
+    items: &'a [i32],
+    lower_bound: i64,
+    loads: Vec<i64>,
+    /// The elf each item goes to
+    assignment: Vec<usize>,
+    best_assignment: Vec<usize>,
+    best: i64,
+}
+
+impl Search<'_> {
+    fn assign(&mut self, idx: usize) {
+        let Some(item) = self.items.get(idx).map(|item| *item as i64) else {
+            let max = self.loads.iter().copied().max().unwrap_or(0);
+
+            if max < self.best {
+                self.best = max;
+                self.best_assignment.clone_from(&self.assignment);
+            }
+
+            return;
+        };
+
+        for elf in 0..self.loads.len() {
+            // Nothing can beat a plan that reaches the lower bound
+            if self.best == self.lower_bound {
+                return;
+            }
+
+            // Elves carrying the same are interchangeable, so only one needs trying
+            if self.loads[..elf].contains(&self.loads[elf]) {
+                continue;
+            }
+
+            if self.loads[elf] + item >= self.best {
+                continue;
+            }
+
+            self.loads[elf] += item;
+            self.assignment[idx] = elf;
+            self.assign(idx + 1);
+            self.loads[elf] -= item;
+        }
+    }
+}
+
+fn sorted_largest_first(items: &[i32]) -> Vec<i32> {
+    let mut items = items.to_vec();
+    items.sort_unstable_by_key(|item| Reverse(*item));
+
+    items
+}
+
+/// Which elf each item goes to when the least loaded elf always takes the next
+fn lpt_assignment(items: &[i32], elves: usize) -> Vec<usize> {
+    let mut loads = (0..elves)
+        .map(|elf| Reverse((0, elf)))
+        .collect::<BinaryHeap<_>>();
+
+    items
+        .iter()
+        .map(|item| {
+            let Reverse((load, elf)) = loads.pop().expect("There is at least one elf");
+            loads.push(Reverse((load + *item as i64, elf)));
+
+            elf
+        })
+        .collect()
+}
+
+fn max_load(items: &[i32], assignment: &[usize], elves: usize) -> i64 {
+    let mut loads = vec![0; elves];
+
+    for (item, elf) in items.iter().zip(assignment) {
+        loads[*elf] += *item as i64;
+    }
+
+    loads.into_iter().max().unwrap_or(0)
+}
+
+fn to_plan(method: Method, items: &[i32], assignment: &[usize], elves: usize) -> Plan {
+    let mut loads = vec![
+        Load {
+            items: Vec::new(),
+            calories: 0,
+        };
+        elves
+    ];
+
+    // Items are already largest first, so each load's are too
+    for (item, elf) in items.iter().zip(assignment) {
+        loads[*elf].items.push(*item);
+        loads[*elf].calories += *item as i64;
+    }
+
+    loads.sort_by_key(|load| Reverse(load.calories));
+
+    Plan { method, loads }
+}
+
+/// The report `advent day1 rebalance` prints: how the plan was found, how
+/// close it is to the lower bound and what each elf ends up carrying
+pub fn format_plan(plan: &Plan) -> String {
+    let items = plan
+        .loads
+        .iter()
+        .flat_map(|load| load.items.iter().copied())
+        .collect::<Vec<_>>();
+
+    let method = match plan.method {
+        Method::Exact => "exactly",
+        Method::Greedy => "greedily",
+    };
+
+    let summary = format!(
+        "{} items shared {} between {} elves: the most any carries is {} (no plan can do better than {})",
+        items.len(),
+        method,
+        plan.loads.len(),
+        plan.max_load(),
+        lower_bound(&items, plan.loads.len())
+    );
+
+    let rows = plan
+        .loads
+        .iter()
+        .enumerate()
+        .map(|(elf, load)| {
+            vec![
+                elf.to_string(),
+                load.items.len().to_string(),
+                load.calories.to_string(),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let table = format_table(
+        &[
+            ("Elf", Align::Right),
+            ("Items", Align::Right),
+            ("Calories", Align::Right),
+        ],
+        &rows,
+    );
+
+    format!("{}\n\n{}", summary, table)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::day_1::{generate, input_to_elves};
+
+    static TEST_INPUT: &str =
+        "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+
+    fn elves_items(elves: &[Elf]) -> Vec<i32> {
+        elves.iter().flat_map(|elf| elf.items.clone()).collect()
+    }
+
+    fn loads(plan: &Plan) -> Vec<i64> {
+        plan.loads.iter().map(|load| load.calories).collect()
+    }
+
+    #[test]
+    fn test_rebalance() {
+        let elves = input_to_elves(TEST_INPUT.as_bytes()).unwrap();
+        let plan = rebalance(&elves, 5);
+
+        // 55000 shared five ways, with the 10000 item alone
+        assert_eq!(plan.method, Method::Exact);
+        assert_eq!(loads(&plan), vec![11000, 11000, 11000, 11000, 11000]);
+
+        // Sharing 55000 three ways can't be even when every item is in thousands
+        let plan = rebalance(&elves, 3);
+
+        assert_eq!(lower_bound(&elves_items(&elves), 3), 18334);
+        assert_eq!(plan.max_load(), 19000);
+    }
+
+    #[test]
+    fn test_exact_beats_greedy() {
+        // Greedy splits the threes up and ends with 7 against 5, but 6 each is possible
+        let items = [3, 3, 2, 2, 2];
+        let greedy = greedy_partition(&items, 2);
+        let exact = exact_partition(&items, 2);
+
+        assert_eq!(loads(&greedy), vec![7, 5]);
+        assert_eq!(loads(&exact), vec![6, 6]);
+        assert_eq!(exact.loads[0].items.len() + exact.loads[1].items.len(), 5);
+    }
+
+    #[test]
+    fn test_more_elves_than_items() {
+        let plan = exact_partition(&[5, 9], 4);
+
+        assert_eq!(loads(&plan), vec![9, 5, 0, 0]);
+        assert_eq!(loads(&greedy_partition(&[5, 9], 4)), vec![9, 5, 0, 0]);
+        assert_eq!(loads(&exact_partition(&[], 2)), vec![0, 0]);
+    }
+
+    #[test]
+    fn test_plans_keep_every_item() {
+        crate::property::check(generate::generate, |snacks| {
+            let items = snacks.iter().flatten().copied().collect::<Vec<_>>();
+            let elves = snacks.len() % 5 + 1;
+
+            let greedy = greedy_partition(&items, elves);
+            let exact = exact_partition(&items[..items.len().min(12)], elves);
+
+            for (plan, items) in [
+                (&greedy, &items[..]),
+                (&exact, &items[..items.len().min(12)]),
+            ] {
+                let mut shared = plan
+                    .loads
+                    .iter()
+                    .flat_map(|load| load.items.iter().copied())
+                    .collect::<Vec<_>>();
+                shared.sort_unstable();
+
+                let mut expected = items.to_vec();
+                expected.sort_unstable();
+
+                assert_eq!(shared, expected);
+                assert!(plan.max_load() >= lower_bound(items, elves));
+            }
+
+            // Greedy is never more than a third worse than the best
+            let small_greedy = greedy_partition(&items[..items.len().min(12)], elves);
+
+            assert!(exact.max_load() <= small_greedy.max_load());
+            assert!(3 * small_greedy.max_load() <= 4 * exact.max_load());
+        });
+    }
+
+    #[test]
+    fn test_format_plan() {
+        let plan = exact_partition(&[3, 3, 2, 2, 2], 2);
+
+        assert_eq!(
+            format_plan(&plan),
+            [
+                "5 items shared exactly between 2 elves: the most any carries is 6 (no plan can do better than 6)",
+                "",
+                "Elf  Items  Calories",
+                "  0      2         6",
+                "  1      3         6",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -0,0 +1,361 @@
+//! Statistics over the food the elves carry, for planning that needs more than
+//! the puzzle's top totals.
+
+use super::Elf;
+use crate::table::{format_table, Align};
+
+/// How many ranges `advent day1 stats` splits the totals into
+pub const DEFAULT_HISTOGRAM_BUCKETS: usize = 10;
+
+/// The most outliers a report names before summing up the rest
+const LISTED_OUTLIERS: usize = 10;
+
+/// The widest bar in a report's histogram
+const BAR_WIDTH: usize = 40;
+
+/// The calorie totals of a group of elves, sorted once so that any percentile
+/// can be read off
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalorieStats {
+    /// Every elf's total, smallest first
+    totals: Vec<i32>,
+}
+
+impl CalorieStats {
+    /// `None` if there are no elves to describe
+    pub fn new(elves: &[Elf]) -> Option<Self> {
+        if elves.is_empty() {
+            return None;
+        }
+
+        let mut totals = elves.iter().map(|elf| elf.calories).collect::<Vec<_>>();
+        totals.sort_unstable();
+
+        Some(Self { totals })
+    }
+
+    pub fn count(&self) -> usize {
+        self.totals.len()
+    }
+
+    pub fn min(&self) -> i32 {
+        self.totals[0]
+    }
+
+    pub fn max(&self) -> i32 {
+        self.totals[self.totals.len() - 1]
+    }
+
+    pub fn mean(&self) -> f64 {
+        let sum = self.totals.iter().map(|total| *total as i64).sum::<i64>();
+
+        sum as f64 / self.count() as f64
+    }
+
+    pub fn median(&self) -> f64 {
+        self.percentile(50.0)
+    }
+
+    /// The total that `percent` of the elves carry no more than, interpolating
+    /// between the two nearest elves. Percentages outside 0 to 100 are treated
+    /// as the nearest end.
+    pub fn percentile(&self, percent: f64) -> f64 {
+        let rank = percent.clamp(0.0, 100.0) / 100.0 * (self.count() - 1) as f64;
+        let below = rank.floor() as usize;
+        let above = rank.ceil() as usize;
+
+        let low = self.totals[below] as f64;
+        let high = self.totals[above] as f64;
+
+        low + (high - low) * (rank - below as f64)
+    }
+
+    /// Totals outside this range are outliers, being more than one and a half
+    /// interquartile ranges beyond the quartiles (Tukey's fences)
+    pub fn fences(&self) -> (f64, f64) {
+        let lower_quartile = self.percentile(25.0);
+        let upper_quartile = self.percentile(75.0);
+        let reach = 1.5 * (upper_quartile - lower_quartile);
+
+        (lower_quartile - reach, upper_quartile + reach)
+    }
+
+    pub fn is_outlier(&self, calories: i32) -> bool {
+        let (low, high) = self.fences();
+        let calories = calories as f64;
+
+        calories < low || calories > high
+    }
+
+    /// Splits the range from the smallest total to the largest into `buckets`
+    /// ranges, counting the elves in each. Their widths differ by at most one
+    /// calorie. Fewer buckets come back when the range holds fewer calorie
+    /// values than that, one for each value.
+    pub fn histogram(&self, buckets: usize) -> Vec<Bucket> {
+        if buckets == 0 {
+            return Vec::new();
+        }
+
+        let min = self.min() as i64;
+        let span = self.max() as i64 - min + 1;
+        let buckets = (buckets as i64).min(span);
+
+        // Totals are never negative, so `span * idx` stays well inside an i64
+        let start = |idx: i64| min + span * idx / buckets;
+
+        let mut histogram = (0..buckets)
+            .map(|idx| Bucket {
+                start: start(idx) as i32,
+                end: (start(idx + 1) - 1) as i32,
+                count: 0,
+            })
+            .collect::<Vec<_>>();
+
+        for total in &self.totals {
+            let idx = histogram.partition_point(|bucket| bucket.end < *total);
+            histogram[idx].count += 1;
+        }
+
+        histogram
+    }
+}
+
+/// The elves carrying from `start` to `end` calories, both included
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bucket {
+    pub start: i32,
+    pub end: i32,
+    pub count: usize,
+}
+
+/// How many food items each elf carries, in the order they're listed
+pub fn item_counts(elves: &[Elf]) -> Vec<usize> {
+    elves.iter().map(|elf| elf.items.len()).collect()
+}
+
+/// The elves whose totals are outliers among `elves`, in the order they're
+/// listed
+pub fn outliers(elves: &[Elf]) -> Vec<&Elf> {
+    match CalorieStats::new(elves) {
+        Some(stats) => outliers_by(elves, &stats),
+        None => Vec::new(),
+    }
+}
+
+/// The elves whose totals are outliers by `stats`, which should describe them
+fn outliers_by<'a>(elves: &'a [Elf], stats: &CalorieStats) -> Vec<&'a Elf> {
+    elves
+        .iter()
+        .filter(|elf| stats.is_outlier(elf.calories))
+        .collect()
+}
+
+/// The report `advent day1 stats` prints: how much food there is, how the
+/// totals are spread, a histogram of them and any outliers
+pub fn format_report(elves: &[Elf], buckets: usize) -> String {
+    let Some(stats) = CalorieStats::new(elves) else {
+        return "No elves".to_string();
+    };
+
+    let counts = item_counts(elves);
+    let items = counts.iter().sum::<usize>();
+
+    let summary = format!(
+        "{} elves carrying {} food items ({} to {} each, {:.1} on average)",
+        stats.count(),
+        items,
+        counts.iter().min().unwrap_or(&0),
+        counts.iter().max().unwrap_or(&0),
+        items as f64 / stats.count() as f64
+    );
+
+    let spread = [
+        ("mean", format!("{:.1}", stats.mean())),
+        ("min", stats.min().to_string()),
+        ("p10", format!("{:.1}", stats.percentile(10.0))),
+        ("p25", format!("{:.1}", stats.percentile(25.0))),
+        ("median", format!("{:.1}", stats.median())),
+        ("p75", format!("{:.1}", stats.percentile(75.0))),
+        ("p90", format!("{:.1}", stats.percentile(90.0))),
+        ("max", stats.max().to_string()),
+    ]
+    .into_iter()
+    .map(|(name, value)| vec![name.to_string(), value])
+    .collect::<Vec<_>>();
+
+    let histogram = stats.histogram(buckets);
+    let fullest = histogram
+        .iter()
+        .map(|bucket| bucket.count)
+        .max()
+        .unwrap_or(0);
+
+    let bars = histogram
+        .iter()
+        .map(|bucket| {
+            vec![
+                bucket.start.to_string(),
+                bucket.end.to_string(),
+                bucket.count.to_string(),
+                "#".repeat((bucket.count * BAR_WIDTH).div_ceil(fullest)),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    [
+        summary,
+        format_table(&[("", Align::Left), ("Calories", Align::Right)], &spread),
+        format_table(
+            &[
+                ("From", Align::Right),
+                ("To", Align::Right),
+                ("Elves", Align::Right),
+                ("", Align::Left),
+            ],
+            &bars,
+        ),
+        format_outliers(elves, &stats),
+    ]
+    .join("\n\n")
+}
+
+fn format_outliers(elves: &[Elf], stats: &CalorieStats) -> String {
+    let (low, high) = stats.fences();
+    let outliers = outliers_by(elves, stats);
+
+    if outliers.is_empty() {
+        return format!("No outliers outside {:.1} to {:.1}", low, high);
+    }
+
+    let mut listed = outliers
+        .iter()
+        .take(LISTED_OUTLIERS)
+        .map(|elf| format!("elf {} carrying {}", elf.num, elf.calories))
+        .collect::<Vec<_>>();
+
+    if outliers.len() > LISTED_OUTLIERS {
+        listed.push(format!("and {} more", outliers.len() - LISTED_OUTLIERS));
+    }
+
+    format!(
+        "Outliers outside {:.1} to {:.1}: {}",
+        low,
+        high,
+        listed.join(", ")
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::day_1::input_to_elves;
+
+    static TEST_INPUT: &str =
+        "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+
+    fn elves() -> Vec<Elf> {
+        input_to_elves(TEST_INPUT.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn test_calorie_stats() {
+        let stats = CalorieStats::new(&elves()).unwrap();
+
+        assert_eq!((stats.count(), stats.min(), stats.max()), (5, 4000, 24000));
+        assert_eq!(stats.mean(), 11000.0);
+        assert_eq!(stats.median(), 10000.0);
+        assert_eq!(stats.percentile(25.0), 6000.0);
+        assert_eq!(stats.percentile(90.0), 18800.0);
+        assert_eq!(stats.percentile(150.0), 24000.0);
+        assert_eq!(stats.fences(), (-1500.0, 18500.0));
+
+        assert_eq!(CalorieStats::new(&[]), None);
+    }
+
+    #[test]
+    fn test_histogram() {
+        let stats = CalorieStats::new(&elves()).unwrap();
+        let bucket = |start, end, count| Bucket { start, end, count };
+
+        assert_eq!(
+            stats.histogram(4),
+            vec![
+                bucket(4000, 8999, 2),
+                bucket(9000, 13999, 2),
+                bucket(14000, 18999, 0),
+                bucket(19000, 24000, 1),
+            ]
+        );
+
+        assert!(stats.histogram(0).is_empty());
+
+        // Ten calorie values split six ways still give six buckets
+        let spread = input_to_elves("1\n\n10\n".as_bytes()).unwrap();
+
+        assert_eq!(
+            CalorieStats::new(&spread).unwrap().histogram(6),
+            vec![
+                bucket(1, 1, 1),
+                bucket(2, 3, 0),
+                bucket(4, 5, 0),
+                bucket(6, 6, 0),
+                bucket(7, 8, 0),
+                bucket(9, 10, 1),
+            ]
+        );
+
+        // Every elf carrying the same leaves a single bucket
+        let same = input_to_elves("7\n\n7\n".as_bytes()).unwrap();
+
+        assert_eq!(
+            CalorieStats::new(&same).unwrap().histogram(10),
+            vec![bucket(7, 7, 2)]
+        );
+    }
+
+    #[test]
+    fn test_items_and_outliers() {
+        let elves = elves();
+
+        assert_eq!(item_counts(&elves), vec![3, 1, 2, 3, 1]);
+        assert_eq!(elves[3].items, vec![7000, 8000, 9000]);
+
+        let outliers = outliers(&elves)
+            .iter()
+            .map(|elf| elf.num)
+            .collect::<Vec<_>>();
+
+        assert_eq!(outliers, vec![3]);
+    }
+
+    #[test]
+    fn test_format_report() {
+        assert_eq!(
+            format_report(&elves(), 4),
+            [
+                "5 elves carrying 10 food items (1 to 3 each, 2.0 on average)",
+                "",
+                "        Calories",
+                "mean     11000.0",
+                "min         4000",
+                "p10       4800.0",
+                "p25       6000.0",
+                "median   10000.0",
+                "p75      11000.0",
+                "p90      18800.0",
+                "max        24000",
+                "",
+                " From     To  Elves",
+                " 4000   8999      2  ########################################",
+                " 9000  13999      2  ########################################",
+                "14000  18999      0",
+                "19000  24000      1  ####################",
+                "",
+                "Outliers outside -1500.0 to 18500.0: elf 3 carrying 24000",
+            ]
+            .join("\n")
+        );
+
+        assert_eq!(format_report(&[], 4), "No elves");
+    }
+}
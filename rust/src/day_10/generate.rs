@@ -0,0 +1,66 @@
+use std::{
+    collections::VecDeque,
+    io::{self, Write},
+};
+
+use super::Operation;
+use crate::rng::Rng;
+
+/// A program long enough to fill the screen, sometimes running a little past it
+pub fn generate(rng: &mut Rng) -> VecDeque<Operation> {
+    let mut operations = VecDeque::new();
+    let mut cycles = 0;
+    let extra = rng.count(0..=5);
+
+    while cycles < 240 + extra {
+        let operation = match rng.bool() {
+            // Negative and multi-digit values both turn up in real programs
+            true => Operation::Addx(rng.range(-40..=40) as i32),
+            false => Operation::Noop,
+        };
+
+        cycles += operation.cycles_requires() as usize;
+        operations.push_back(operation);
+    }
+
+    operations
+}
+
+pub fn serialize(operations: &VecDeque<Operation>) -> String {
+    operations
+        .iter()
+        .map(|operation| match operation {
+            Operation::Addx(value) => format!("addx {}\n", value),
+            Operation::Noop => "noop\n".to_string(),
+        })
+        .collect()
+}
+
+/// A program `size` instructions long, or long enough to fill the screen if
+/// that's longer. Each `addx` moves the register somewhere near 0, so the
+/// signal strength only grows with the cycle.
+pub fn stress(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let mut register = 1;
+    let mut cycles = 0;
+    let mut written = 0;
+
+    while written < size || cycles < 240 {
+        match rng.bool() {
+            true => {
+                let next = rng.range(-20..=20);
+
+                writeln!(out, "addx {}", next - register)?;
+                register = next;
+                cycles += 2;
+            }
+            false => {
+                writeln!(out, "noop")?;
+                cycles += 1;
+            }
+        }
+
+        written += 1;
+    }
+
+    Ok(())
+}
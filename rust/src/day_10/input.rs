@@ -1,4 +1,4 @@
-pub static INPUT_DAY_10: &'static str = r#"
+pub static INPUT_DAY_10: &str = r#"
 noop
 addx 5
 addx -2
@@ -147,7 +147,7 @@ addx -24
 noop
 "#;
 
-
+#[cfg(test)]
 pub static LONG_TEST_INPUT: &str = r#"
 addx 15
 addx -11
@@ -295,4 +295,4 @@ addx -11
 noop
 noop
 noop
-"#;
\ No newline at end of file
+"#;
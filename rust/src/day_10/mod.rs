@@ -1,15 +1,29 @@
-use std::collections::VecDeque;
+//! Day 10: Cathode-Ray Tube
 
+use std::{collections::VecDeque, io::BufRead};
+
+use crate::answer::Answer;
+use crate::error::{FragmentError, ParseError};
+use crate::normalize::LineReader;
+use crate::solution::Solution;
+
+pub mod generate;
 pub mod input;
 
 const DEBUG_MODE: bool = false;
 const PRINT_SIGNAL_STRENGTH: bool = false;
 
-struct CRT {
+/// The last cycle part 1 reads the signal strength at
+const LAST_SIGNAL_CYCLE: i32 = 220;
+
+/// The 40 by 6 screen the CPU's register moves a sprite across
+#[allow(clippy::upper_case_acronyms)]
+pub struct CRT {
     pixels: Vec<Vec<char>>,
 }
 
 impl CRT {
+    /// Lights the pixel in column `x` of row `y`
     pub fn draw(&mut self, x: usize, y: usize) {
         if x > 39 {
             panic!("Draw outside X bounds {}", x);
@@ -21,6 +35,7 @@ impl CRT {
         self.pixels[y][x] = '#';
     }
 
+    /// A screen with every pixel dark
     pub fn new() -> Self {
         let line = vec!['.'; 40];
 
@@ -29,27 +44,30 @@ impl CRT {
         }
     }
 
-    pub fn print_it(&self) {
-        for row in &self.pixels {
-            for pixel in row {
-                print!("{}", pixel);
-            }
-            print!("\n");
-        }
+    /// Rows of `#` for lit pixels and `.` for dark ones
+    pub fn pixels(&self) -> &[Vec<char>] {
+        &self.pixels
+    }
+
+    /// The screen as one line of text per row
+    pub fn rows(&self) -> Vec<String> {
+        self.pixels.iter().map(|row| row.iter().collect()).collect()
     }
 }
 
+/// A CPU with one register that runs one instruction at a time
 #[derive(Debug)]
-struct CPU {
+#[allow(clippy::upper_case_acronyms)]
+pub struct CPU {
     register: i32,
     active_operation: Option<ActiveOperation>,
     cycle_count: i32,
     signal_strength_history: Vec<i32>,
 }
 
-#[derive(Debug, PartialEq)]
-
-enum Operation {
+/// An instruction in the program
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operation {
     Addx(i32),
     Noop,
 }
@@ -70,6 +88,7 @@ impl ActiveOperation {
 }
 
 impl Operation {
+    /// How many cycles the instruction takes to finish
     pub fn cycles_requires(&self) -> i32 {
         match self {
             Operation::Addx(_) => 2,
@@ -78,7 +97,14 @@ impl Operation {
     }
 }
 
+impl Default for CRT {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl CPU {
+    /// A CPU at its first cycle with the register set to 1
     pub fn new() -> Self {
         Self {
             register: 1,
@@ -88,18 +114,33 @@ impl CPU {
         }
     }
 
+    /// The value of the X register
+    pub fn register(&self) -> i32 {
+        self.register
+    }
+
+    /// The cycle the CPU is currently in, starting from 1
+    pub fn cycle_count(&self) -> i32 {
+        self.cycle_count
+    }
+
+    /// The row of the screen being drawn during this cycle
     pub fn current_screen_row(&self) -> usize {
         ((self.cycle_count - 1) / 40) as usize
     }
 
+    /// The column of the screen being drawn during this cycle
     pub fn current_screen_column(&self) -> usize {
         ((self.cycle_count - 1) % 40) as usize
     }
 
+    /// The register times the current cycle
     pub fn signal_strength(&self) -> i32 {
         self.register * self.cycle_count
     }
 
+    /// The signal strength during `cycle`, which must be one the CPU has
+    /// finished
     pub fn signal_strength_at_cycle(&self, cycle: usize) -> i32 {
         let strength = self.signal_strength_history.get(cycle - 2);
         match strength {
@@ -110,6 +151,7 @@ impl CPU {
         }
     }
 
+    /// Starts running `operation`; the previous one must have finished
     pub fn set_active_operation(&mut self, operation: Operation) {
         if DEBUG_MODE {
             println!("Setting new operation {:?}", operation);
@@ -123,6 +165,7 @@ impl CPU {
         self.active_operation = Some(ActiveOperation::new(operation));
     }
 
+    /// Advances one cycle, finishing the active operation if it's done
     pub fn process_operation(&mut self) {
         self.cycle_count += 1;
 
@@ -137,7 +180,6 @@ impl CPU {
                         println!("Incrementing current operation cycle");
                     }
                     active_operation.current_cycle += 1;
-                    return;
                 } else if active_operation.current_cycle
                     == active_operation.operation.cycles_requires()
                 {
@@ -166,41 +208,84 @@ impl CPU {
     }
 }
 
-fn parse_input(input: &str) -> VecDeque<Operation> {
+impl Default for CPU {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn parse_operation(trimmed_line: &str) -> Result<Operation, FragmentError<'_>> {
+    let split = trimmed_line.split_once(" ");
+
+    match split {
+        Some(split) => match split {
+            ("addx", val) => val
+                .parse::<i32>()
+                .map(Operation::Addx)
+                .map_err(|_| FragmentError::new(val, "Invalid addx value")),
+            _ => Err(FragmentError::new(
+                trimmed_line,
+                "Encountered a multi word operation text that does not match addx",
+            )),
+        },
+        None => match trimmed_line {
+            "noop" => Ok(Operation::Noop),
+            _ => Err(FragmentError::new(
+                trimmed_line,
+                "Encountered a single operation text that does not match noop",
+            )),
+        },
+    }
+}
+
+/// Parses one `addx` or `noop` per line, rejecting programs that would
+/// overflow the register
+pub fn parse_input(input: impl BufRead) -> Result<VecDeque<Operation>, ParseError> {
+    read_operations(&mut LineReader::new(Day10::DAY, input))
+}
+
+fn read_operations<R: BufRead>(
+    lines: &mut LineReader<R>,
+) -> Result<VecDeque<Operation>, ParseError> {
     let mut operations = VecDeque::new();
-    for line in input.lines() {
-        let trimmed_line = line.trim();
-        if trimmed_line == "" {
+
+    // Run the register alongside so a program that overflows it is rejected here
+    let mut register: i32 = 1;
+    let mut cycle_count: i32 = 1;
+
+    while let Some(line) = lines.next_line()? {
+        if line.is_blank() {
             continue;
         }
 
-        let split = trimmed_line.split_once(" ");
+        let operation =
+            parse_operation(line.text).map_err(|err| err.at(Day10::DAY, line.index, line.raw))?;
 
-        let operation = match split {
-            Some(split) => match split {
-                ("addx", val) => {
-                    Operation::Addx(i32::from_str_radix(val, 10).expect("Invalid addx value"))
-                }
-                _ => panic!(
-                    "Encountered a multi word operation text that does not match addx {}",
-                    trimmed_line
-                ),
-            },
-            None => match trimmed_line {
-                "noop" => Operation::Noop,
-                _ => panic!(
-                    "Encountered a single operation text that does not match noop {}",
-                    trimmed_line
-                ),
-            },
+        let add_value = match operation {
+            Operation::Addx(add_value) => add_value,
+            Operation::Noop => 0,
         };
 
+        // Signal strength is the register times the cycle, and it's checked every cycle
+        let next = cycle_count
+            .checked_add(operation.cycles_requires())
+            .and_then(|next_cycle| Some((next_cycle, register.checked_add(add_value)?)))
+            .filter(|(next_cycle, next_register)| {
+                register.checked_mul(*next_cycle).is_some()
+                    && next_register.checked_mul(*next_cycle).is_some()
+            });
+
+        (cycle_count, register) = next.ok_or_else(|| {
+            FragmentError::new(line.text, "Register overflows").at(Day10::DAY, line.index, line.raw)
+        })?;
+
         operations.push_back(operation);
     }
-    operations
+    Ok(operations)
 }
 
-fn process_operations(
+/// Runs the whole program, drawing on `crt` as it goes if there is one
+pub fn process_operations(
     cpu: &mut CPU,
     mut operation_queue: VecDeque<Operation>,
     mut crt: Option<&mut CRT>,
@@ -217,7 +302,9 @@ fn process_operations(
                 let x = cpu.current_screen_column();
                 let y = cpu.current_screen_row();
 
-                if x >= (cpu.register - 1) as usize && x <= (cpu.register + 1) as usize {
+                // Anything after the last row has nowhere to be drawn. The
+                // sprite is compared as signed so it can hang off the left edge.
+                if y < crt.pixels.len() && (x as i64 - i64::from(cpu.register)).abs() <= 1 {
                     crt.draw(x, y);
                 }
             }
@@ -226,15 +313,13 @@ fn process_operations(
 
             cpu.signal_strength_history.push(cpu.signal_strength());
 
-            if PRINT_SIGNAL_STRENGTH {
-                if cpu.cycle_count % 20 == 0 {
-                    println!(
-                        "Cycle count is {} and register value is {} and signal strength is {}",
-                        cpu.cycle_count,
-                        cpu.register,
-                        cpu.signal_strength()
-                    );
-                }
+            if PRINT_SIGNAL_STRENGTH && cpu.cycle_count % 20 == 0 {
+                println!(
+                    "Cycle count is {} and register value is {} and signal strength is {}",
+                    cpu.cycle_count,
+                    cpu.register,
+                    cpu.signal_strength()
+                );
             }
         }
     }
@@ -244,38 +329,61 @@ fn process_operations(
     }
 }
 
-pub fn day_10_part_1(input: &'static str) -> String {
-    let operations = parse_input(input);
+pub struct Day10;
 
-    let mut cpu = CPU::new();
+impl Solution for Day10 {
+    type Parsed = VecDeque<Operation>;
 
-    process_operations(&mut cpu, operations, None);
+    const DAY: u8 = 10;
+    const INPUT: &'static str = input::INPUT_DAY_10;
 
-    let relevant_strengths = vec![
-        cpu.signal_strength_at_cycle(20),
-        cpu.signal_strength_at_cycle(60),
-        cpu.signal_strength_at_cycle(100),
-        cpu.signal_strength_at_cycle(140),
-        cpu.signal_strength_at_cycle(180),
-        cpu.signal_strength_at_cycle(220),
-    ];
+    fn read(&self, input: &mut dyn BufRead) -> Result<Self::Parsed, ParseError> {
+        let mut lines = LineReader::new(Day10::DAY, input);
+        let operations = read_operations(&mut lines)?;
 
-    let strength_sum: i32 = relevant_strengths.iter().sum();
+        // Part 1 reads the signal strength up to this cycle
+        let cycles = operations
+            .iter()
+            .map(Operation::cycles_requires)
+            .sum::<i32>();
 
-    strength_sum.to_string()
-}
+        if cycles < LAST_SIGNAL_CYCLE {
+            return Err(lines.end_of_input(format!(
+                "Program should run for at least {} cycles",
+                LAST_SIGNAL_CYCLE
+            )));
+        }
+
+        Ok(operations)
+    }
 
-pub fn day_10_part_2(input: &'static str) -> String {
-    let operations = parse_input(input);
+    fn part_1(&self, operations: &Self::Parsed) -> Answer {
+        let mut cpu = CPU::new();
+
+        process_operations(&mut cpu, operations.clone(), None);
+
+        let relevant_strengths = [
+            cpu.signal_strength_at_cycle(20),
+            cpu.signal_strength_at_cycle(60),
+            cpu.signal_strength_at_cycle(100),
+            cpu.signal_strength_at_cycle(140),
+            cpu.signal_strength_at_cycle(180),
+            cpu.signal_strength_at_cycle(LAST_SIGNAL_CYCLE as usize),
+        ];
 
-    let mut cpu = CPU::new();
-    let mut crt = CRT::new();
+        let strength_sum: i32 = relevant_strengths.iter().sum();
+
+        strength_sum.into()
+    }
 
-    process_operations(&mut cpu, operations, Some(&mut crt));
+    fn part_2(&self, operations: &Self::Parsed) -> Answer {
+        let mut cpu = CPU::new();
+        let mut crt = CRT::new();
 
-    crt.print_it();
+        process_operations(&mut cpu, operations.clone(), Some(&mut crt));
 
-    "see printed output above".to_string()
+        Answer::Grid(crt.rows())
+    }
 }
 
 #[cfg(test)]
@@ -294,7 +402,7 @@ mod test {
 
     #[test]
     fn test_parse_input() {
-        let operations = parse_input(TEST_INPUT);
+        let operations = parse_input(TEST_INPUT.as_bytes()).unwrap();
 
         let op1 = Operation::Noop;
         let op2 = Operation::Addx(3);
@@ -305,7 +413,7 @@ mod test {
 
     #[test]
     fn test_cpu_register() {
-        let operations = parse_input(TEST_INPUT);
+        let operations = parse_input(TEST_INPUT.as_bytes()).unwrap();
 
         let mut cpu = CPU::new();
 
@@ -316,7 +424,7 @@ mod test {
 
     #[test]
     fn test_signal_strength() {
-        let operations = parse_input(LONG_TEST_INPUT);
+        let operations = parse_input(LONG_TEST_INPUT.as_bytes()).unwrap();
 
         let mut cpu = CPU::new();
 
@@ -330,7 +438,7 @@ mod test {
             cpu.signal_strength_at_cycle(100),
             cpu.signal_strength_at_cycle(140),
             cpu.signal_strength_at_cycle(180),
-            cpu.signal_strength_at_cycle(220),
+            cpu.signal_strength_at_cycle(LAST_SIGNAL_CYCLE as usize),
         ];
 
         dbg!("{:?}", &relevant_strengths);
@@ -341,13 +449,59 @@ mod test {
     }
 
     #[test]
-    fn test_print_crt() {
+    fn test_draw_crt() {
         let mut crt = CRT::new();
 
         crt.draw(30, 2);
 
-        crt.print_it();
-
         assert_eq!(crt.pixels[2][30], '#');
+        assert_eq!(
+            crt.rows()[2],
+            format!("{}#{}", ".".repeat(30), ".".repeat(9))
+        );
+    }
+
+    #[test]
+    fn test_invalid_program() {
+        let err = Day10.parse(TEST_INPUT).unwrap_err();
+
+        assert_eq!(err.message, "Program should run for at least 220 cycles");
+
+        let err = parse_input("addx 2147483647\n".as_bytes()).unwrap_err();
+
+        assert_eq!(
+            err,
+            ParseError::new(10, 1, 1, "addx 2147483647", "Register overflows")
+        );
+
+        // Strength is the register times the cycle, so it can overflow first
+        let err = parse_input("addx 500000000\nnoop\nnoop\n".as_bytes()).unwrap_err();
+
+        assert_eq!(err.line, 3);
+    }
+
+    #[test]
+    fn test_invalid_operations() {
+        let err = parse_input("noop\n  addx five\n".as_bytes()).unwrap_err();
+
+        assert_eq!(err, ParseError::new(10, 2, 8, "five", "Invalid addx value"));
+
+        let err = parse_input("noop\nhalt\n".as_bytes()).unwrap_err();
+
+        assert_eq!(
+            err.message,
+            "Encountered a single operation text that does not match noop"
+        );
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        crate::property::check(generate::generate, |operations| {
+            let parsed = Day10.parse(&generate::serialize(operations)).unwrap();
+
+            assert_eq!(&parsed, operations);
+
+            Day10.part_1(&parsed);
+        });
     }
 }
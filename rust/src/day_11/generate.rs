@@ -0,0 +1,122 @@
+use std::io::{self, Write};
+
+use super::{Monkey, OperationType, OperationValue};
+use crate::rng::Rng;
+
+pub fn generate(rng: &mut Rng) -> Vec<Monkey> {
+    let count = rng.count(2..=8);
+
+    (0..count)
+        .map(|index| {
+            // Monkeys never throw to themselves
+            let mut target = || (index + rng.count(1..=count - 1)) % count;
+            let monkey_throw_true = target();
+            let monkey_throw_false = target();
+
+            let operation_value = match rng.bool() {
+                true => OperationValue::Old,
+                false => OperationValue::Value(rng.range(1..=20) as u128),
+            };
+
+            Monkey {
+                index,
+                // Some monkeys start with nothing
+                items: (0..rng.count(0..=6))
+                    .map(|_| rng.range(1..=100) as u128)
+                    .collect(),
+                operation_type: match rng.bool() {
+                    true => OperationType::Add,
+                    false => OperationType::Multiply,
+                },
+                operation_value,
+                divisible_by: rng.range(2..=23) as u128,
+                monkey_throw_true,
+                monkey_throw_false,
+                inspection_count: 0,
+            }
+        })
+        .collect()
+}
+
+/// The divisors of the first monkeys in a stress input. Their product fits in
+/// 64 bits, so every monkey after them divides by 1.
+const STRESS_DIVISORS: [u128; 15] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47];
+
+/// `size` monkeys, or two if that's more, holding up to ten items each
+pub fn stress(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let count = size.max(2);
+
+    for index in 0..count {
+        let mut target = || (index + rng.count(1..=count - 1)) % count;
+        let monkey_throw_true = target();
+        let monkey_throw_false = target();
+
+        let operation_value = match rng.bool() {
+            true => OperationValue::Old,
+            false => OperationValue::Value(rng.range(1..=20) as u128),
+        };
+
+        let monkey = Monkey {
+            index,
+            items: (0..rng.count(0..=10))
+                .map(|_| rng.range(1..=100) as u128)
+                .collect(),
+            operation_type: match rng.bool() {
+                true => OperationType::Add,
+                false => OperationType::Multiply,
+            },
+            operation_value,
+            divisible_by: STRESS_DIVISORS.get(index).copied().unwrap_or(1),
+            monkey_throw_true,
+            monkey_throw_false,
+            inspection_count: 0,
+        };
+
+        if index > 0 {
+            writeln!(out)?;
+        }
+
+        write!(out, "{}", serialize(&[monkey]))?;
+    }
+
+    Ok(())
+}
+
+pub fn serialize(monkeys: &[Monkey]) -> String {
+    monkeys
+        .iter()
+        .map(|monkey| {
+            let items = monkey
+                .items
+                .iter()
+                .map(u128::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            let operator = match monkey.operation_type {
+                OperationType::Add => "+",
+                OperationType::Multiply => "*",
+            };
+
+            let value = match &monkey.operation_value {
+                OperationValue::Old => "old".to_string(),
+                OperationValue::Value(value) => value.to_string(),
+            };
+
+            [
+                format!("Monkey {}:", monkey.index),
+                format!("  Starting items: {}", items),
+                format!("  Operation: new = old {} {}", operator, value),
+                format!("  Test: divisible by {}", monkey.divisible_by),
+                format!("    If true: throw to monkey {}", monkey.monkey_throw_true),
+                format!(
+                    "    If false: throw to monkey {}",
+                    monkey.monkey_throw_false
+                ),
+            ]
+            .join("\n")
+                + "\n"
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -1,4 +1,4 @@
-pub static INPUT_DAY_11: &'static str = r#"
+pub static INPUT_DAY_11: &str = r#"
 Monkey 0:
   Starting items: 85, 77, 77
   Operation: new = old * 7
@@ -56,8 +56,8 @@ Monkey 7:
     If false: throw to monkey 1
 "#;
 
-
-pub static TEST_INPUT: &'static str = r#"
+#[cfg(test)]
+pub static TEST_INPUT: &str = r#"
 Monkey 0:
   Starting items: 79, 98
   Operation: new = old * 19
@@ -85,4 +85,4 @@ Monkey 3:
   Test: divisible by 17
     If true: throw to monkey 0
     If false: throw to monkey 1
-"#;
\ No newline at end of file
+"#;
@@ -1,21 +1,30 @@
-use std::collections::VecDeque;
+//! Day 11: Monkey in the Middle
 
+use std::{collections::VecDeque, io::BufRead, str::FromStr};
+
+use crate::answer::Answer;
+use crate::error::{FragmentError, ParseError};
+use crate::normalize::LineReader;
+use crate::solution::Solution;
+
+pub mod generate;
 pub mod input;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 enum OperationType {
     Add,
     Multiply,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 enum OperationValue {
     Old,
     Value(u128),
 }
 
-#[derive(Debug, PartialEq)]
-struct Monkey {
+/// A monkey's notes along with how many items it has inspected so far
+#[derive(Debug, Clone, PartialEq)]
+pub struct Monkey {
     index: usize,
     items: VecDeque<u128>,
     operation_type: OperationType,
@@ -26,86 +35,195 @@ struct Monkey {
     inspection_count: u128,
 }
 
-impl Monkey {}
+impl Monkey {
+    /// The number from the `Monkey N:` heading
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The worry levels of the items it holds, in the order it inspects them
+    pub fn items(&self) -> &VecDeque<u128> {
+        &self.items
+    }
+
+    /// The divisor it tests each worry level against
+    pub fn divisible_by(&self) -> u128 {
+        self.divisible_by
+    }
+
+    /// The monkeys it throws to when the test passes and when it fails
+    pub fn throw_targets(&self) -> (usize, usize) {
+        (self.monkey_throw_true, self.monkey_throw_false)
+    }
+
+    /// How many items it has inspected
+    pub fn inspection_count(&self) -> u128 {
+        self.inspection_count
+    }
+}
+
+/// Every monkey, in the order of their notes
+pub type ProcessedInput = Vec<Monkey>;
+
+/// Takes the next line of a monkey's notes, returning its index, the full line
+/// and whatever follows `prefix`
+fn expect_line<'a, R: BufRead>(
+    lines: &'a mut LineReader<R>,
+    prefix: &str,
+) -> Result<(usize, &'a str, &'a str), ParseError> {
+    if lines.at_end()? {
+        return Err(lines.end_of_input(format!(
+            "Expected a line starting with \"{}\"",
+            prefix.trim()
+        )));
+    }
+
+    let line = lines
+        .next_content_line()?
+        .expect("There is another line, checked above");
+
+    match line.text.strip_prefix(prefix) {
+        Some(rest) => Ok((line.index, line.raw, rest.trim())),
+        None => Err(FragmentError::new(
+            line.text,
+            format!("Expected a line starting with \"{}\"", prefix.trim()),
+        )
+        .at(Day11::DAY, line.index, line.raw)),
+    }
+}
 
-type ProcessedInput = Vec<Monkey>;
+fn parse_number<'a, T: FromStr>(value: &'a str, message: &str) -> Result<T, FragmentError<'a>> {
+    value
+        .parse::<T>()
+        .map_err(|_| FragmentError::new(value, message))
+}
 
-fn parse_input(input: &str) -> ProcessedInput {
+/// Parses each monkey's notes, checking that every throw lands on a monkey
+/// that exists
+pub fn parse_input(input: impl BufRead) -> Result<ProcessedInput, ParseError> {
     let mut processed_output = Vec::new();
 
-    let mut lines = input.lines();
+    // Throw targets can only be checked once every monkey has been read, so
+    // the error for each is made while its line is still at hand
+    let mut throw_targets = Vec::new();
 
-    loop {
-        let line_1 = lines.next().map(|line| line.trim());
+    // Worry levels are kept below the product of the divisors. Holding that and
+    // every value to 64 bits means multiplying two of them can't overflow a u128.
+    let mut divisor_product: u64 = 1;
 
-        if line_1 == Some("") {
-            continue;
-        }
+    let mut lines = LineReader::new(Day11::DAY, input);
 
+    while !lines.at_end()? {
         // Line 1
-        let index = if let Some(line_1) = line_1 {
-            let monkey_num = line_1
-                .trim()
-                .split_once(" ")
-                .expect("Failed to parse line")
-                .1
-                .strip_suffix(":")
-                .expect("Strip monkey suffix failed");
-
-            usize::from_str_radix(monkey_num, 10).expect("Parse monkey num failed")
-        } else {
-            // Reached end of input text
-            break;
-        };
+        let (line_idx, line, monkey_num) = expect_line(&mut lines, "Monkey ")?;
 
-        // Line 2
-        let monkey_items_line = lines.next().expect("Failed to parse items").trim();
+        let index = monkey_num
+            .strip_suffix(":")
+            .ok_or(FragmentError::new(monkey_num, "Strip monkey suffix failed"))
+            .and_then(|monkey_num| parse_number::<usize>(monkey_num, "Parse monkey num failed"))
+            .map_err(|err| err.at(Day11::DAY, line_idx, line))?;
 
-        let monkey_items_nums_string = monkey_items_line
-            .strip_prefix("Starting items: ")
-            .expect("Failed to parse");
+        // Line 2
+        let (line_idx, line, monkey_items_nums_string) =
+            expect_line(&mut lines, "Starting items:")?;
 
         let items = monkey_items_nums_string
-            .split(", ")
-            .map(|item| u128::from_str_radix(item, 10).expect("Failed to parse"))
-            .collect::<VecDeque<_>>();
+            .split(",")
+            .map(|item| item.trim())
+            .filter(|item| !item.is_empty())
+            .map(|item| parse_number::<u64>(item, "Invalid worry level").map(u128::from))
+            .collect::<Result<VecDeque<_>, _>>()
+            .map_err(|err| err.at(Day11::DAY, line_idx, line))?;
 
         // Line 3
-        let line_3 = lines.next().expect("Failed to parse").trim();
-        let stripped_prefix_line_3 = line_3.strip_prefix("Operation: new = old ").unwrap();
+        let (line_idx, line, stripped_prefix_line_3) =
+            expect_line(&mut lines, "Operation: new = old ")?;
 
         let (operation_type_string, operation_value_string) =
-            stripped_prefix_line_3.split_once(" ").unwrap();
-
-        let operation_type: OperationType = match operation_type_string {
-            "+" => OperationType::Add,
-            "*" => OperationType::Multiply,
-            _ => panic!(),
-        };
+            stripped_prefix_line_3.split_once(" ").ok_or_else(|| {
+                FragmentError::new(stripped_prefix_line_3, "Expected an operator and a value").at(
+                    Day11::DAY,
+                    line_idx,
+                    line,
+                )
+            })?;
+
+        let operation_type: OperationType =
+            match operation_type_string {
+                "+" => OperationType::Add,
+                "*" => OperationType::Multiply,
+                _ => {
+                    return Err(FragmentError::new(
+                        operation_type_string,
+                        "Operator should be + or *",
+                    )
+                    .at(Day11::DAY, line_idx, line))
+                }
+            };
 
         let operation_value: OperationValue = match operation_value_string {
             "old" => OperationValue::Old,
-            val => {
-                let value = u128::from_str_radix(val, 10).unwrap();
-                OperationValue::Value(value)
-            }
+            val => parse_number::<u64>(val, "Invalid operation value")
+                .map(|val| OperationValue::Value(val.into()))
+                .map_err(|err| err.at(Day11::DAY, line_idx, line))?,
         };
 
         // Line 4
-        let line_4 = lines.next().expect("Failed to parse").trim();
-        let stripped_prefix_line_4 = line_4.strip_prefix("Test: divisible by ").unwrap();
+        let (line_idx, line, stripped_prefix_line_4) =
+            expect_line(&mut lines, "Test: divisible by ")?;
+
+        let divisible_by = match stripped_prefix_line_4.parse::<u64>() {
+            Ok(divisible_by) if divisible_by > 0 => divisible_by,
+            _ => {
+                return Err(
+                    FragmentError::new(stripped_prefix_line_4, "Invalid divisor").at(
+                        Day11::DAY,
+                        line_idx,
+                        line,
+                    ),
+                )
+            }
+        };
+
+        divisor_product = divisor_product.checked_mul(divisible_by).ok_or_else(|| {
+            FragmentError::new(
+                stripped_prefix_line_4,
+                "Divisors multiply to more than 64 bits",
+            )
+            .at(Day11::DAY, line_idx, line)
+        })?;
 
-        let divisible_by = u128::from_str_radix(stripped_prefix_line_4, 10).unwrap();
+        let divisible_by = u128::from(divisible_by);
 
         // Line 5
-        let line_5 = lines.next().expect("Failed to parse").trim();
-        let stripped_prefix_line_5 = line_5.strip_prefix("If true: throw to monkey ").unwrap();
-        let monkey_throw_true: usize = usize::from_str_radix(stripped_prefix_line_5, 10).unwrap();
+        let (line_idx, line, stripped_prefix_line_5) =
+            expect_line(&mut lines, "If true: throw to monkey ")?;
+        let monkey_throw_true: usize =
+            parse_number::<usize>(stripped_prefix_line_5, "Invalid monkey number")
+                .map_err(|err| err.at(Day11::DAY, line_idx, line))?;
+        throw_targets.push((
+            monkey_throw_true,
+            FragmentError::new(stripped_prefix_line_5, "No monkey with that number").at(
+                Day11::DAY,
+                line_idx,
+                line,
+            ),
+        ));
 
         // Line 6
-        let line_6 = lines.next().expect("Failed to parse").trim();
-        let stripped_prefix_line_6 = line_6.strip_prefix("If false: throw to monkey ").unwrap();
-        let monkey_throw_false: usize = usize::from_str_radix(stripped_prefix_line_6, 10).unwrap();
+        let (line_idx, line, stripped_prefix_line_6) =
+            expect_line(&mut lines, "If false: throw to monkey ")?;
+        let monkey_throw_false: usize =
+            parse_number::<usize>(stripped_prefix_line_6, "Invalid monkey number")
+                .map_err(|err| err.at(Day11::DAY, line_idx, line))?;
+        throw_targets.push((
+            monkey_throw_false,
+            FragmentError::new(stripped_prefix_line_6, "No monkey with that number").at(
+                Day11::DAY,
+                line_idx,
+                line,
+            ),
+        ));
 
         let monkey = Monkey {
             index,
@@ -121,22 +239,35 @@ fn parse_input(input: &str) -> ProcessedInput {
         processed_output.push(monkey);
     }
 
-    processed_output
+    for (target, error) in throw_targets {
+        if target >= processed_output.len() {
+            return Err(error);
+        }
+    }
+
+    // Monkey business needs the two most active monkeys
+    if processed_output.len() < 2 {
+        return Err(lines.end_of_input("Expected at least two monkeys"));
+    }
+
+    Ok(processed_output)
 }
 
-fn handle_processed_input(
+/// Plays one round. Worry levels are kept modulo `baseline`, which has to be a
+/// multiple of every monkey's divisor for the throws to come out the same.
+pub fn handle_processed_input(
     monkeys: &mut ProcessedInput,
     has_ability_to_feel_relief: bool,
     baseline: u128,
 ) {
     for monkey_index in 0..monkeys.len() {
-        while monkeys[monkey_index].items.len() > 0 {
+        while !monkeys[monkey_index].items.is_empty() {
             let monkey = &mut monkeys[monkey_index];
             let item = monkey.items.pop_front();
             if let Some(item) = item {
                 let operation_value = match &monkey.operation_value {
-                    OperationValue::Old => item.clone(),
-                    OperationValue::Value(val) => val.clone(),
+                    OperationValue::Old => item,
+                    OperationValue::Value(val) => *val,
                 };
 
                 let new_value_after_inspection = match monkey.operation_type {
@@ -150,15 +281,13 @@ fn handle_processed_input(
                     new_value_after_inspection
                 };
 
-                let throw_target_test = &new_value_after_relief % monkey.divisible_by == 0;
+                let throw_target_test = new_value_after_relief % monkey.divisible_by == 0;
 
                 let monkey_throw_true = monkey.monkey_throw_true;
                 let monkey_throw_false = monkey.monkey_throw_false;
 
                 monkey.inspection_count += 1;
 
-                drop(monkey);
-
                 match throw_target_test {
                     true => monkeys[monkey_throw_true]
                         .items
@@ -172,7 +301,8 @@ fn handle_processed_input(
     }
 }
 
-fn monkey_business(input: &ProcessedInput) -> u128 {
+/// The inspection counts of the two busiest monkeys multiplied together
+pub fn monkey_business(input: &ProcessedInput) -> u128 {
     let mut vals = input
         .iter()
         .map(|monkey| monkey.inspection_count)
@@ -186,38 +316,53 @@ fn monkey_business(input: &ProcessedInput) -> u128 {
     most * second_most
 }
 
-pub fn day_11_part_1(input: &'static str) -> String {
-    let mut processed_input = parse_input(input);
+pub struct Day11;
 
-    let baseline = processed_input
-        .iter()
-        .map(|monkey| monkey.divisible_by)
-        .product::<u128>();
+impl Solution for Day11 {
+    type Parsed = ProcessedInput;
+
+    const DAY: u8 = 11;
+    const INPUT: &'static str = input::INPUT_DAY_11;
 
-    for _ in 0..20 {
-        handle_processed_input(&mut processed_input, true, baseline);
+    fn read(&self, input: &mut dyn BufRead) -> Result<Self::Parsed, ParseError> {
+        parse_input(input)
     }
 
-    let monkey_business = monkey_business(&processed_input);
+    fn part_1(&self, monkeys: &Self::Parsed) -> Answer {
+        let mut processed_input = monkeys.clone();
 
-    monkey_business.to_string()
-}
+        let baseline = processed_input
+            .iter()
+            .map(|monkey| monkey.divisible_by)
+            .product::<u128>();
 
-pub fn day_11_part_2(input: &'static str) -> String {
-    let mut processed_input = parse_input(input);
+        for _ in 0..20 {
+            handle_processed_input(&mut processed_input, true, baseline);
+        }
 
-    let baseline = processed_input
-        .iter()
-        .map(|monkey| monkey.divisible_by)
-        .product::<u128>();
+        let monkey_business = monkey_business(&processed_input);
 
-    for _ in 0..10000 {
-        handle_processed_input(&mut processed_input, false, baseline);
+        // Each count is at most the number of items times the number of rounds
+        Answer::Integer(i128::try_from(monkey_business).expect("Inspection counts are small"))
     }
 
-    let monkey_business = monkey_business(&processed_input);
+    fn part_2(&self, monkeys: &Self::Parsed) -> Answer {
+        let mut processed_input = monkeys.clone();
+
+        let baseline = processed_input
+            .iter()
+            .map(|monkey| monkey.divisible_by)
+            .product::<u128>();
+
+        for _ in 0..10000 {
+            handle_processed_input(&mut processed_input, false, baseline);
+        }
+
+        let monkey_business = monkey_business(&processed_input);
 
-    monkey_business.to_string()
+        // Each count is at most the number of items times the number of rounds
+        Answer::Integer(i128::try_from(monkey_business).expect("Inspection counts are small"))
+    }
 }
 
 #[cfg(test)]
@@ -229,14 +374,14 @@ mod test {
 
     #[test]
     fn test_parse_input() {
-        let processed_input = parse_input(TEST_INPUT);
+        let processed_input = parse_input(TEST_INPUT.as_bytes()).unwrap();
 
         assert_eq!(processed_input[3].monkey_throw_false, 1);
     }
 
     #[test]
     fn test_monkey_business() {
-        let mut processed_input = parse_input(TEST_INPUT);
+        let mut processed_input = parse_input(TEST_INPUT.as_bytes()).unwrap();
 
         let baseline = processed_input
             .iter()
@@ -253,4 +398,59 @@ mod test {
 
         assert_eq!(monkey_business, 10605);
     }
+
+    #[test]
+    fn test_invalid_notes() {
+        let notes =
+            TEST_INPUT.replacen("Operation: new = old * 19", "Operation: new = old / 19", 1);
+
+        let err = parse_input(notes.as_bytes()).unwrap_err();
+
+        assert_eq!(
+            err,
+            ParseError::new(11, 4, 24, "/", "Operator should be + or *")
+        );
+
+        let notes = TEST_INPUT.replacen("throw to monkey 3", "throw to monkey 7", 1);
+
+        let err = parse_input(notes.as_bytes()).unwrap_err();
+
+        assert_eq!(err.message, "No monkey with that number");
+        assert_eq!(err.text, "7");
+
+        let notes = TEST_INPUT.replacen("divisible by 23", "divisible by 18446744073709551557", 1);
+
+        let err = parse_input(notes.as_bytes()).unwrap_err();
+
+        assert_eq!(err.message, "Divisors multiply to more than 64 bits");
+        assert_eq!(err.text, "19");
+
+        let notes = TEST_INPUT.replacen("79, 98", "79, 18446744073709551616", 1);
+
+        let err = parse_input(notes.as_bytes()).unwrap_err();
+
+        assert_eq!(err.message, "Invalid worry level");
+
+        let notes = TEST_INPUT.trim_end().rsplit_once('\n').unwrap().0;
+
+        let err = parse_input(notes.as_bytes()).unwrap_err();
+
+        assert_eq!(
+            err,
+            ParseError::end_of_input(
+                11,
+                notes.lines().count(),
+                "Expected a line starting with \"If false: throw to monkey\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        crate::property::check(generate::generate, |monkeys| {
+            let parsed = Day11.parse(&generate::serialize(monkeys)).unwrap();
+
+            assert_eq!(&parsed, monkeys);
+        });
+    }
 }
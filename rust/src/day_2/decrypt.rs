@@ -0,0 +1,296 @@
+//! The puzzle only guesses what the second column of the guide means: first
+//! that `X`, `Y` and `Z` are rock, paper and scissors, then that they are
+//! losing, tying and winning. This scores the guide under every way of reading
+//! those letters, to find which reading helps the player most and which least.
+//!
+//! A round's score only depends on the opponent's shape and the letter, so the
+//! guide is read once, line by line as [`super::parse_input_part_1`] reads it,
+//! counting the nine pairs. Every mapping is scored from those counts.
+
+use std::{fmt, io::BufRead};
+
+use super::{get_score, read_moves, read_rounds, what_to_choose, Outcome, Rps};
+use crate::error::ParseError;
+use crate::table::{format_table, Align};
+
+/// The guide's second column letters, in order
+const LETTERS: [char; 3] = ['X', 'Y', 'Z'];
+
+const OUTCOMES: [Outcome; 3] = [Outcome::Lose, Outcome::Tie, Outcome::Win];
+
+/// What `X`, `Y` and `Z` stand for, in that order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mapping {
+    /// The shape the player plays
+    Moves([Rps; 3]),
+    /// How the round should end, playing whatever ends it that way
+    Outcomes([Outcome; 3]),
+}
+
+impl Mapping {
+    /// The reading part 1 of the puzzle assumes
+    pub const PART_1: Mapping = Mapping::Moves(Rps::ALL);
+    /// The reading part 2 of the puzzle assumes
+    pub const PART_2: Mapping = Mapping::Outcomes(OUTCOMES);
+
+    /// Every way of reading the letters: the six orders of the shapes, then
+    /// the six orders of the outcomes
+    pub fn all() -> Vec<Mapping> {
+        let moves = permutations(Rps::ALL).into_iter().map(Mapping::Moves);
+        let outcomes = permutations(OUTCOMES).into_iter().map(Mapping::Outcomes);
+
+        moves.chain(outcomes).collect()
+    }
+
+    /// The shape the player makes for a letter, given as the shape
+    /// [`super::parse_input_part_1`] reads it as
+    pub fn choose(&self, opponent: Rps, letter: Rps) -> Rps {
+        match self {
+            Mapping::Moves(moves) => moves[letter as usize],
+            Mapping::Outcomes(outcomes) => what_to_choose(&opponent, &outcomes[letter as usize]),
+        }
+    }
+
+    fn meanings(&self) -> [String; 3] {
+        match self {
+            Mapping::Moves(moves) => moves.map(|shape| format!("{:?}", shape)),
+            Mapping::Outcomes(outcomes) => outcomes.map(|outcome| format!("{:?}", outcome)),
+        }
+    }
+}
+
+impl fmt::Display for Mapping {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let meanings = LETTERS
+            .iter()
+            .zip(self.meanings())
+            .map(|(letter, meaning)| format!("{}={}", letter, meaning))
+            .collect::<Vec<_>>();
+
+        write!(f, "{}", meanings.join(" "))
+    }
+}
+
+fn permutations<T: Copy>([a, b, c]: [T; 3]) -> [[T; 3]; 6] {
+    [
+        [a, b, c],
+        [a, c, b],
+        [b, a, c],
+        [b, c, a],
+        [c, a, b],
+        [c, b, a],
+    ]
+}
+
+/// How often each letter is played against each of the opponent's shapes,
+/// which is all that scoring a guide under any mapping needs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tally {
+    /// Indexed by the opponent's shape and then the letter
+    counts: [[i64; 3]; 3],
+}
+
+impl Tally {
+    /// Counts the rounds of a guide without keeping them. Each line is read as
+    /// two shapes, like [`super::parse_input_part_1`] does, so rock, paper and
+    /// scissors in the second column stand for `X`, `Y` and `Z`.
+    pub fn read(guide: impl BufRead) -> Result<Self, ParseError> {
+        let mut counts = [[0; 3]; 3];
+
+        read_rounds(guide, [&read_moves], |_, (opponent, letter)| {
+            counts[opponent as usize][letter as usize] += 1;
+        })?;
+
+        Ok(Self { counts })
+    }
+
+    /// The player's total score reading the guide with `mapping`
+    pub fn score(&self, mapping: &Mapping) -> i64 {
+        let mut total = 0;
+
+        for opponent in Rps::ALL {
+            for letter in Rps::ALL {
+                let round = get_score(&(opponent, mapping.choose(opponent, letter)));
+
+                total += self.counts[opponent as usize][letter as usize] * round as i64;
+            }
+        }
+
+        total
+    }
+}
+
+/// Every mapping and the score it gives, highest first. Mappings that score
+/// the same stay in the order [`Mapping::all`] gives them.
+pub fn search(guide: impl BufRead) -> Result<Vec<(Mapping, i64)>, ParseError> {
+    let tally = Tally::read(guide)?;
+    let mut scores = Mapping::all()
+        .into_iter()
+        .map(|mapping| (mapping, tally.score(&mapping)))
+        .collect::<Vec<_>>();
+
+    scores.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+
+    Ok(scores)
+}
+
+/// The report `advent day2 decrypt` prints: the best and worst readings and
+/// the score every reading gives
+pub fn format_search(scores: &[(Mapping, i64)]) -> String {
+    let (Some((best, highest)), Some((worst, lowest))) = (scores.first(), scores.last()) else {
+        return "No mappings".to_string();
+    };
+
+    let summary = format!(
+        "Highest score {} reading {}\nLowest score {} reading {}",
+        highest, best, lowest, worst
+    );
+
+    let rows = scores
+        .iter()
+        .map(|(mapping, score)| {
+            let reading = match mapping {
+                Mapping::Moves(_) => "moves",
+                Mapping::Outcomes(_) => "outcomes",
+            };
+
+            let puzzle = match *mapping {
+                Mapping::PART_1 => "part 1",
+                Mapping::PART_2 => "part 2",
+                _ => "",
+            };
+
+            let [x, y, z] = mapping.meanings();
+
+            vec![
+                reading.to_string(),
+                x,
+                y,
+                z,
+                score.to_string(),
+                puzzle.to_string(),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let table = format_table(
+        &[
+            ("Reading", Align::Left),
+            ("X", Align::Left),
+            ("Y", Align::Left),
+            ("Z", Align::Left),
+            ("Score", Align::Right),
+            ("Puzzle", Align::Left),
+        ],
+        &rows,
+    );
+
+    format!("{}\n\n{}", summary, table)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::day_2::{generate, get_total_scores, parse_input_part_1, parse_input_part_2};
+
+    static TEST_INPUT: &str = "A Y\nB X\nC Z\n";
+
+    #[test]
+    fn test_puzzle_mappings() {
+        let tally = Tally::read(TEST_INPUT.as_bytes()).unwrap();
+
+        assert_eq!(tally.score(&Mapping::PART_1), 15);
+        assert_eq!(tally.score(&Mapping::PART_2), 12);
+
+        // Swapping what Y and Z mean loses every round
+        let swapped = Mapping::Moves([Rps::Rock, Rps::Scissors, Rps::Paper]);
+
+        assert_eq!(tally.score(&swapped), 3 + 1 + 2);
+    }
+
+    #[test]
+    fn test_invalid_guide() {
+        let err = search("A Y\nB W\n".as_bytes()).unwrap_err();
+
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.text, "W");
+    }
+
+    #[test]
+    fn test_matches_parsers() {
+        crate::property::check(generate::generate, |guide| {
+            let input = generate::serialize(guide);
+            let as_moves = parse_input_part_1(input.as_bytes()).unwrap();
+            let as_outcomes = parse_input_part_2(input.as_bytes()).unwrap();
+            let tally = Tally::read(input.as_bytes()).unwrap();
+
+            assert_eq!(
+                tally.score(&Mapping::PART_1),
+                get_total_scores(&as_moves) as i64
+            );
+            assert_eq!(
+                tally.score(&Mapping::PART_2),
+                get_total_scores(&as_outcomes) as i64
+            );
+
+            // Scoring from the tally is the same as reading every round again
+            for mapping in Mapping::all() {
+                let reread = as_moves
+                    .iter()
+                    .map(|(opponent, letter)| (*opponent, mapping.choose(*opponent, *letter)))
+                    .collect::<Vec<_>>();
+
+                assert_eq!(tally.score(&mapping), get_total_scores(&reread) as i64);
+            }
+        });
+    }
+
+    #[test]
+    fn test_search() {
+        let scores = search(TEST_INPUT.as_bytes()).unwrap();
+
+        assert_eq!(scores.len(), 12);
+        assert!(scores.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+
+        // Reading X as scissors and Z as rock wins every round
+        assert_eq!(
+            scores[0],
+            (
+                Mapping::Moves([Rps::Scissors, Rps::Paper, Rps::Rock]),
+                6 * 3 + 6
+            )
+        );
+        assert_eq!(
+            scores[11],
+            (Mapping::Moves([Rps::Rock, Rps::Scissors, Rps::Paper]), 6)
+        );
+    }
+
+    #[test]
+    fn test_format_search() {
+        assert_eq!(
+            format_search(&search(TEST_INPUT.as_bytes()).unwrap()),
+            [
+                "Highest score 24 reading X=Scissors Y=Paper Z=Rock",
+                "Lowest score 6 reading X=Rock Y=Scissors Z=Paper",
+                "",
+                "Reading   X         Y         Z         Score  Puzzle",
+                "moves     Scissors  Paper     Rock         24",
+                "outcomes  Win       Lose      Tie          18",
+                "moves     Rock      Paper     Scissors     15  part 1",
+                "moves     Paper     Rock      Scissors     15",
+                "moves     Paper     Scissors  Rock         15",
+                "moves     Scissors  Rock      Paper        15",
+                "outcomes  Lose      Win       Tie          15",
+                "outcomes  Tie       Lose      Win          15",
+                "outcomes  Tie       Win       Lose         15",
+                "outcomes  Win       Tie       Lose         15",
+                "outcomes  Lose      Tie       Win          12  part 2",
+                "moves     Rock      Scissors  Paper         6",
+            ]
+            .join("\n")
+        );
+
+        assert_eq!(format_search(&[]), "No mappings");
+    }
+}
@@ -0,0 +1,438 @@
+//! Rock paper scissors with any odd number of shapes, where every shape beats
+//! half of the others and loses to the rest. A game is defined by listing its
+//! shapes in order: each beats the shapes an odd number of places before it,
+//! counting round from the end of the list when it runs out. Listing rock,
+//! paper and scissors gives the puzzle's game, and the same rule covers rock
+//! paper scissors lizard Spock and its seven shape cousin.
+
+use std::{fmt, io::BufRead};
+
+use super::{read_guide, split_line, Hand, Matchups, Outcome, Score, SingleMatchup};
+use crate::error::{FragmentError, ParseError};
+
+/// The most shapes a game can have, so each can have its own letter
+pub const MAX_SHAPES: usize = 25;
+
+/// A shape in a game with `count` shapes, scoring one more than its place in
+/// the game's list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Shape {
+    index: u8,
+    count: u8,
+}
+
+impl Shape {
+    pub(super) fn new(index: usize, count: usize) -> Self {
+        debug_assert!(index < count && count <= MAX_SHAPES);
+
+        Self {
+            index: index as u8,
+            count: count as u8,
+        }
+    }
+
+    /// Where the shape is listed in its game
+    pub fn index(&self) -> usize {
+        self.index as usize
+    }
+
+    /// The shape to play against this one to get `outcome`: the next one along
+    /// beats it and the one before loses to it
+    pub fn to_get(&self, outcome: Outcome) -> Shape {
+        let offset = match outcome {
+            Outcome::Lose => self.count - 1,
+            Outcome::Tie => 0,
+            Outcome::Win => 1,
+        };
+
+        Shape {
+            index: (self.index + offset) % self.count,
+            count: self.count,
+        }
+    }
+}
+
+impl Score for Shape {
+    fn score(&self) -> i32 {
+        self.index as i32 + 1
+    }
+}
+
+impl Hand for Shape {
+    fn battle(&self, rhs: &Self) -> Outcome {
+        debug_assert_eq!(self.count, rhs.count, "Shapes are from different games");
+
+        match (rhs.index + self.count - self.index) % self.count {
+            0 => Outcome::Tie,
+            places if places % 2 == 1 => Outcome::Win,
+            _ => Outcome::Lose,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameError {
+    /// Games need an odd number of shapes, from 3 to [`MAX_SHAPES`], so that
+    /// every shape can beat exactly half of the others
+    ShapeCount(usize),
+    DuplicateShape(String),
+    /// The letters for one column of the guide, and why they don't fit the game
+    Letters(String, &'static str),
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameError::ShapeCount(count) => write!(
+                f,
+                "A game needs an odd number of shapes from 3 to {}, not {}",
+                MAX_SHAPES, count
+            ),
+            GameError::DuplicateShape(name) => write!(f, "{} is listed more than once", name),
+            GameError::Letters(letters, problem) => write!(f, "Letters {:?} {}", letters, problem),
+        }
+    }
+}
+
+impl std::error::Error for GameError {}
+
+/// The shapes of a cyclic game and the letters a strategy guide writes them
+/// with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    shapes: Vec<String>,
+    /// The opponent's letter for each shape
+    opponent_letters: Vec<char>,
+    /// The player's letter for each shape, in the first reading of the guide
+    player_letters: Vec<char>,
+    /// The letters for losing, tying and winning, in the second reading
+    outcome_letters: Vec<char>,
+}
+
+impl Game {
+    /// The opponent writes the shapes from `A` onwards and the player writes
+    /// them with letters ending at `Z`, as the puzzle does with three shapes.
+    /// Outcomes are `X`, `Y` and `Z` whatever the game.
+    pub fn new(shapes: &[&str]) -> Result<Self, GameError> {
+        let count = shapes.len();
+
+        if !(3..=MAX_SHAPES).contains(&count) || count.is_multiple_of(2) {
+            return Err(GameError::ShapeCount(count));
+        }
+
+        for (idx, shape) in shapes.iter().enumerate() {
+            if shapes[..idx].contains(shape) {
+                return Err(GameError::DuplicateShape(shape.to_string()));
+            }
+        }
+
+        Ok(Self {
+            shapes: shapes.iter().map(|shape| shape.to_string()).collect(),
+            opponent_letters: ('A'..='Z').take(count).collect(),
+            player_letters: ('A'..='Z').skip(26 - count).collect(),
+            outcome_letters: vec!['X', 'Y', 'Z'],
+        })
+    }
+
+    pub fn rock_paper_scissors() -> Self {
+        Self::new(&["Rock", "Paper", "Scissors"]).expect("The puzzle's game is valid")
+    }
+
+    /// Scissors cuts paper, paper covers rock, rock crushes lizard, lizard
+    /// poisons Spock, Spock smashes scissors, scissors decapitates lizard,
+    /// lizard eats paper, paper disproves Spock, Spock vaporizes rock and rock
+    /// crushes scissors
+    pub fn rock_paper_scissors_lizard_spock() -> Self {
+        Self::new(&["Rock", "Paper", "Scissors", "Spock", "Lizard"])
+            .expect("Rock paper scissors lizard Spock is valid")
+    }
+
+    /// RPS-7, which adds fire, air, water and sponge
+    pub fn rock_paper_scissors_7() -> Self {
+        Self::new(&[
+            "Rock", "Paper", "Fire", "Air", "Scissors", "Water", "Sponge",
+        ])
+        .expect("RPS-7 is valid")
+    }
+
+    /// The letters each side writes the shapes with, one per shape in the
+    /// order they're listed
+    pub fn with_letters(self, opponent: &str, player: &str) -> Result<Self, GameError> {
+        Ok(Self {
+            opponent_letters: self.letters(opponent, self.shapes.len())?,
+            player_letters: self.letters(player, self.shapes.len())?,
+            ..self
+        })
+    }
+
+    /// The letters for losing, tying and winning, in that order
+    pub fn with_outcome_letters(self, outcomes: &str) -> Result<Self, GameError> {
+        Ok(Self {
+            outcome_letters: self.letters(outcomes, 3)?,
+            ..self
+        })
+    }
+
+    fn letters(&self, letters: &str, count: usize) -> Result<Vec<char>, GameError> {
+        let chars = letters.chars().collect::<Vec<_>>();
+        let error = |problem| Err(GameError::Letters(letters.to_string(), problem));
+
+        if chars.len() != count {
+            return error("need one letter for each choice");
+        }
+
+        if chars.iter().any(|c| c.is_whitespace()) {
+            return error("can't include spaces");
+        }
+
+        if (1..count).any(|idx| chars[..idx].contains(&chars[idx])) {
+            return error("can't repeat a letter");
+        }
+
+        Ok(chars)
+    }
+
+    pub fn shape_count(&self) -> usize {
+        self.shapes.len()
+    }
+
+    /// Every shape, in the order they're listed
+    pub fn shapes(&self) -> impl Iterator<Item = Shape> + '_ {
+        (0..self.shapes.len()).map(|idx| Shape::new(idx, self.shapes.len()))
+    }
+
+    pub fn shape(&self, name: &str) -> Option<Shape> {
+        self.shapes
+            .iter()
+            .position(|shape| shape.eq_ignore_ascii_case(name))
+            .map(|idx| Shape::new(idx, self.shapes.len()))
+    }
+
+    pub fn name(&self, shape: Shape) -> &str {
+        &self.shapes[shape.index()]
+    }
+
+    /// Reads a guide where both columns are shapes, as in part 1 of the puzzle
+    pub fn parse_input_part_1(&self, input: impl BufRead) -> Result<Matchups<Shape>, ParseError> {
+        let [matchups] = read_guide(input, [&|line| self.read_moves(line)])?;
+
+        Ok(matchups)
+    }
+
+    /// Reads a guide where the second column is how the round should end, as in
+    /// part 2 of the puzzle
+    pub fn parse_input_part_2(&self, input: impl BufRead) -> Result<Matchups<Shape>, ParseError> {
+        let [matchups] = read_guide(input, [&|line| self.read_outcome(line)])?;
+
+        Ok(matchups)
+    }
+
+    fn read_moves<'a>(&self, line: &'a str) -> Result<SingleMatchup<Shape>, FragmentError<'a>> {
+        let (opponent_text, my_text) = split_line(line)?;
+
+        Ok((
+            self.read_shape(&self.opponent_letters, opponent_text)?,
+            self.read_shape(&self.player_letters, my_text)?,
+        ))
+    }
+
+    fn read_outcome<'a>(&self, line: &'a str) -> Result<SingleMatchup<Shape>, FragmentError<'a>> {
+        let (opponent_text, outcome_text) = split_line(line)?;
+        let opponent_move = self.read_shape(&self.opponent_letters, opponent_text)?;
+
+        let outcome = match letter_index(&self.outcome_letters, outcome_text) {
+            Some(0) => Outcome::Lose,
+            Some(1) => Outcome::Tie,
+            Some(2) => Outcome::Win,
+            _ => {
+                return Err(FragmentError::new(
+                    outcome_text,
+                    format!(
+                        "Expected one of {}",
+                        self.letter_list(&self.outcome_letters)
+                    ),
+                ))
+            }
+        };
+
+        Ok((opponent_move, opponent_move.to_get(outcome)))
+    }
+
+    fn read_shape<'a>(&self, letters: &[char], text: &'a str) -> Result<Shape, FragmentError<'a>> {
+        letter_index(letters, text)
+            .map(|idx| Shape::new(idx, self.shapes.len()))
+            .ok_or_else(|| {
+                FragmentError::new(
+                    text,
+                    format!("Expected one of {}", self.letter_list(letters)),
+                )
+            })
+    }
+
+    fn letter_list(&self, letters: &[char]) -> String {
+        letters
+            .iter()
+            .map(char::to_string)
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
+
+/// Where `text` is in `letters`, if it's a single one of them
+fn letter_index(letters: &[char], text: &str) -> Option<usize> {
+    let mut chars = text.chars();
+
+    match (chars.next(), chars.next()) {
+        (Some(letter), None) => letters.iter().position(|c| *c == letter),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::day_2::{get_total_scores, Rps};
+
+    static TEST_INPUT: &str = "A Y\nB X\nC Z\n";
+
+    fn games() -> [Game; 3] {
+        [
+            Game::rock_paper_scissors(),
+            Game::rock_paper_scissors_lizard_spock(),
+            Game::rock_paper_scissors_7(),
+        ]
+    }
+
+    fn beats(game: &Game, winner: &str, loser: &str) -> bool {
+        let winner = game.shape(winner).unwrap();
+        let loser = game.shape(loser).unwrap();
+
+        loser.battle(&winner) == Outcome::Win && winner.battle(&loser) == Outcome::Lose
+    }
+
+    #[test]
+    fn test_rock_paper_scissors() {
+        let game = Game::rock_paper_scissors();
+
+        for (rps, shape) in Rps::ALL.into_iter().zip(game.shapes()) {
+            assert_eq!(rps.shape(), shape);
+            assert_eq!(rps.score(), shape.score());
+        }
+
+        let moves = game.parse_input_part_1(TEST_INPUT.as_bytes()).unwrap();
+        let outcomes = game.parse_input_part_2(TEST_INPUT.as_bytes()).unwrap();
+
+        assert_eq!(get_total_scores(&moves), 15);
+        assert_eq!(get_total_scores(&outcomes), 12);
+    }
+
+    #[test]
+    fn test_every_shape_beats_half() {
+        for game in games() {
+            let count = game.shape_count();
+
+            for shape in game.shapes() {
+                let beaten = game
+                    .shapes()
+                    .filter(|other| other.battle(&shape) == Outcome::Win)
+                    .count();
+
+                assert_eq!(beaten, count / 2, "{} in {:?}", game.name(shape), game);
+                assert_eq!(shape.battle(&shape), Outcome::Tie);
+
+                for outcome in [Outcome::Lose, Outcome::Tie, Outcome::Win] {
+                    assert_eq!(shape.battle(&shape.to_get(outcome)), outcome);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_lizard_spock() {
+        let game = Game::rock_paper_scissors_lizard_spock();
+
+        for (winner, loser) in [
+            ("Scissors", "Paper"),
+            ("Paper", "Rock"),
+            ("Rock", "Lizard"),
+            ("Lizard", "Spock"),
+            ("Spock", "Scissors"),
+            ("Scissors", "Lizard"),
+            ("Lizard", "Paper"),
+            ("Paper", "Spock"),
+            ("Spock", "Rock"),
+            ("Rock", "Scissors"),
+        ] {
+            assert!(beats(&game, winner, loser), "{} beats {}", winner, loser);
+        }
+
+        let game = Game::rock_paper_scissors_7();
+
+        for (winner, loser) in [
+            ("Rock", "Fire"),
+            ("Fire", "Paper"),
+            ("Water", "Fire"),
+            ("Air", "Water"),
+            ("Sponge", "Water"),
+            ("Scissors", "Sponge"),
+            ("Paper", "Air"),
+        ] {
+            assert!(beats(&game, winner, loser), "{} beats {}", winner, loser);
+        }
+    }
+
+    #[test]
+    fn test_letters() {
+        let game = Game::rock_paper_scissors_lizard_spock();
+
+        // Rock ties, then paper loses to scissors
+        let moves = game.parse_input_part_1("A V\nC W\n".as_bytes()).unwrap();
+
+        assert_eq!(get_total_scores(&moves), 4 + 2);
+
+        // Lizard poisons Spock
+        let outcomes = game.parse_input_part_2("D Z\n".as_bytes()).unwrap();
+
+        assert_eq!(game.name(outcomes[0].1), "Lizard");
+        assert_eq!(get_total_scores(&outcomes), 6 + 5);
+
+        let game = game
+            .with_letters("rpsSl", "RPSKL")
+            .unwrap()
+            .with_outcome_letters("-=+")
+            .unwrap();
+
+        let moves = game.parse_input_part_1("S K\nl L\n".as_bytes()).unwrap();
+
+        assert_eq!(get_total_scores(&moves), (3 + 4) + (3 + 5));
+
+        let outcomes = game.parse_input_part_2("r -\n".as_bytes()).unwrap();
+
+        assert_eq!(game.name(outcomes[0].1), "Lizard");
+
+        let err = game.parse_input_part_1("r X\n".as_bytes()).unwrap_err();
+
+        assert_eq!((err.line, err.column), (1, 3));
+        assert_eq!(err.message, "Expected one of R/P/S/K/L");
+    }
+
+    #[test]
+    fn test_invalid_games() {
+        assert_eq!(Game::new(&["Rock", "Paper"]), Err(GameError::ShapeCount(2)));
+        assert_eq!(
+            Game::new(&["Rock", "Paper", "Scissors", "Spock"]),
+            Err(GameError::ShapeCount(4))
+        );
+        assert_eq!(
+            Game::new(&["Rock", "Paper", "Rock"]),
+            Err(GameError::DuplicateShape("Rock".to_string()))
+        );
+
+        let game = Game::rock_paper_scissors();
+
+        assert!(game.clone().with_letters("AB", "XYZ").is_err());
+        assert!(game.clone().with_letters("ABA", "XYZ").is_err());
+        assert!(game.clone().with_letters("A C", "XYZ").is_err());
+        assert!(game.with_outcome_letters("LTWX").is_err());
+    }
+}
@@ -0,0 +1,29 @@
+use std::io::{self, Write};
+
+use crate::rng::Rng;
+
+/// Lines of the strategy guide, as the opponent's letter and the second letter
+pub fn generate(rng: &mut Rng) -> Vec<(char, char)> {
+    (0..rng.count(1..=50))
+        .map(|_| (*rng.choose(&['A', 'B', 'C']), *rng.choose(&['X', 'Y', 'Z'])))
+        .collect()
+}
+
+pub fn serialize(guide: &[(char, char)]) -> String {
+    guide
+        .iter()
+        .map(|(opponent, second)| format!("{} {}\n", opponent, second))
+        .collect()
+}
+
+/// A guide `size` rounds long
+pub fn stress(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    for _ in 0..size {
+        let opponent = rng.choose(&['A', 'B', 'C']);
+        let second = rng.choose(&['X', 'Y', 'Z']);
+
+        writeln!(out, "{} {}", opponent, second)?;
+    }
+
+    Ok(())
+}
@@ -1,16 +1,60 @@
+//! Day 2: Rock Paper Scissors
+
+use std::io::BufRead;
+
+use crate::answer::Answer;
+use crate::error::{FragmentError, ParseError};
+use crate::normalize::LineReader;
+use crate::solution::Solution;
+
+pub mod decrypt;
+pub mod game;
+pub mod generate;
 pub mod input;
+pub mod strategy;
 
-#[derive(Debug, PartialEq)]
-enum Rps {
+use game::Shape;
+
+/// A shape, written `A`/`B`/`C` by the opponent and `X`/`Y`/`Z` in the first
+/// reading of the guide
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rps {
     Rock,
     Paper,
     Scissors,
 }
 
-trait Score {
+impl Rps {
+    pub const ALL: [Rps; 3] = [Rps::Rock, Rps::Paper, Rps::Scissors];
+
+    /// The same shape in [`game::Game::rock_paper_scissors`], which decides
+    /// how rounds end
+    pub fn shape(self) -> Shape {
+        Shape::new(self as usize, Rps::ALL.len())
+    }
+
+    fn from_shape(shape: Shape) -> Rps {
+        Rps::ALL[shape.index()]
+    }
+}
+
+/// Points a shape or a round's outcome adds to the score
+pub trait Score {
     fn score(&self) -> i32;
 }
 
+/// A shape that can be played, in the puzzle's game or any other cyclic one
+pub trait Hand: Score {
+    /// How a round ends for whoever plays `rhs` against `self`
+    fn battle(&self, rhs: &Self) -> Outcome;
+}
+
+impl Hand for Rps {
+    fn battle(&self, rhs: &Self) -> Outcome {
+        rps_battle(self, rhs)
+    }
+}
+
 impl Score for Rps {
     fn score(&self) -> i32 {
         match self {
@@ -21,21 +65,26 @@ impl Score for Rps {
     }
 }
 
-impl<'a> From<&'a str> for Rps {
-    fn from(s: &'a str) -> Self {
+impl<'a> TryFrom<&'a str> for Rps {
+    type Error = FragmentError<'a>;
+
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
         match s {
-            "A" => Rps::Rock,
-            "B" => Rps::Paper,
-            "C" => Rps::Scissors,
-            "X" => Rps::Rock,
-            "Y" => Rps::Paper,
-            "Z" => Rps::Scissors,
-            _ => panic!("Cannot convert input to Rps: {}", s),
+            "A" => Ok(Rps::Rock),
+            "B" => Ok(Rps::Paper),
+            "C" => Ok(Rps::Scissors),
+            "X" => Ok(Rps::Rock),
+            "Y" => Ok(Rps::Paper),
+            "Z" => Ok(Rps::Scissors),
+            _ => Err(FragmentError::new(s, "Cannot convert input to Rps")),
         }
     }
 }
 
-enum Outcome {
+/// How a round ends for the player, written `X`/`Y`/`Z` in the second
+/// reading of the guide
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
     Lose,
     Tie,
     Win,
@@ -51,107 +100,165 @@ impl Score for Outcome {
     }
 }
 
-impl<'a> From<&'a str> for Outcome {
-    fn from(s: &'a str) -> Self {
+impl<'a> TryFrom<&'a str> for Outcome {
+    type Error = FragmentError<'a>;
+
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
         match s {
-            "X" => Outcome::Lose,
-            "Y" => Outcome::Tie,
-            "Z" => Outcome::Win,
-            _ => panic!("Cannot convert that input to an Outcome: {}", s),
+            "X" => Ok(Outcome::Lose),
+            "Y" => Ok(Outcome::Tie),
+            "Z" => Ok(Outcome::Win),
+            _ => Err(FragmentError::new(
+                s,
+                "Cannot convert that input to an Outcome",
+            )),
         }
     }
 }
 
-fn what_to_choose(opponent: &Rps, intended_outcome: &Outcome) -> Rps {
-    match (opponent, intended_outcome) {
-        (Rps::Rock, Outcome::Lose) => Rps::Scissors,
-        (Rps::Rock, Outcome::Tie) => Rps::Rock,
-        (Rps::Rock, Outcome::Win) => Rps::Paper,
-        (Rps::Paper, Outcome::Lose) => Rps::Rock,
-        (Rps::Paper, Outcome::Tie) => Rps::Paper,
-        (Rps::Paper, Outcome::Win) => Rps::Scissors,
-        (Rps::Scissors, Outcome::Lose) => Rps::Paper,
-        (Rps::Scissors, Outcome::Tie) => Rps::Scissors,
-        (Rps::Scissors, Outcome::Win) => Rps::Rock,
-    }
+/// The shape to play against `opponent` to get `intended_outcome`
+pub fn what_to_choose(opponent: &Rps, intended_outcome: &Outcome) -> Rps {
+    Rps::from_shape(opponent.shape().to_get(*intended_outcome))
 }
 
-fn rps_battle(lhs: &Rps, rhs: &Rps) -> Outcome {
-    match (lhs, rhs) {
-        (Rps::Rock, Rps::Rock) => Outcome::Tie,
-        (Rps::Rock, Rps::Paper) => Outcome::Win,
-        (Rps::Rock, Rps::Scissors) => Outcome::Lose,
-        (Rps::Paper, Rps::Rock) => Outcome::Lose,
-        (Rps::Paper, Rps::Paper) => Outcome::Tie,
-        (Rps::Paper, Rps::Scissors) => Outcome::Win,
-        (Rps::Scissors, Rps::Rock) => Outcome::Win,
-        (Rps::Scissors, Rps::Paper) => Outcome::Lose,
-        (Rps::Scissors, Rps::Scissors) => Outcome::Tie,
-    }
+/// How a round ends for whoever plays `rhs` against `lhs`
+pub fn rps_battle(lhs: &Rps, rhs: &Rps) -> Outcome {
+    lhs.shape().battle(&rhs.shape())
 }
-type SingleMatchup = (Rps, Rps);
-type Matchups = Vec<SingleMatchup>;
 
-fn parse_input_part_1(input: &'static str) -> Matchups {
-    let mut matchups: Matchups = Default::default();
+/// The opponent's shape and the player's shape
+pub type SingleMatchup<H = Rps> = (H, H);
+pub type Matchups<H = Rps> = Vec<SingleMatchup<H>>;
 
-    for line in input.lines() {
-        if line.trim() == "" {
-            continue;
-        } else {
-            let mut split = line.trim().split(" ");
-            let opponent_move: Rps = split.next().unwrap().into();
-            let my_move: Rps = split.next().unwrap().into();
-            let matchup: SingleMatchup = (opponent_move, my_move);
-            matchups.push(matchup);
-        }
+/// Splits a guide line like "A Y" into its two letters
+fn split_line(line: &str) -> Result<(&str, &str), FragmentError<'_>> {
+    let mut split = line.split(" ");
+
+    let first = split.next().unwrap_or(line);
+    let second = split.next().ok_or(FragmentError::new(
+        line,
+        "Expected two letters separated by a space",
+    ))?;
+
+    if let Some(extra) = split.next() {
+        return Err(FragmentError::new(
+            extra,
+            "Unexpected text after the second letter",
+        ));
     }
 
-    matchups
+    Ok((first, second))
 }
 
-fn parse_input_part_2(input: &'static str) -> Matchups {
-    let mut matchups: Matchups = Default::default();
+/// The first reading of a guide line: the opponent's shape and the shape to play
+fn read_moves(line: &str) -> Result<SingleMatchup, FragmentError<'_>> {
+    let (opponent_text, my_text) = split_line(line)?;
+    let opponent_move: Rps = opponent_text.try_into()?;
+    let my_move: Rps = my_text.try_into()?;
+    Ok((opponent_move, my_move))
+}
+
+/// The second reading of a guide line: the opponent's shape and how the round
+/// should end, choosing the shape that ends it that way
+fn read_outcome(line: &str) -> Result<SingleMatchup, FragmentError<'_>> {
+    let (opponent_text, outcome_text) = split_line(line)?;
+    let opponent_move: Rps = opponent_text.try_into()?;
+    let intended_outcome: Outcome = outcome_text.try_into()?;
+    let my_move = what_to_choose(&opponent_move, &intended_outcome);
+    Ok((opponent_move, my_move))
+}
+
+/// One way of reading a line of the guide
+type Reading<'r, H> = &'r dyn Fn(&str) -> Result<SingleMatchup<H>, FragmentError<'_>>;
+
+/// Reads each line of the guide every way in `readings`, handing each round to
+/// `round` in the same order, so the guide only has to be read once however it
+/// is interpreted and never has to be kept
+fn read_rounds<H, const N: usize>(
+    input: impl BufRead,
+    readings: [Reading<'_, H>; N],
+    mut round: impl FnMut(usize, SingleMatchup<H>),
+) -> Result<(), ParseError> {
+    let mut lines = LineReader::new(Day2::DAY, input);
 
-    for line in input.lines() {
-        if line.trim() == "" {
+    while let Some(line) = lines.next_line()? {
+        if line.is_blank() {
             continue;
-        } else {
-            let mut split = line.trim().split(" ");
-            let opponent_move: Rps = split.next().unwrap().into();
-            let intended_outcome: Outcome = split.next().unwrap().into();
-            let my_move = what_to_choose(&opponent_move, &intended_outcome);
-            let matchup: SingleMatchup = (opponent_move, my_move);
-            matchups.push(matchup);
+        }
+
+        for (idx, reading) in readings.iter().enumerate() {
+            let matchup =
+                reading(line.text).map_err(|err| err.at(Day2::DAY, line.index, line.raw))?;
+            round(idx, matchup);
         }
     }
 
-    matchups
+    Ok(())
 }
 
-fn get_score(matchup: &SingleMatchup) -> i32 {
-    let outcome = rps_battle(&matchup.0, &matchup.1);
+/// Every round of the guide, read every way in `readings`
+fn read_guide<H, const N: usize>(
+    input: impl BufRead,
+    readings: [Reading<'_, H>; N],
+) -> Result<[Matchups<H>; N], ParseError> {
+    let mut matchups: [Matchups<H>; N] = std::array::from_fn(|_| Vec::new());
+
+    read_rounds(input, readings, |idx, matchup| matchups[idx].push(matchup))?;
+
+    Ok(matchups)
+}
+
+/// Reads the guide as the opponent's shape and the shape to play
+pub fn parse_input_part_1(input: impl BufRead) -> Result<Matchups, ParseError> {
+    let [matchups] = read_guide(input, [&read_moves])?;
+
+    Ok(matchups)
+}
+
+/// Reads the guide as the opponent's shape and how the round should end,
+/// choosing the shape that ends it that way
+pub fn parse_input_part_2(input: impl BufRead) -> Result<Matchups, ParseError> {
+    let [matchups] = read_guide(input, [&read_outcome])?;
+
+    Ok(matchups)
+}
+
+/// The player's score for one round
+pub fn get_score<H: Hand>(matchup: &SingleMatchup<H>) -> i32 {
+    let outcome = matchup.0.battle(&matchup.1);
     let my_choice = &matchup.1;
 
     outcome.score() + my_choice.score()
 }
 
-fn get_total_scores(matchups: &Matchups) -> i32 {
+pub fn get_total_scores<H: Hand>(matchups: &Matchups<H>) -> i32 {
     matchups
         .iter()
         .fold(0, |acc, matchup| acc + get_score(matchup))
 }
 
-pub fn day_2_part_1(input: &'static str) -> i32 {
-    let matchups = parse_input_part_1(input);
+pub struct Day2;
 
-    get_total_scores(&matchups)
-}
+impl Solution for Day2 {
+    // The guide is read both ways up front: as two moves, and as a move and an outcome
+    type Parsed = (Matchups, Matchups);
 
-pub fn day_2_part_2(input: &'static str) -> i32 {
-    let matchups = parse_input_part_2(input);
+    const DAY: u8 = 2;
+    const INPUT: &'static str = input::INPUT_DAY_2;
+
+    fn read(&self, input: &mut dyn BufRead) -> Result<Self::Parsed, ParseError> {
+        let [as_moves, as_outcomes] = read_guide(input, [&read_moves, &read_outcome])?;
+
+        Ok((as_moves, as_outcomes))
+    }
+
+    fn part_1(&self, (matchups, _): &Self::Parsed) -> Answer {
+        get_total_scores(matchups).into()
+    }
 
-    get_total_scores(&matchups)
+    fn part_2(&self, (_, matchups): &Self::Parsed) -> Answer {
+        get_total_scores(matchups).into()
+    }
 }
 
 #[cfg(test)]
@@ -166,18 +273,41 @@ mod test {
 
     #[test]
     fn part_1() {
-        let matchups = super::parse_input_part_1(TEST_INPUT);
+        let matchups = super::parse_input_part_1(TEST_INPUT.as_bytes()).unwrap();
 
         assert_eq!(get_total_scores(&matchups), 15);
     }
 
     #[test]
     fn part_2() {
-        let matchups = super::parse_input_part_2(TEST_INPUT);
+        let matchups = super::parse_input_part_2(TEST_INPUT.as_bytes()).unwrap();
 
         assert_eq!(get_total_scores(&matchups), 12);
     }
 
+    #[test]
+    fn test_rps_battle() {
+        use Outcome::*;
+        use Rps::*;
+
+        let table = [
+            (Rock, Rock, Tie),
+            (Rock, Paper, Win),
+            (Rock, Scissors, Lose),
+            (Paper, Rock, Lose),
+            (Paper, Paper, Tie),
+            (Paper, Scissors, Win),
+            (Scissors, Rock, Win),
+            (Scissors, Paper, Lose),
+            (Scissors, Scissors, Tie),
+        ];
+
+        for (lhs, rhs, outcome) in table {
+            assert_eq!(rps_battle(&lhs, &rhs), outcome);
+            assert_eq!(what_to_choose(&lhs, &outcome), rhs);
+        }
+    }
+
     #[test]
     fn correct_selection() {
         assert_eq!(what_to_choose(&Rps::Paper, &Outcome::Lose), Rps::Rock);
@@ -186,4 +316,60 @@ mod test {
 
         assert_eq!(what_to_choose(&Rps::Rock, &Outcome::Win), Rps::Paper);
     }
+
+    #[test]
+    fn test_invalid_guide() {
+        let err = parse_input_part_1("A Y\nB Q\n".as_bytes()).unwrap_err();
+
+        assert_eq!(
+            err,
+            ParseError::new(2, 2, 3, "Q", "Cannot convert input to Rps")
+        );
+
+        let err = parse_input_part_2("A Y\nBX\n".as_bytes()).unwrap_err();
+
+        assert_eq!(err.line, 2);
+        assert_eq!(err.text, "BX");
+    }
+
+    #[test]
+    fn test_windows_line_endings() {
+        let input = "\u{feff}A Y\r\nB X\r\nC Z\r\n";
+
+        assert_eq!(
+            parse_input_part_1(input.as_bytes()).unwrap(),
+            parse_input_part_1(TEST_INPUT.as_bytes()).unwrap()
+        );
+
+        let err = parse_input_part_1("\u{feff}A Y\r\nB Q\r\n".as_bytes()).unwrap_err();
+
+        assert_eq!(
+            err,
+            ParseError::new(2, 2, 3, "Q", "Cannot convert input to Rps")
+        );
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        crate::property::check(generate::generate, |guide| {
+            let (as_moves, as_outcomes) = Day2.parse(&generate::serialize(guide)).unwrap();
+
+            let letter = |c: char| Rps::try_from(c.to_string().as_str()).unwrap();
+
+            let expected = guide
+                .iter()
+                .map(|(opponent, second)| (letter(*opponent), letter(*second)))
+                .collect::<Vec<_>>();
+
+            assert_eq!(as_moves, expected);
+            assert_eq!(as_outcomes.len(), guide.len());
+
+            for ((opponent, second), (their_move, my_move)) in guide.iter().zip(&as_outcomes) {
+                let outcome = Outcome::try_from(second.to_string().as_str()).unwrap();
+
+                assert_eq!(*their_move, letter(*opponent));
+                assert_eq!(rps_battle(their_move, my_move).score(), outcome.score());
+            }
+        });
+    }
 }
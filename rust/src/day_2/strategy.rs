@@ -0,0 +1,477 @@
+//! Strategies that choose a shape each round instead of reading it from a
+//! guide, and a tournament that plays them against each other. Rounds are
+//! scored the same way as the puzzle's, with [`get_score`].
+
+use super::{get_score, rps_battle, what_to_choose, Outcome, Rps};
+use crate::rng::Rng;
+use crate::table::{format_table, Align};
+
+/// How many rounds each match of `advent day2 tournament` lasts
+pub const DEFAULT_ROUNDS: usize = 1000;
+
+/// Chooses a shape each round, and may learn from what its opponent plays
+pub trait Strategy {
+    fn name(&self) -> String;
+
+    /// The shape to play this round
+    fn choose(&mut self) -> Rps;
+
+    /// What both players chose in the round just played. Strategies that don't
+    /// adapt ignore it.
+    fn observe(&mut self, _mine: Rps, _theirs: Rps) {}
+}
+
+/// Plays the same shape every round
+pub struct Always(pub Rps);
+
+impl Strategy for Always {
+    fn name(&self) -> String {
+        format!("always {:?}", self.0).to_lowercase()
+    }
+
+    fn choose(&mut self) -> Rps {
+        self.0
+    }
+}
+
+/// Plays any shape with equal chance, the same way every time for a seed
+pub struct SeededRandom {
+    seed: u64,
+    rng: Rng,
+}
+
+impl SeededRandom {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: Rng::new(seed),
+        }
+    }
+}
+
+impl Strategy for SeededRandom {
+    fn name(&self) -> String {
+        format!("random (seed {})", self.seed)
+    }
+
+    fn choose(&mut self) -> Rps {
+        *self.rng.choose(&Rps::ALL)
+    }
+}
+
+/// Plays whatever beats the shape the opponent has played most often
+#[derive(Default)]
+pub struct FrequencyCounter {
+    counts: [usize; 3],
+}
+
+impl Strategy for FrequencyCounter {
+    fn name(&self) -> String {
+        "frequency counter".to_string()
+    }
+
+    fn choose(&mut self) -> Rps {
+        what_to_choose(&most_common(&self.counts), &Outcome::Win)
+    }
+
+    fn observe(&mut self, _mine: Rps, theirs: Rps) {
+        self.counts[theirs as usize] += 1;
+    }
+}
+
+/// Plays the player's shapes from a strategy guide in order, starting over
+/// once they run out. An empty guide plays rock.
+pub struct FollowGuide {
+    moves: Vec<Rps>,
+    next: usize,
+}
+
+impl FollowGuide {
+    pub fn new(moves: Vec<Rps>) -> Self {
+        Self { moves, next: 0 }
+    }
+}
+
+impl Strategy for FollowGuide {
+    fn name(&self) -> String {
+        "follow the guide".to_string()
+    }
+
+    fn choose(&mut self) -> Rps {
+        let Some(shape) = self.moves.get(self.next % self.moves.len().max(1)) else {
+            return Rps::Rock;
+        };
+
+        self.next += 1;
+        *shape
+    }
+}
+
+/// Predicts the opponent's next shape from what they have played after their
+/// last one before, and plays whatever beats it
+#[derive(Default)]
+pub struct Markov {
+    /// How often each shape followed each other shape
+    transitions: [[usize; 3]; 3],
+    last: Option<Rps>,
+    /// Stands in before there is a last shape to go on
+    counts: [usize; 3],
+}
+
+impl Strategy for Markov {
+    fn name(&self) -> String {
+        "markov predictor".to_string()
+    }
+
+    fn choose(&mut self) -> Rps {
+        let counts = match self.last {
+            Some(last) => &self.transitions[last as usize],
+            None => &self.counts,
+        };
+
+        what_to_choose(&most_common(counts), &Outcome::Win)
+    }
+
+    fn observe(&mut self, _mine: Rps, theirs: Rps) {
+        if let Some(last) = self.last {
+            self.transitions[last as usize][theirs as usize] += 1;
+        }
+
+        self.counts[theirs as usize] += 1;
+        self.last = Some(theirs);
+    }
+}
+
+/// The shape counted most, the earliest of [`Rps::ALL`] when several are
+fn most_common(counts: &[usize; 3]) -> Rps {
+    Rps::ALL
+        .into_iter()
+        .rev()
+        .max_by_key(|shape| counts[*shape as usize])
+        .expect("There are three shapes")
+}
+
+/// How one strategy fared over some rounds
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Record {
+    pub wins: usize,
+    pub ties: usize,
+    pub losses: usize,
+    pub score: i64,
+}
+
+impl Record {
+    pub fn rounds(&self) -> usize {
+        self.wins + self.ties + self.losses
+    }
+
+    /// The share of rounds that ended in each of a win, a tie and a loss
+    pub fn rates(&self) -> (f64, f64, f64) {
+        let rounds = self.rounds().max(1) as f64;
+
+        (
+            self.wins as f64 / rounds,
+            self.ties as f64 / rounds,
+            self.losses as f64 / rounds,
+        )
+    }
+
+    fn add(&mut self, other: &Record) {
+        self.wins += other.wins;
+        self.ties += other.ties;
+        self.losses += other.losses;
+        self.score += other.score;
+    }
+
+    fn record(&mut self, opponent: Rps, mine: Rps) {
+        match rps_battle(&opponent, &mine) {
+            Outcome::Win => self.wins += 1,
+            Outcome::Tie => self.ties += 1,
+            Outcome::Lose => self.losses += 1,
+        }
+
+        self.score += get_score(&(opponent, mine)) as i64;
+    }
+}
+
+/// Plays `rounds` rounds between two strategies, returning how each fared
+pub fn play_match(
+    first: &mut dyn Strategy,
+    second: &mut dyn Strategy,
+    rounds: usize,
+) -> (Record, Record) {
+    let mut first_record = Record::default();
+    let mut second_record = Record::default();
+
+    for _ in 0..rounds {
+        let first_shape = first.choose();
+        let second_shape = second.choose();
+
+        first_record.record(second_shape, first_shape);
+        second_record.record(first_shape, second_shape);
+
+        first.observe(first_shape, second_shape);
+        second.observe(second_shape, first_shape);
+    }
+
+    (first_record, second_record)
+}
+
+/// Makes a fresh strategy for every match, so nothing learned in one carries
+/// over to the next
+pub type Entrant<'a> = Box<dyn Fn() -> Box<dyn Strategy> + 'a>;
+
+/// The five strategies `advent day2 tournament` plays, following `guide` and
+/// playing randomly from `seed`
+pub fn default_entrants(guide: &[Rps], seed: u64) -> Vec<Entrant<'_>> {
+    vec![
+        Box::new(|| Box::new(Always(Rps::Rock))),
+        Box::new(move || Box::new(SeededRandom::new(seed))),
+        Box::new(|| Box::<FrequencyCounter>::default()),
+        Box::new(|| Box::new(FollowGuide::new(guide.to_vec()))),
+        Box::new(|| Box::<Markov>::default()),
+    ]
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchResult {
+    pub first: String,
+    pub second: String,
+    pub first_record: Record,
+    pub second_record: Record,
+}
+
+/// Every match of a tournament, and how each strategy did across all of them,
+/// best score first
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tournament {
+    pub matches: Vec<MatchResult>,
+    pub standings: Vec<(String, Record)>,
+}
+
+/// Plays every strategy against every other once, for `rounds` rounds each
+pub fn round_robin(entrants: &[Entrant], rounds: usize) -> Tournament {
+    let names = entrants
+        .iter()
+        .map(|make| make().name())
+        .collect::<Vec<_>>();
+    let mut totals = vec![Record::default(); entrants.len()];
+    let mut matches = Vec::new();
+
+    for first in 0..entrants.len() {
+        for second in first + 1..entrants.len() {
+            let (first_record, second_record) =
+                play_match(&mut *entrants[first](), &mut *entrants[second](), rounds);
+
+            totals[first].add(&first_record);
+            totals[second].add(&second_record);
+
+            matches.push(MatchResult {
+                first: names[first].clone(),
+                second: names[second].clone(),
+                first_record,
+                second_record,
+            });
+        }
+    }
+
+    let mut standings = names.into_iter().zip(totals).collect::<Vec<_>>();
+    standings.sort_by_key(|(_, record)| std::cmp::Reverse(record.score));
+
+    Tournament { matches, standings }
+}
+
+/// The report `advent day2 tournament` prints: each match, then the standings
+pub fn format_tournament(tournament: &Tournament) -> String {
+    let percent = |rate: f64| format!("{:.1}%", rate * 100.0);
+
+    let matches = tournament
+        .matches
+        .iter()
+        .map(|result| {
+            let (wins, ties, losses) = result.first_record.rates();
+
+            vec![
+                result.first.clone(),
+                result.second.clone(),
+                percent(wins),
+                percent(ties),
+                percent(losses),
+                result.first_record.score.to_string(),
+                result.second_record.score.to_string(),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let standings = tournament
+        .standings
+        .iter()
+        .map(|(name, record)| {
+            let (wins, ties, losses) = record.rates();
+
+            vec![
+                name.clone(),
+                percent(wins),
+                percent(ties),
+                percent(losses),
+                record.score.to_string(),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let rates = [
+        ("Win", Align::Right),
+        ("Tie", Align::Right),
+        ("Loss", Align::Right),
+    ];
+
+    format!(
+        "{}\n\n{}",
+        format_table(
+            &[
+                &[("Strategy", Align::Left), ("Opponent", Align::Left)],
+                &rates[..],
+                &[("Score", Align::Right), ("Against", Align::Right)],
+            ]
+            .concat(),
+            &matches,
+        ),
+        format_table(
+            &[
+                &[("Strategy", Align::Left)],
+                &rates[..],
+                &[("Score", Align::Right)],
+            ]
+            .concat(),
+            &standings,
+        )
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_play_match() {
+        let (rock, frequency) =
+            play_match(&mut Always(Rps::Rock), &mut FrequencyCounter::default(), 10);
+
+        // Paper beats rock from the first round, scoring 6 + 2 against 0 + 1
+        assert_eq!(
+            frequency,
+            Record {
+                wins: 10,
+                ties: 0,
+                losses: 0,
+                score: 80,
+            }
+        );
+        assert_eq!(
+            rock,
+            Record {
+                wins: 0,
+                ties: 0,
+                losses: 10,
+                score: 10,
+            }
+        );
+
+        let (first, second) = play_match(&mut Always(Rps::Paper), &mut Always(Rps::Paper), 4);
+
+        assert_eq!(first, second);
+        assert_eq!((first.ties, first.score), (4, 20));
+    }
+
+    #[test]
+    fn test_markov_learns_a_cycle() {
+        let mut cycle = FollowGuide::new(Rps::ALL.to_vec());
+        let (markov, _) = play_match(&mut Markov::default(), &mut cycle, 300);
+
+        // A cycle is lost to until every transition has been seen once
+        assert!(markov.wins >= 295, "{:?}", markov);
+
+        // Counting shapes alone can't see a cycle coming
+        let mut cycle = FollowGuide::new(Rps::ALL.to_vec());
+        let (frequency, _) = play_match(&mut FrequencyCounter::default(), &mut cycle, 300);
+
+        assert!(frequency.wins < 150, "{:?}", frequency);
+    }
+
+    #[test]
+    fn test_follow_guide() {
+        let mut guide = FollowGuide::new(vec![Rps::Paper, Rps::Scissors]);
+        let shapes = (0..5).map(|_| guide.choose()).collect::<Vec<_>>();
+
+        assert_eq!(
+            shapes,
+            vec![
+                Rps::Paper,
+                Rps::Scissors,
+                Rps::Paper,
+                Rps::Scissors,
+                Rps::Paper
+            ]
+        );
+        assert_eq!(FollowGuide::new(Vec::new()).choose(), Rps::Rock);
+    }
+
+    #[test]
+    fn test_seeded_random() {
+        let shapes = |seed| {
+            let mut random = SeededRandom::new(seed);
+            (0..50).map(|_| random.choose()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(shapes(3), shapes(3));
+        assert_ne!(shapes(3), shapes(4));
+        assert!(Rps::ALL.iter().all(|shape| shapes(3).contains(shape)));
+    }
+
+    #[test]
+    fn test_round_robin() {
+        let guide = [Rps::Paper, Rps::Rock, Rps::Scissors];
+        let tournament = round_robin(&default_entrants(&guide, 1), 100);
+
+        // Five strategies make ten pairs
+        assert_eq!(tournament.matches.len(), 10);
+        assert_eq!(tournament.standings.len(), 5);
+
+        for (_, record) in &tournament.standings {
+            assert_eq!(record.rounds(), 400);
+        }
+
+        let scores = tournament
+            .standings
+            .iter()
+            .map(|(_, record)| record.score)
+            .collect::<Vec<_>>();
+
+        assert!(scores.windows(2).all(|pair| pair[0] >= pair[1]));
+        assert_eq!(tournament.standings[4].0, "always rock");
+
+        // Each match is played from scratch, so the same tournament comes out
+        assert_eq!(round_robin(&default_entrants(&guide, 1), 100), tournament);
+    }
+
+    #[test]
+    fn test_format_tournament() {
+        let entrants: Vec<Entrant> = vec![
+            Box::new(|| Box::new(Always(Rps::Rock))),
+            Box::new(|| Box::<FrequencyCounter>::default()),
+        ];
+
+        assert_eq!(
+            format_tournament(&round_robin(&entrants, 10)),
+            [
+                "Strategy     Opponent            Win   Tie    Loss  Score  Against",
+                "always rock  frequency counter  0.0%  0.0%  100.0%     10       80",
+                "",
+                "Strategy              Win   Tie    Loss  Score",
+                "frequency counter  100.0%  0.0%    0.0%     80",
+                "always rock          0.0%  0.0%  100.0%     10",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -0,0 +1,87 @@
+use std::io::{self, Write};
+
+use crate::rng::Rng;
+
+#[derive(Debug)]
+pub struct Rucksack {
+    pub first: String,
+    pub second: String,
+    /// The only item in both compartments
+    pub shared: char,
+}
+
+#[derive(Debug)]
+pub struct Group {
+    /// The only item all three rucksacks carry
+    pub badge: char,
+    pub rucksacks: Vec<Rucksack>,
+}
+
+pub fn generate(rng: &mut Rng) -> Vec<Group> {
+    (0..rng.count(1..=10))
+        .map(|_| generate_group(rng))
+        .collect()
+}
+
+fn generate_group(rng: &mut Rng) -> Group {
+    let mut letters = ('a'..='z').chain('A'..='Z').collect::<Vec<_>>();
+    rng.shuffle(&mut letters);
+
+    // Each rucksack draws from its own letters so nothing but the badge is common to all three
+    let badge = letters[0];
+    let rucksacks = letters[1..]
+        .chunks(letters.len() / 3)
+        .take(3)
+        .map(|pool| generate_rucksack(rng, badge, pool))
+        .collect();
+
+    Group { badge, rucksacks }
+}
+
+fn generate_rucksack(rng: &mut Rng, badge: char, pool: &[char]) -> Rucksack {
+    // The rest of the pool is split so the shared item is the only one in both compartments
+    let shared = pool[0];
+    let (first_pool, second_pool) = pool[1..].split_at(pool.len() / 2);
+    let size = rng.count(2..=12);
+    let badge_first = rng.bool();
+
+    let mut fill = |pool: &[char], has_badge: bool| {
+        let mut items = vec![shared];
+        if has_badge {
+            items.push(badge);
+        }
+        while items.len() < size {
+            items.push(*rng.choose(pool));
+        }
+        rng.shuffle(&mut items);
+        items.into_iter().collect::<String>()
+    };
+
+    let first = fill(first_pool, badge_first);
+    let second = fill(second_pool, !badge_first);
+
+    Rucksack {
+        first,
+        second,
+        shared,
+    }
+}
+
+pub fn serialize(groups: &[Group]) -> String {
+    groups
+        .iter()
+        .flat_map(|group| &group.rucksacks)
+        .map(|rucksack| format!("{}{}\n", rucksack.first, rucksack.second))
+        .collect()
+}
+
+/// `size` groups of three rucksacks
+pub fn stress(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    for _ in 0..size {
+        for rucksack in generate_group(rng).rucksacks {
+            writeln!(out, "{}{}", rucksack.first, rucksack.second)?;
+        }
+    }
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-pub static INPUT_DAY_3: &'static str = r#"
+pub static INPUT_DAY_3: &str = r#"
 GwrhJPDJCZFRcwfZWV
 LjnQlqNpjjmpmQlLlqNfZRvQcTWcTSTTZcSQcZ
 nNqjdspspngnmjmslqmjjjCDGrHPHMGddGCMCGPPPJWC
@@ -299,4 +299,4 @@ TVcTfHNFcwtjMhTvgbHZsBbWmmZbnH
 WsQgstQmvQJnssWsWPzhRzhBjZBSBRZSnj
 qwCNqFwDrrlDrFPvRhTSPPzLRz
 bppqwppCddlvfbDNVgmMmtMfVVmfmVWW
-"#;
\ No newline at end of file
+"#;
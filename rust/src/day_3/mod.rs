@@ -1,3 +1,13 @@
+//! Day 3: Rucksack Reorganization
+
+use std::io::BufRead;
+
+use crate::answer::Answer;
+use crate::error::{FragmentError, ParseError};
+use crate::normalize::LineReader;
+use crate::solution::Solution;
+
+pub mod generate;
 pub mod input;
 
 const LOWERCASE_A: u8 = 97;
@@ -7,14 +17,16 @@ const UPPERCASE_Z: u8 = 90;
 const ALPHABET_OFFSET: u8 = 26;
 
 fn is_lowercase(byte: u8) -> bool {
-    byte >= LOWERCASE_A && byte <= LOWERCASE_Z
+    (LOWERCASE_A..=LOWERCASE_Z).contains(&byte)
 }
 
 fn is_uppercase(byte: u8) -> bool {
-    byte >= UPPERCASE_A && byte <= UPPERCASE_Z
+    (UPPERCASE_A..=UPPERCASE_Z).contains(&byte)
 }
 
-fn get_priority(byte: u8) -> i32 {
+/// `a` to `z` are worth 1 to 26 and `A` to `Z` are worth 27 to 52. Panics on
+/// anything else, which [`Container::new`] never lets through.
+pub fn get_priority(byte: u8) -> i32 {
     if is_lowercase(byte) {
         (byte - LOWERCASE_A + 1) as i32
     } else if is_uppercase(byte) {
@@ -24,45 +36,50 @@ fn get_priority(byte: u8) -> i32 {
     }
 }
 
+/// A rucksack, split into its two equal compartments
 #[derive(Debug)]
-
-struct Container {
+pub struct Container {
+    line_idx: usize,
     first: Vec<u8>,
     second: Vec<u8>,
     matching_item: u8,
 }
 
+/// A group of three elves' rucksacks and the badge they all carry
 #[derive(Debug)]
-struct ContainerTriple<'a> {
+pub struct ContainerTriple<'a> {
+    #[allow(dead_code)]
     a: &'a Container,
+    #[allow(dead_code)]
     b: &'a Container,
+    #[allow(dead_code)]
     c: &'a Container,
     matching_badge: u8,
 }
 
 impl<'a> ContainerTriple<'a> {
-    pub fn new(a: &'a Container, b: &'a Container, c: &'a Container) -> Self {
-        let matching_badge: u8 = a
-            .first
-            .iter()
-            .zip(a.second.iter())
-            .find_map(|(val_a, val_b)| {
-                if b.contains_item(val_a) && c.contains_item(val_a) {
-                    Some(*val_a)
-                } else if b.contains_item(&val_b) && c.contains_item(&val_b) {
-                    Some(*val_b)
-                } else {
-                    None
-                }
-            })
-            .expect("Triple did not contain any matching badge");
-
-        Self {
+    /// Groups three rucksacks, or `None` if no item is in all three
+    pub fn new(a: &'a Container, b: &'a Container, c: &'a Container) -> Option<Self> {
+        let matching_badge: u8 =
+            a.first
+                .iter()
+                .zip(a.second.iter())
+                .find_map(|(val_a, val_b)| {
+                    if b.contains_item(val_a) && c.contains_item(val_a) {
+                        Some(*val_a)
+                    } else if b.contains_item(val_b) && c.contains_item(val_b) {
+                        Some(*val_b)
+                    } else {
+                        None
+                    }
+                })?;
+
+        Some(Self {
             a,
             b,
             c,
             matching_badge,
-        }
+        })
     }
 
     pub fn matching_badge_char(&self) -> char {
@@ -75,7 +92,18 @@ impl<'a> ContainerTriple<'a> {
 }
 
 impl Container {
-    pub fn new(line: &'static str) -> Self {
+    /// Splits a line of item letters in half. The halves must have an item in common.
+    pub fn new(line: &str) -> Result<Self, FragmentError<'_>> {
+        if let Some((idx, character)) = line
+            .char_indices()
+            .find(|(_, character)| !character.is_ascii_alphabetic())
+        {
+            return Err(FragmentError::new(
+                &line[idx..idx + character.len_utf8()],
+                "Did not receive an upper or lowercase letter",
+            ));
+        }
+
         let mut first: Vec<u8> = Vec::new();
         let mut second: Vec<u8> = Vec::new();
 
@@ -92,15 +120,25 @@ impl Container {
         let matching_item = *first
             .iter()
             .find(|byte| second.contains(byte))
-            .expect("Couldn't find a matching item");
+            .ok_or(FragmentError::new(line, "Couldn't find a matching item"))?;
 
-        Self {
+        Ok(Self {
+            line_idx: 0,
             first,
             second,
             matching_item,
-        }
+        })
     }
 
+    /// The original line, rebuilt from both compartments
+    pub fn contents(&self) -> String {
+        let mut bytes = self.first.clone();
+        bytes.extend(&self.second);
+
+        String::from_utf8_lossy(&bytes).to_string()
+    }
+
+    /// The item in both compartments
     pub fn matching_item(&self) -> u8 {
         self.matching_item
     }
@@ -118,6 +156,7 @@ impl Container {
     }
 }
 
+/// Sums priorities across rucksacks or groups
 pub trait Priority {
     fn total_priority(&self) -> i32;
 }
@@ -135,42 +174,85 @@ impl<'a> Priority for Vec<ContainerTriple<'a>> {
     }
 }
 
-fn parse_input(input: &'static str) -> Vec<Container> {
+/// Reads one rucksack per line
+pub fn parse_input(input: impl BufRead) -> Result<Vec<Container>, ParseError> {
     let mut containers = Vec::new();
-    for line in input.lines() {
-        let trimmed_line = line.trim();
-        if trimmed_line == "" {
+    let mut lines = LineReader::new(Day3::DAY, input);
+
+    while let Some(line) = lines.next_line()? {
+        if line.is_blank() {
             continue;
         } else {
-            let container = Container::new(trimmed_line);
+            let mut container =
+                Container::new(line.text).map_err(|err| err.at(Day3::DAY, line.index, line.raw))?;
+            container.line_idx = line.index;
 
             containers.push(container);
         }
     }
-    containers
+    Ok(containers)
 }
 
-fn parse_triples<'a>(input: &'a Vec<Container>) -> Vec<ContainerTriple<'a>> {
-    let triples = input
-        .chunks(3)
-        .map(|chunk| ContainerTriple::new(&chunk[0], &chunk[1], &chunk[2]))
-        .collect::<Vec<_>>();
+/// Groups rucksacks in threes, in order, and finds each group's badge
+pub fn parse_triples(input: &[Container]) -> Result<Vec<ContainerTriple<'_>>, ParseError> {
+    let mut triples = Vec::new();
+
+    for chunk in input.chunks(3) {
+        let triple = match chunk {
+            [a, b, c] => ContainerTriple::new(a, b, c).ok_or_else(|| {
+                ParseError::new(
+                    Day3::DAY,
+                    a.line_idx + 1,
+                    1,
+                    a.contents(),
+                    "Triple did not contain any matching badge",
+                )
+            })?,
+            _ => {
+                let last = &chunk[chunk.len() - 1];
+
+                return Err(ParseError::new(
+                    Day3::DAY,
+                    last.line_idx + 1,
+                    1,
+                    last.contents(),
+                    "Rucksacks do not divide into groups of three",
+                ));
+            }
+        };
 
-    triples
+        triples.push(triple);
+    }
+
+    Ok(triples)
 }
 
-pub fn day_3_part_1(input: &'static str) -> i32 {
-    let containers = parse_input(input);
+pub struct Day3;
 
-    containers.total_priority()
-}
+impl Solution for Day3 {
+    type Parsed = Vec<Container>;
 
-pub fn day_3_part_2(input: &'static str) -> i32 {
-    let containers = parse_input(input);
+    const DAY: u8 = 3;
+    const INPUT: &'static str = input::INPUT_DAY_3;
 
-    let triples = parse_triples(&containers);
+    fn read(&self, input: &mut dyn BufRead) -> Result<Self::Parsed, ParseError> {
+        let containers = parse_input(input)?;
 
-    triples.total_priority()
+        // Groups are only used by part 2, but check them here so bad input is reported early
+        parse_triples(&containers)?;
+
+        Ok(containers)
+    }
+
+    fn part_1(&self, containers: &Self::Parsed) -> Answer {
+        containers.total_priority().into()
+    }
+
+    fn part_2(&self, containers: &Self::Parsed) -> Answer {
+        let triples = parse_triples(containers).expect("Groups are checked when parsing");
+
+        triples.total_priority().into()
+    }
 }
 
 #[cfg(test)]
@@ -191,7 +273,7 @@ mod test {
         // In the above example, the priority of the item type that appears in both compartments
         // of each rucksack is 16 (p), 38 (L), 42 (P), 22 (v), 20 (t), and 19 (s); the sum of these is 157.
 
-        let containers = parse_input(TEST_INPUT);
+        let containers = parse_input(TEST_INPUT.as_bytes()).unwrap();
 
         assert_eq!(containers[0].matching_item_char(), 'p');
 
@@ -212,9 +294,9 @@ mod test {
         // Priorities for these items must still be found to organize the sticker attachment efforts: here, they are 18 (r)
         // for the first group and 52 (Z) for the second group. The sum of these is 70.
 
-        let containers = parse_input(TEST_INPUT);
+        let containers = parse_input(TEST_INPUT.as_bytes()).unwrap();
 
-        let triples = parse_triples(&containers);
+        let triples = parse_triples(&containers).unwrap();
 
         dbg!("{}", &triples);
 
@@ -228,4 +310,54 @@ mod test {
 
         assert_eq!(triples.total_priority(), 52 + 18);
     }
+
+    #[test]
+    fn test_invalid_rucksacks() {
+        let err = parse_input("vJrwpWtwJgWrhcsFMMfFFhFp\n  abc1ef\n".as_bytes()).unwrap_err();
+
+        assert_eq!(
+            err,
+            ParseError::new(3, 2, 6, "1", "Did not receive an upper or lowercase letter")
+        );
+
+        let err = parse_input("abcdef\n".as_bytes()).unwrap_err();
+
+        assert_eq!(err.message, "Couldn't find a matching item");
+
+        let containers =
+            parse_input("vJrwpWtwJgWrhcsFMMfFFhFp\nttgJtRGJQctTZtZT\n".as_bytes()).unwrap();
+        let err = parse_triples(&containers).unwrap_err();
+
+        assert_eq!(err.line, 2);
+        assert_eq!(err.text, "ttgJtRGJQctTZtZT");
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        crate::property::check(generate::generate, |groups| {
+            let containers = Day3.parse(&generate::serialize(groups)).unwrap();
+            let rucksacks = groups.iter().flat_map(|group| &group.rucksacks);
+
+            assert_eq!(containers.len(), groups.len() * 3);
+
+            for (container, rucksack) in containers.iter().zip(rucksacks) {
+                assert_eq!(
+                    container.contents(),
+                    format!("{}{}", rucksack.first, rucksack.second)
+                );
+                assert_eq!(container.matching_item_char(), rucksack.shared);
+            }
+
+            let triples = parse_triples(&containers).unwrap();
+            let badges = triples
+                .iter()
+                .map(ContainerTriple::matching_badge_char)
+                .collect::<Vec<_>>();
+
+            assert_eq!(
+                badges,
+                groups.iter().map(|group| group.badge).collect::<Vec<_>>()
+            );
+        });
+    }
 }
@@ -0,0 +1,48 @@
+use std::io::{self, Write};
+
+use super::{CleaningAssignment, CleaningAssignmentPair};
+use crate::rng::Rng;
+
+fn generate_assignment(rng: &mut Rng) -> CleaningAssignment {
+    let start = rng.range(1..=99) as i32;
+    let end = rng.range(start as i64..=99) as i32;
+
+    CleaningAssignment { start, end }
+}
+
+pub fn generate(rng: &mut Rng) -> Vec<CleaningAssignmentPair> {
+    (0..rng.count(1..=50))
+        .map(|_| CleaningAssignmentPair {
+            left: generate_assignment(rng),
+            right: generate_assignment(rng),
+        })
+        .collect()
+}
+
+pub fn serialize(pairs: &[CleaningAssignmentPair]) -> String {
+    pairs
+        .iter()
+        .map(|pair| {
+            format!(
+                "{}-{},{}-{}\n",
+                pair.left.start, pair.left.end, pair.right.start, pair.right.end
+            )
+        })
+        .collect()
+}
+
+/// `size` pairs of elves
+pub fn stress(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    for _ in 0..size {
+        let left = generate_assignment(rng);
+        let right = generate_assignment(rng);
+
+        writeln!(
+            out,
+            "{}-{},{}-{}",
+            left.start, left.end, right.start, right.end
+        )?;
+    }
+
+    Ok(())
+}
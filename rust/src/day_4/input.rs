@@ -1,4 +1,4 @@
-pub static INPUT_DAY_4: &'static str = r#"
+pub static INPUT_DAY_4: &str = r#"
 8-17,16-49
 17-38,18-36
 17-43,43-43
@@ -999,4 +999,4 @@ pub static INPUT_DAY_4: &'static str = r#"
 26-26,26-99
 42-83,78-79
 5-17,4-67
-"#;
\ No newline at end of file
+"#;
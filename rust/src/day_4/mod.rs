@@ -1,25 +1,60 @@
+//! Day 4: Camp Cleanup
+
+use std::io::BufRead;
+
+use crate::answer::Answer;
+use crate::error::{FragmentError, ParseError};
+use crate::normalize::LineReader;
+use crate::solution::Solution;
+
+pub mod generate;
 pub mod input;
 
+/// An inclusive range of section IDs
 #[derive(Debug, PartialEq)]
-struct CleaningAssignment {
+pub struct CleaningAssignment {
     start: i32,
     end: i32,
 }
 
 impl CleaningAssignment {
-    pub fn from_str_range(str_range: &'static str) -> Self {
+    /// Reads a range like `2-4`, which must not start after it ends
+    pub fn from_str_range(str_range: &str) -> Result<Self, FragmentError<'_>> {
         // str_range is the form "9-10" or "50-60"
 
-        let mut start_and_end = str_range.trim().split("-");
+        let trimmed_range = str_range.trim();
+
+        let (start_text, end_text) = trimmed_range
+            .split_once("-")
+            .ok_or(FragmentError::new(trimmed_range, "Range is missing a '-'"))?;
+
+        let start = start_text
+            .parse::<i32>()
+            .map_err(|_| FragmentError::new(start_text, "Start value invalid"))?;
+        let end = end_text
+            .parse::<i32>()
+            .map_err(|_| FragmentError::new(end_text, "End value invalid"))?;
 
-        let start =
-            i32::from_str_radix(start_and_end.next().unwrap(), 10).expect("Start value invalid");
-        let end =
-            i32::from_str_radix(start_and_end.next().unwrap(), 10).expect("End value invalid");
+        if start > end {
+            return Err(FragmentError::new(
+                trimmed_range,
+                "Range starts after it ends",
+            ));
+        }
+
+        Ok(CleaningAssignment { start, end })
+    }
+
+    pub fn start(&self) -> i32 {
+        self.start
+    }
 
-        CleaningAssignment { start, end }
+    pub fn end(&self) -> i32 {
+        self.end
     }
 
+    /// Whether either range contains the other
+    #[allow(clippy::if_same_then_else, clippy::needless_bool)]
     pub fn fully_overlaps_with(&self, other: &CleaningAssignment) -> bool {
         if self.start >= other.start && self.end <= other.end {
             true
@@ -30,6 +65,8 @@ impl CleaningAssignment {
         }
     }
 
+    /// Whether the ranges share any section
+    #[allow(clippy::if_same_then_else, clippy::needless_bool)]
     pub fn partially_overlaps_with(&self, other: &CleaningAssignment) -> bool {
         if self.start >= other.start && self.start <= other.end {
             true
@@ -45,24 +82,36 @@ impl CleaningAssignment {
     }
 }
 
+/// The assignments of a pair of elves
 #[derive(Debug, PartialEq)]
-struct CleaningAssignmentPair {
+pub struct CleaningAssignmentPair {
     left: CleaningAssignment,
     right: CleaningAssignment,
 }
 
 impl CleaningAssignmentPair {
-    pub fn from_str_range_pair(str_range_pair: &'static str) -> Self {
+    /// Reads two comma separated ranges like `2-4,6-8`
+    pub fn from_str_range_pair(str_range_pair: &str) -> Result<Self, FragmentError<'_>> {
         // str_range is the form "1-2,9-10" or "10-20,50-60"
 
-        let mut left_and_right = str_range_pair.trim().split(",");
+        let trimmed_pair = str_range_pair.trim();
 
-        let left =
-            CleaningAssignment::from_str_range(left_and_right.next().expect("Left range invalid"));
-        let right =
-            CleaningAssignment::from_str_range(left_and_right.next().expect("Right range invalid"));
+        let (left_text, right_text) = trimmed_pair
+            .split_once(",")
+            .ok_or(FragmentError::new(trimmed_pair, "Right range invalid"))?;
 
-        Self { left, right }
+        let left = CleaningAssignment::from_str_range(left_text)?;
+        let right = CleaningAssignment::from_str_range(right_text)?;
+
+        Ok(Self { left, right })
+    }
+
+    pub fn left(&self) -> &CleaningAssignment {
+        &self.left
+    }
+
+    pub fn right(&self) -> &CleaningAssignment {
+        &self.right
     }
 
     pub fn fully_overlaps(&self) -> bool {
@@ -74,7 +123,8 @@ impl CleaningAssignmentPair {
     }
 }
 
-trait Overlap {
+/// Counts the pairs whose assignments overlap
+pub trait Overlap {
     fn overlapping_assignments(&self) -> i32;
 
     fn partially_overlapping_assignments(&self) -> i32;
@@ -99,32 +149,50 @@ impl Overlap for Vec<CleaningAssignmentPair> {
     }
 }
 
-fn parse_input_to_pairs(input: &'static str) -> Vec<CleaningAssignmentPair> {
+/// Reads one pair per line
+pub fn parse_input_to_pairs(
+    input: impl BufRead,
+) -> Result<Vec<CleaningAssignmentPair>, ParseError> {
     let mut pairs = Vec::new();
-    for line in input.lines() {
-        if line.trim() == "" {
+    let mut lines = LineReader::new(Day4::DAY, input);
+
+    while let Some(line) = lines.next_line()? {
+        if line.is_blank() {
             continue;
         }
 
-        pairs.push(CleaningAssignmentPair::from_str_range_pair(line));
+        let pair = CleaningAssignmentPair::from_str_range_pair(line.text)
+            .map_err(|err| err.at(Day4::DAY, line.index, line.raw))?;
+
+        pairs.push(pair);
     }
 
-    pairs
+    Ok(pairs)
 }
 
-pub fn day_4_part_1(input: &'static str) -> i32 {
-    let pairs = parse_input_to_pairs(input);
+pub struct Day4;
 
-    pairs.overlapping_assignments()
-}
+impl Solution for Day4 {
+    type Parsed = Vec<CleaningAssignmentPair>;
 
-pub fn day_4_part_2(input: &'static str) -> i32 {
-    let pairs = parse_input_to_pairs(input);
+    const DAY: u8 = 4;
+    const INPUT: &'static str = input::INPUT_DAY_4;
 
-    pairs.partially_overlapping_assignments()
+    fn read(&self, input: &mut dyn BufRead) -> Result<Self::Parsed, ParseError> {
+        parse_input_to_pairs(input)
+    }
+
+    fn part_1(&self, pairs: &Self::Parsed) -> Answer {
+        pairs.overlapping_assignments().into()
+    }
+
+    fn part_2(&self, pairs: &Self::Parsed) -> Answer {
+        pairs.partially_overlapping_assignments().into()
+    }
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod test {
     use super::*;
 
@@ -132,7 +200,7 @@ mod test {
     fn test_from_str_range() {
         let val = CleaningAssignment { start: 10, end: 20 };
 
-        assert_eq!(val, CleaningAssignment::from_str_range("10-20"));
+        assert_eq!(val, CleaningAssignment::from_str_range("10-20").unwrap());
     }
 
     #[test]
@@ -144,59 +212,59 @@ mod test {
 
         assert_eq!(
             val,
-            CleaningAssignmentPair::from_str_range_pair("10-20,30-40")
+            CleaningAssignmentPair::from_str_range_pair("10-20,30-40").unwrap()
         );
     }
 
     #[test]
     fn test_fully_overlaps_with() {
-        let val = CleaningAssignment::from_str_range("2-8");
-        let val2 = CleaningAssignment::from_str_range("3-7");
+        let val = CleaningAssignment::from_str_range("2-8").unwrap();
+        let val2 = CleaningAssignment::from_str_range("3-7").unwrap();
 
         assert_eq!(val.fully_overlaps_with(&val2), true);
 
-        let val = CleaningAssignment::from_str_range("5-8");
-        let val2 = CleaningAssignment::from_str_range("3-7");
+        let val = CleaningAssignment::from_str_range("5-8").unwrap();
+        let val2 = CleaningAssignment::from_str_range("3-7").unwrap();
 
         assert_eq!(val.fully_overlaps_with(&val2), false);
 
-        let val = CleaningAssignment::from_str_range("6-6");
-        let val2 = CleaningAssignment::from_str_range("4-6");
+        let val = CleaningAssignment::from_str_range("6-6").unwrap();
+        let val2 = CleaningAssignment::from_str_range("4-6").unwrap();
 
         assert_eq!(val.fully_overlaps_with(&val2), true);
     }
 
     #[test]
     fn test_fully_overlaps_pair() {
-        let val = CleaningAssignmentPair::from_str_range_pair("2-8,3-7");
+        let val = CleaningAssignmentPair::from_str_range_pair("2-8,3-7").unwrap();
         assert_eq!(val.fully_overlaps(), true);
 
-        let val = CleaningAssignmentPair::from_str_range_pair("5-7,7-9");
+        let val = CleaningAssignmentPair::from_str_range_pair("5-7,7-9").unwrap();
         assert_eq!(val.fully_overlaps(), false);
 
-        let val = CleaningAssignmentPair::from_str_range_pair("6-6,4-6");
+        let val = CleaningAssignmentPair::from_str_range_pair("6-6,4-6").unwrap();
         assert_eq!(val.fully_overlaps(), true);
     }
 
     #[test]
     fn test_partially_overlaps_with() {
-        let val = CleaningAssignment::from_str_range("2-8");
-        let val2 = CleaningAssignment::from_str_range("3-7");
+        let val = CleaningAssignment::from_str_range("2-8").unwrap();
+        let val2 = CleaningAssignment::from_str_range("3-7").unwrap();
 
         assert_eq!(val.partially_overlaps_with(&val2), true);
 
-        let val = CleaningAssignment::from_str_range("5-8");
-        let val2 = CleaningAssignment::from_str_range("3-7");
+        let val = CleaningAssignment::from_str_range("5-8").unwrap();
+        let val2 = CleaningAssignment::from_str_range("3-7").unwrap();
 
         assert_eq!(val.partially_overlaps_with(&val2), true);
 
-        let val = CleaningAssignment::from_str_range("6-6");
-        let val2 = CleaningAssignment::from_str_range("4-6");
+        let val = CleaningAssignment::from_str_range("6-6").unwrap();
+        let val2 = CleaningAssignment::from_str_range("4-6").unwrap();
 
         assert_eq!(val.partially_overlaps_with(&val2), true);
 
-        let val = CleaningAssignment::from_str_range("2-3");
-        let val2 = CleaningAssignment::from_str_range("4-5");
+        let val = CleaningAssignment::from_str_range("2-3").unwrap();
+        let val2 = CleaningAssignment::from_str_range("4-5").unwrap();
 
         assert_eq!(val.partially_overlaps_with(&val2), false);
     }
@@ -212,15 +280,42 @@ mod test {
 
     #[test]
     fn part_1() {
-        let pairs = parse_input_to_pairs(TEST_INPUT);
+        let pairs = parse_input_to_pairs(TEST_INPUT.as_bytes()).unwrap();
 
         assert_eq!(pairs.overlapping_assignments(), 2);
     }
 
     #[test]
     fn part_2() {
-        let pairs = parse_input_to_pairs(TEST_INPUT);
+        let pairs = parse_input_to_pairs(TEST_INPUT.as_bytes()).unwrap();
 
         assert_eq!(pairs.partially_overlapping_assignments(), 4);
     }
+
+    #[test]
+    fn test_invalid_pairs() {
+        let err = parse_input_to_pairs("2-4,6-8\n2-3,4-x\n".as_bytes()).unwrap_err();
+
+        assert_eq!(err, ParseError::new(4, 2, 7, "x", "End value invalid"));
+
+        let err = parse_input_to_pairs("2-4\n".as_bytes()).unwrap_err();
+
+        assert_eq!(err.message, "Right range invalid");
+
+        let err = parse_input_to_pairs("  4-2,1-1\n".as_bytes()).unwrap_err();
+
+        assert_eq!(
+            err,
+            ParseError::new(4, 1, 3, "4-2", "Range starts after it ends")
+        );
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        crate::property::check(generate::generate, |pairs| {
+            let parsed = Day4.parse(&generate::serialize(pairs)).unwrap();
+
+            assert_eq!(&parsed, pairs);
+        });
+    }
 }
@@ -0,0 +1,130 @@
+use std::io::{self, Write};
+
+use super::{MoveInstruction, Stacks};
+use crate::rng::Rng;
+
+/// Starting stacks and moves that never take more crates than a stack holds
+pub fn generate(rng: &mut Rng) -> (Stacks, Vec<MoveInstruction>) {
+    // More than nine stacks gives multi-digit stack numbers
+    let stacks = (0..rng.count(2..=12))
+        .map(|_| {
+            (0..rng.count(0..=15))
+                .map(|_| (b'A' + rng.count(0..=25) as u8) as char)
+                .collect::<Vec<_>>()
+        })
+        .collect::<Stacks>();
+
+    let mut heights = stacks.iter().map(Vec::len).collect::<Vec<_>>();
+    let mut moves = Vec::new();
+
+    for _ in 0..rng.count(0..=30) {
+        let from_idx = rng.count(0..=heights.len() - 1);
+
+        if heights[from_idx] == 0 {
+            continue;
+        }
+
+        let to_idx = (from_idx + rng.count(1..=heights.len() - 1)) % heights.len();
+        let quantity = rng.count(1..=heights[from_idx]);
+
+        heights[from_idx] -= quantity;
+        heights[to_idx] += quantity;
+
+        moves.push(MoveInstruction {
+            from_stack: from_idx + 1,
+            to_stack: to_idx + 1,
+            quantity: quantity as i32,
+        });
+    }
+
+    (stacks, moves)
+}
+
+pub fn serialize((stacks, moves): &(Stacks, Vec<MoveInstruction>)) -> String {
+    let height = stacks.iter().map(Vec::len).max().unwrap_or(0);
+
+    let mut lines = (0..height)
+        .rev()
+        .map(|level| {
+            stacks
+                .iter()
+                .map(|stack| match stack.get(level) {
+                    Some(label) => format!("[{}] ", label),
+                    None => "    ".to_string(),
+                })
+                .collect::<String>()
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>();
+
+    let numbers = (1..=stacks.len())
+        .map(|number| format!(" {:<3}", number))
+        .collect::<String>();
+
+    lines.push(numbers.trim_end().to_string());
+    lines.push(String::new());
+
+    lines.extend(moves.iter().map(|move_instruction| {
+        format!(
+            "move {} from {} to {}",
+            move_instruction.quantity, move_instruction.from_stack, move_instruction.to_stack
+        )
+    }));
+
+    lines.join("\n") + "\n"
+}
+
+/// Nine stacks holding `size` crates between them, then `size` moves. Only the
+/// stack heights are kept, so the crates' labels are picked as they're drawn.
+pub fn stress(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let mut heights = [0; 9];
+
+    for _ in 0..size {
+        heights[rng.count(0..=8)] += 1;
+    }
+
+    let top = heights.iter().copied().max().unwrap_or(0);
+
+    for level in (0..top).rev() {
+        let row = heights
+            .iter()
+            .map(|height| match *height > level {
+                true => format!("[{}] ", (b'A' + rng.count(0..=25) as u8) as char),
+                false => "    ".to_string(),
+            })
+            .collect::<String>();
+
+        writeln!(out, "{}", row.trim_end())?;
+    }
+
+    writeln!(out, " 1   2   3   4   5   6   7   8   9")?;
+    writeln!(out)?;
+
+    for _ in 0..size {
+        // Every crate is somewhere, so there is always a stack to move from
+        let from_idx = loop {
+            let idx = rng.count(0..=8);
+
+            if heights[idx] > 0 {
+                break idx;
+            }
+        };
+
+        let to_idx = (from_idx + rng.count(1..=8)) % 9;
+        let quantity = rng.count(1..=heights[from_idx]);
+
+        heights[from_idx] -= quantity;
+        heights[to_idx] += quantity;
+
+        writeln!(
+            out,
+            "move {} from {} to {}",
+            quantity,
+            from_idx + 1,
+            to_idx + 1
+        )?;
+    }
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-pub static INPUT_DAY_5_STACKS: &'static str = r#"
+pub static INPUT_DAY_5: &str = r#"
     [G] [R]                 [P]    
     [H] [W]     [T] [P]     [H]    
     [F] [T] [P] [B] [D]     [N]    
@@ -8,9 +8,7 @@ pub static INPUT_DAY_5_STACKS: &'static str = r#"
 [M] [D] [J] [F] [F] [N] [C] [S] [F]
 [Q] [R] [V] [J] [N] [R] [H] [G] [Z]
  1   2   3   4   5   6   7   8   9 
-"#;
 
-pub static INPUT_DAY_5_MOVES: &'static str = r#"
 move 5 from 8 to 2
 move 2 from 4 to 5
 move 3 from 3 to 9
@@ -514,4 +512,4 @@ move 1 from 1 to 2
 move 2 from 5 to 4
 move 27 from 3 to 8
 move 5 from 3 to 9
-"#;
\ No newline at end of file
+"#;
@@ -1,65 +1,201 @@
+//! Day 5: Supply Stacks
+
+use std::io::BufRead;
+
+use crate::answer::Answer;
+use crate::error::{FragmentError, ParseError};
+use crate::normalize::{Line, LineReader};
+use crate::solution::Solution;
+
+pub mod generate;
 pub mod input;
 
-type Stacks = Vec<Vec<char>>;
+/// Crate stacks from left to right, each listed bottom to top
+pub type Stacks = Vec<Vec<char>>;
 
-pub fn parse_stacks(input: &'static str) -> Stacks {
+/// Parses the crate drawing, which ends with its row of stack numbers.
+///
+/// Which stack a crate is on depends on its column, so the drawing is read
+/// without trimming. Only the indentation in front of the stack numbers is
+/// removed from every row, which lets the drawing be indented as a whole.
+pub fn parse_stacks(input: impl BufRead) -> Result<Stacks, ParseError> {
+    read_stacks(&mut LineReader::new(Day5::DAY, input))
+}
+
+/// Reads the drawing up to and including its row of stack numbers, leaving
+/// the moves to be read next
+fn read_stacks<R: BufRead>(lines: &mut LineReader<R>) -> Result<Stacks, ParseError> {
     let mut stacks: Stacks = Vec::new();
 
-    for line in input.lines().rev() {
-        line.as_bytes()
-            .chunks(4)
-            .enumerate()
-            .for_each(|(idx, group)| {
-                if stacks.get(idx).is_none() {
-                    stacks.push(Vec::new());
-                }
+    // Rows can only be lined up once the stack numbers are read, so the
+    // drawing is kept until then. It's only as tall as the tallest stack.
+    let mut rows = Vec::new();
 
-                if group[0] == b'[' {
-                    stacks[idx].push(group[1] as char);
-                } else {
-                    // Do nothing
+    while let Some(line) = lines.next_layout_line()? {
+        rows.push((line.index, line.raw.to_string()));
+
+        if line
+            .text
+            .trim_start()
+            .starts_with(|c: char| c.is_ascii_digit())
+        {
+            break;
+        }
+    }
+
+    let rows = rows.iter().map(|(index, raw)| Line {
+        index: *index,
+        raw,
+        text: raw.trim_end(),
+    });
+
+    // Stack numbers sit one column in from the crates above them
+    let indent = rows.clone().next_back().map_or(0, |numbers| {
+        let text = numbers.text.trim_start_matches(' ');
+        (numbers.text.len() - text.len()).saturating_sub(1)
+    });
+
+    for line in rows.rev() {
+        let text = line.dedent(indent).map(|line| line.text).ok_or_else(|| {
+            FragmentError::new(
+                line.text.trim_start(),
+                "Drawing is indented less than its stack numbers",
+            )
+            .at(Day5::DAY, line.index, line.raw)
+        })?;
+
+        for (idx, group) in text.as_bytes().chunks(4).enumerate() {
+            if stacks.get(idx).is_none() {
+                stacks.push(Vec::new());
+            }
+
+            if group[0] == b'[' {
+                match group {
+                    [b'[', label, b']', ..] if label.is_ascii_alphanumeric() => {
+                        stacks[idx].push(*label as char);
+                    }
+                    _ => {
+                        return Err(ParseError::new(
+                            Day5::DAY,
+                            line.index + 1,
+                            indent + idx * 4 + 1,
+                            String::from_utf8_lossy(group).trim_end(),
+                            "Crates should look like [A]",
+                        ))
+                    }
                 }
-            });
+            } else {
+                // Do nothing
+            }
+        }
     }
 
-    stacks
+    Ok(stacks)
 }
 
-#[derive(Debug)]
-struct MoveInstruction {
+/// One `move N from A to B` step, with stacks numbered from 1
+#[derive(Debug, PartialEq)]
+pub struct MoveInstruction {
     from_stack: usize,
     to_stack: usize,
     quantity: i32,
 }
 
-fn parse_moves(input: &'static str) -> Vec<MoveInstruction> {
+impl MoveInstruction {
+    /// How many crates the crane moves
+    pub fn quantity(&self) -> i32 {
+        self.quantity
+    }
+
+    /// The stack the crates are taken from
+    pub fn from_stack(&self) -> usize {
+        self.from_stack
+    }
+
+    /// The stack the crates are put on
+    pub fn to_stack(&self) -> usize {
+        self.to_stack
+    }
+}
+
+fn parse_move<'a>(trimmed_line: &'a str) -> Result<MoveInstruction, FragmentError<'a>> {
+    // Filter out the text in the instructions and keep only the numerical values
+    let mut tokens = trimmed_line
+        .split(" ")
+        .filter(|val| val.parse::<i32>().is_ok());
+
+    let mut next_number = || {
+        tokens.next().ok_or(FragmentError::new(
+            trimmed_line,
+            "Expected a quantity and two stack numbers",
+        ))
+    };
+
+    let quantity_text = next_number()?;
+    let from_text = next_number()?;
+    let to_text = next_number()?;
+
+    let quantity = quantity_text.parse::<i32>().unwrap_or(-1);
+    if quantity < 0 {
+        return Err(FragmentError::new(
+            quantity_text,
+            "Quantity cannot be negative",
+        ));
+    }
+
+    let stack_number = |text: &'a str| match text.parse::<usize>() {
+        Ok(number) if number > 0 => Ok(number),
+        _ => Err(FragmentError::new(text, "Stack numbers start at 1")),
+    };
+
+    // Assign the three numerical values into a move instruction
+    Ok(MoveInstruction {
+        quantity,
+        from_stack: stack_number(from_text)?,
+        to_stack: stack_number(to_text)?,
+    })
+}
+
+/// Parses the move list, skipping blank lines
+pub fn parse_moves(input: impl BufRead) -> Result<Vec<MoveInstruction>, ParseError> {
+    read_moves(&mut LineReader::new(Day5::DAY, input), |_| Ok(()))
+}
+
+/// Reads the rest of the input as moves, rejecting any that `check` does
+fn read_moves<R: BufRead>(
+    lines: &mut LineReader<R>,
+    mut check: impl FnMut(&MoveInstruction) -> Result<(), &'static str>,
+) -> Result<Vec<MoveInstruction>, ParseError> {
     let mut move_instructions = Vec::new();
-    for line in input.lines() {
-        let trimmed_line = line.trim();
-        if trimmed_line == "" {
+
+    while let Some(line) = lines.next_line()? {
+        if line.is_blank() {
             continue;
         }
 
-        // Filter out the text in the instructions and keep only the numerical values
-        let mut tokens = trimmed_line
-            .split(" ")
-            .filter(|val| i32::from_str_radix(val, 10).is_ok());
-
-        // Assign the three numerical values into a move instruction
-        let move_instruction = MoveInstruction {
-            quantity: i32::from_str_radix(tokens.next().unwrap(), 10).unwrap(),
-            from_stack: usize::from_str_radix(tokens.next().unwrap(), 10).unwrap(),
-            to_stack: usize::from_str_radix(tokens.next().unwrap(), 10).unwrap(),
-        };
+        let move_instruction = parse_move(line.text)
+            .and_then(|move_instruction| {
+                check(&move_instruction)
+                    .map(|_| move_instruction)
+                    .map_err(|message| FragmentError::new(line.text, message))
+            })
+            .map_err(|err| err.at(Day5::DAY, line.index, line.raw))?;
         move_instructions.push(move_instruction);
     }
-    move_instructions
+    Ok(move_instructions)
 }
 
+/// A move named a stack that doesn't exist or took more crates than it held
 #[derive(Debug)]
-struct MoveError;
+pub struct MoveError;
+
+/// Moves crates one at a time with the CrateMover 9000
+pub fn execute_move(
+    stacks: &mut Stacks,
+    move_instruction: &MoveInstruction,
+) -> Result<(), MoveError> {
+    check_stacks(stacks, move_instruction)?;
 
-fn execute_move(stacks: &mut Stacks, move_instruction: &MoveInstruction) -> Result<(), MoveError> {
     // dbg!("{:?}", move_instruction);
     for _ in 0..move_instruction.quantity {
         let val = stacks[move_instruction.from_stack - 1]
@@ -73,14 +209,21 @@ fn execute_move(stacks: &mut Stacks, move_instruction: &MoveInstruction) -> Resu
     Ok(())
 }
 
-fn execute_move_crane_9001(
+/// Moves all the crates at once with the CrateMover 9001, keeping their order
+pub fn execute_move_crane_9001(
     stacks: &mut Stacks,
     move_instruction: &MoveInstruction,
 ) -> Result<(), MoveError> {
+    check_stacks(stacks, move_instruction)?;
+
     // dbg!("{:?}", move_instruction);
 
     let from_idx = move_instruction.from_stack - 1;
     let stack_from_len = stacks[from_idx].len();
+    if move_instruction.quantity as usize > stack_from_len {
+        return Err(MoveError);
+    }
+
     let elems = stacks[from_idx]
         .drain((stack_from_len - move_instruction.quantity as usize)..stack_from_len)
         .collect::<Vec<_>>();
@@ -92,23 +235,36 @@ fn execute_move_crane_9001(
     Ok(())
 }
 
-fn execute_moves(stacks: &mut Stacks, moves: &Vec<MoveInstruction>) {
+fn check_stacks(stacks: &Stacks, move_instruction: &MoveInstruction) -> Result<(), MoveError> {
+    let exists = |number: usize| (1..=stacks.len()).contains(&number);
+
+    if exists(move_instruction.from_stack) && exists(move_instruction.to_stack) {
+        Ok(())
+    } else {
+        Err(MoveError)
+    }
+}
+
+/// Runs every move with the CrateMover 9000, skipping any that fail
+pub fn execute_moves(stacks: &mut Stacks, moves: &[MoveInstruction]) {
     for move_instruction in moves {
-        execute_move(stacks, move_instruction);
+        let _ = execute_move(stacks, move_instruction);
     }
 }
 
-fn execute_moves_crane_9001(stacks: &mut Stacks, moves: &Vec<MoveInstruction>) {
+/// Runs every move with the CrateMover 9001, skipping any that fail
+pub fn execute_moves_crane_9001(stacks: &mut Stacks, moves: &[MoveInstruction]) {
     for move_instruction in moves {
-        execute_move_crane_9001(stacks, move_instruction);
+        let _ = execute_move_crane_9001(stacks, move_instruction);
     }
 }
 
-fn top_crates(stacks: &Stacks) -> String {
+/// The crate on top of each non-empty stack, read left to right
+pub fn top_crates(stacks: &Stacks) -> String {
     let mut top_crates = String::new();
 
     for stack in stacks {
-        let top_char = stack.get(stack.len() - 1);
+        let top_char = stack.last();
 
         if let Some(top_char) = top_char {
             top_crates.push(*top_char);
@@ -118,20 +274,66 @@ fn top_crates(stacks: &Stacks) -> String {
     top_crates
 }
 
-pub fn day_5_part_1(input_stacks: &'static str, input_moves: &'static str) -> String {
-    let mut stacks = parse_stacks(input_stacks);
-    let moves = parse_moves(input_moves);
-    execute_moves(&mut stacks, &moves);
+/// Makes sure a move names a stack that exists and takes no more crates than
+/// the stack holds at that point, so the cranes never run out. `heights` is
+/// how many crates each stack holds before the move and after it.
+fn check_move(
+    heights: &mut [usize],
+    move_instruction: &MoveInstruction,
+) -> Result<(), &'static str> {
+    let from_idx = move_instruction.from_stack - 1;
+    let to_idx = move_instruction.to_stack - 1;
+
+    if from_idx >= heights.len() || to_idx >= heights.len() {
+        return Err("No stack with that number");
+    }
 
-    top_crates(&stacks)
+    let quantity = move_instruction.quantity as usize;
+
+    if quantity > heights[from_idx] {
+        return Err("Not enough crates on the stack");
+    }
+
+    heights[from_idx] -= quantity;
+    heights[to_idx] += quantity;
+
+    Ok(())
 }
 
-pub fn day_5_part_2(input_stacks: &'static str, input_moves: &'static str) -> String {
-    let mut stacks = parse_stacks(input_stacks);
-    let moves = parse_moves(input_moves);
-    execute_moves_crane_9001(&mut stacks, &moves);
+pub struct Day5;
 
-    top_crates(&stacks)
+impl Solution for Day5 {
+    type Parsed = (Stacks, Vec<MoveInstruction>);
+
+    const DAY: u8 = 5;
+    const INPUT: &'static str = input::INPUT_DAY_5;
+
+    fn read(&self, input: &mut dyn BufRead) -> Result<Self::Parsed, ParseError> {
+        let mut lines = LineReader::new(Day5::DAY, input);
+
+        let stacks = read_stacks(&mut lines)?;
+
+        let mut heights = stacks.iter().map(Vec::len).collect::<Vec<_>>();
+        let moves = read_moves(&mut lines, |move_instruction| {
+            check_move(&mut heights, move_instruction)
+        })?;
+
+        Ok((stacks, moves))
+    }
+
+    fn part_1(&self, (stacks, moves): &Self::Parsed) -> Answer {
+        let mut stacks = stacks.clone();
+        execute_moves(&mut stacks, moves);
+
+        top_crates(&stacks).into()
+    }
+
+    fn part_2(&self, (stacks, moves): &Self::Parsed) -> Answer {
+        let mut stacks = stacks.clone();
+        execute_moves_crane_9001(&mut stacks, moves);
+
+        top_crates(&stacks).into()
+    }
 }
 
 #[cfg(test)]
@@ -154,7 +356,7 @@ mod test {
 
     #[test]
     fn test_parse_moves() {
-        let moves = parse_moves(TEST_INPUT_MOVES);
+        let moves = parse_moves(TEST_INPUT_MOVES.as_bytes()).unwrap();
 
         // Index values should be 1 less than text values
         assert_eq!(moves[0].from_stack, 2);
@@ -169,7 +371,7 @@ mod test {
 
     #[test]
     fn test_parse_stacks() {
-        let stacks = parse_stacks(TEST_INPUT);
+        let stacks = parse_stacks(TEST_INPUT.as_bytes()).unwrap();
 
         assert_eq!(stacks[0][0], 'Z');
         assert_eq!(stacks[1][2], 'D');
@@ -177,7 +379,8 @@ mod test {
 
         assert_eq!(stacks.len(), 3);
 
-        let stacks = parse_stacks(input::INPUT_DAY_5_STACKS);
+        // Only the drawing is read, leaving the moves after it
+        let stacks = parse_stacks(input::INPUT_DAY_5.as_bytes()).unwrap();
 
         assert_eq!(stacks[1][1], 'D');
         assert_eq!(stacks[7][3], 'V');
@@ -185,10 +388,39 @@ mod test {
         assert_eq!(stacks.len(), 9);
     }
 
+    #[test]
+    fn test_parse_indented_stacks() {
+        // The first stack is empty, so only the stack numbers show how far
+        // the drawing is indented
+        let input = r#"
+                [C]
+            [B] [D]
+         1   2   3
+        "#;
+
+        assert_eq!(
+            parse_stacks(input.as_bytes()).unwrap(),
+            vec![vec![], vec!['B'], vec!['D', 'C']]
+        );
+
+        let err = parse_stacks("  [A]\n         1\n".as_bytes()).unwrap_err();
+
+        assert_eq!(
+            err,
+            ParseError::new(
+                5,
+                1,
+                3,
+                "[A]",
+                "Drawing is indented less than its stack numbers"
+            )
+        );
+    }
+
     #[test]
     fn test_execute_moves() {
-        let mut stacks = parse_stacks(TEST_INPUT);
-        let moves = parse_moves(TEST_INPUT_MOVES);
+        let mut stacks = parse_stacks(TEST_INPUT.as_bytes()).unwrap();
+        let moves = parse_moves(TEST_INPUT_MOVES.as_bytes()).unwrap();
 
         execute_move(&mut stacks, &moves[0])
             .map_err(|err| println!("{:?}", err))
@@ -209,10 +441,40 @@ mod test {
         assert_eq!(stacks.get(1), Some(&vec![]));
     }
 
+    #[test]
+    fn test_execute_invalid_move() {
+        let stacks = parse_stacks(TEST_INPUT.as_bytes()).unwrap();
+
+        for line in [
+            "move 4 from 1 to 2",
+            "move 1 from 4 to 2",
+            "move 1 from 1 to 9",
+        ] {
+            let moves = parse_moves(line.as_bytes()).unwrap();
+
+            let mut crane_9000 = stacks.clone();
+            assert!(execute_move(&mut crane_9000, &moves[0]).is_err());
+
+            let mut crane_9001 = stacks.clone();
+            assert!(execute_move_crane_9001(&mut crane_9001, &moves[0]).is_err());
+            assert_eq!(crane_9001, stacks);
+        }
+    }
+
+    #[test]
+    fn test_moves_follow_the_drawing() {
+        let input = format!("{}{}", TEST_INPUT, TEST_INPUT_MOVES);
+
+        let mut lines = LineReader::new(Day5::DAY, input.as_bytes());
+
+        assert_eq!(read_stacks(&mut lines).unwrap().len(), 3);
+        assert_eq!(read_moves(&mut lines, |_| Ok(())).unwrap().len(), 4);
+    }
+
     #[test]
     fn part_1() {
-        let mut stacks = parse_stacks(TEST_INPUT);
-        let moves = parse_moves(TEST_INPUT_MOVES);
+        let mut stacks = parse_stacks(TEST_INPUT.as_bytes()).unwrap();
+        let moves = parse_moves(TEST_INPUT_MOVES.as_bytes()).unwrap();
         execute_moves(&mut stacks, &moves);
 
         assert_eq!(stacks[1][0], 'M');
@@ -224,8 +486,8 @@ mod test {
 
     #[test]
     fn part_2() {
-        let mut stacks = parse_stacks(TEST_INPUT);
-        let moves = parse_moves(TEST_INPUT_MOVES);
+        let mut stacks = parse_stacks(TEST_INPUT.as_bytes()).unwrap();
+        let moves = parse_moves(TEST_INPUT_MOVES.as_bytes()).unwrap();
         execute_moves_crane_9001(&mut stacks, &moves);
 
         // assert_eq!(stacks[1][0], 'M');
@@ -234,4 +496,58 @@ mod test {
 
         assert_eq!(top_crates, "MCD".to_string());
     }
+
+    #[test]
+    fn test_invalid_input() {
+        let input = "[A] [B]\n[C] [D\n 1   2\n\nmove 1 from 2 to 1\n";
+
+        let err = Day5.parse(input).unwrap_err();
+
+        assert_eq!(
+            err,
+            ParseError::new(5, 2, 5, "[D", "Crates should look like [A]")
+        );
+
+        let input = "[A] [B]\n 1   2\n\nmove 1 from 2 to 1\nmove 1 from 0 to 1\n";
+
+        let err = Day5.parse(input).unwrap_err();
+
+        assert_eq!(
+            err,
+            ParseError::new(5, 5, 13, "0", "Stack numbers start at 1")
+        );
+
+        let input = "[A] [B]\n 1   2\n\nmove 1 from 2 to 3\n";
+
+        let err = Day5.parse(input).unwrap_err();
+
+        assert_eq!(
+            err,
+            ParseError::new(5, 4, 1, "move 1 from 2 to 3", "No stack with that number")
+        );
+
+        let input = "[A] [B]\n 1   2\n\nmove 1 from 2 to 1\nmove 2 from 2 to 1\n";
+
+        let err = Day5.parse(input).unwrap_err();
+
+        assert_eq!(err.line, 5);
+        assert_eq!(err.message, "Not enough crates on the stack");
+
+        let err = parse_moves("move 1 from 2\n".as_bytes()).unwrap_err();
+
+        assert_eq!(err.message, "Expected a quantity and two stack numbers");
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        crate::property::check(generate::generate, |puzzle| {
+            let parsed = Day5.parse(&generate::serialize(puzzle)).unwrap();
+
+            assert_eq!(&parsed, puzzle);
+
+            // Every move is possible, so both cranes finish without running out of crates
+            Day5.part_1(&parsed);
+            Day5.part_2(&parsed);
+        });
+    }
 }
@@ -0,0 +1,46 @@
+use std::io::{self, Write};
+
+use crate::rng::Rng;
+
+/// A datastream made from a noisy prefix, a run of 14 different letters so
+/// both markers exist, and more noise. Returns the stream and the prefix length.
+pub fn generate(rng: &mut Rng) -> (String, usize) {
+    // Three letters can never make a marker, so nothing is found before the run
+    let prefix_len = rng.count(0..=50);
+    let prefix = (0..prefix_len)
+        .map(|_| *rng.choose(&['a', 'b', 'c']))
+        .collect::<String>();
+
+    let mut letters = ('a'..='z').collect::<Vec<_>>();
+    rng.shuffle(&mut letters);
+
+    let tail = (0..rng.count(0..=50))
+        .map(|_| (b'a' + rng.count(0..=25) as u8) as char)
+        .collect::<String>();
+
+    let stream = format!(
+        "{}{}{}",
+        prefix,
+        letters[..14].iter().collect::<String>(),
+        tail
+    );
+
+    (stream, prefix_len)
+}
+
+pub fn serialize((stream, _): &(String, usize)) -> String {
+    format!("{}\n", stream)
+}
+
+/// A datastream `size` letters long, or 14 if that's shorter, where both
+/// markers are only found at the very end
+pub fn stress(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let mut letters = ('a'..='z').collect::<Vec<_>>();
+    rng.shuffle(&mut letters);
+
+    for _ in 0..size.saturating_sub(14) {
+        write!(out, "{}", rng.choose(&['a', 'b', 'c']))?;
+    }
+
+    writeln!(out, "{}", letters[..14].iter().collect::<String>())
+}
@@ -1,85 +1,305 @@
+//! Day 6: Tuning Trouble
+
+use std::{collections::VecDeque, io::BufRead};
+
+use crate::answer::Answer;
+use crate::error::ParseError;
+use crate::solution::Solution;
+
+pub mod generate;
 pub mod input;
 
-pub fn day_6_part_1(input: &'static str) -> String {
-    find_packet_marker(input, 4).to_string()
+pub struct Day6;
+
+impl Solution for Day6 {
+    // Only where the markers are is kept, since the datastream can be far too
+    // long to hold
+    type Parsed = Markers;
+
+    const DAY: u8 = 6;
+    const INPUT: &'static str = input::INPUT_DAY_6;
+
+    fn read(&self, input: &mut dyn BufRead) -> Result<Self::Parsed, ParseError> {
+        read_markers(input)
+    }
+
+    fn part_1(&self, markers: &Self::Parsed) -> Answer {
+        markers.packet.into()
+    }
+
+    fn part_2(&self, markers: &Self::Parsed) -> Answer {
+        markers.message.into()
+    }
+}
+
+/// How many characters have been read once each marker is complete
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Markers {
+    pub packet: usize,
+    pub message: usize,
 }
 
-pub fn day_6_part_2(input: &'static str) -> String {
-    find_packet_marker(input, 14).to_string()
+/// Looks for the first `len` characters in a row that are all different, fed
+/// one character at a time
+pub struct MarkerSearch {
+    len: usize,
+    window: VecDeque<u8>,
+    /// How many times each character appears in the window
+    counts: [usize; 256],
+    /// How many characters appear in the window more than once
+    repeated: usize,
+    read: usize,
+    found: Option<usize>,
 }
 
-fn find_packet_marker(input: &'static str, buffer_len: usize) -> usize {
-    let mut buffer = String::new();
+impl MarkerSearch {
+    pub fn new(len: usize) -> Self {
+        Self {
+            len,
+            window: VecDeque::with_capacity(len + 1),
+            counts: [0; 256],
+            repeated: 0,
+            read: 0,
+            found: None,
+        }
+    }
 
-    let mut index: Option<usize> = None;
-    for (idx, character) in input.chars().enumerate() {
-        buffer.push(character);
-        if buffer.len() > buffer_len {
-            buffer.remove(0);
+    /// Reads one more character, returning how many had been read when the
+    /// marker was complete once it has been found
+    pub fn push(&mut self, character: u8) -> Option<usize> {
+        if self.found.is_some() {
+            return self.found;
         }
 
-        // Don't start checking until the buffer is at least four characters long
-        if buffer.len() < buffer_len {
-            continue;
+        self.read += 1;
+        self.window.push_back(character);
+
+        self.counts[character as usize] += 1;
+        if self.counts[character as usize] == 2 {
+            self.repeated += 1;
         }
 
-        let mut done = true;
-        for buffer_char in buffer.chars() {
-            if buffer.matches(buffer_char).count() > 1 {
-                done = false;
+        if self.window.len() > self.len {
+            let oldest = self.window.pop_front().expect("The window isn't empty") as usize;
+
+            self.counts[oldest] -= 1;
+            if self.counts[oldest] == 1 {
+                self.repeated -= 1;
             }
         }
-        if done == true {
-            index = Some(idx + 1);
+
+        if self.window.len() == self.len && self.repeated == 0 {
+            self.found = Some(self.read);
+        }
+
+        self.found
+    }
+}
+
+/// How many characters have been read once the last `buffer_len` of them are
+/// all different, or `None` if that never happens
+pub fn find_packet_marker(input: &str, buffer_len: usize) -> Option<usize> {
+    let mut search = MarkerSearch::new(buffer_len);
+
+    input.bytes().find_map(|character| search.push(character))
+}
+
+/// Reads the datastream a buffer at a time, stopping as soon as both markers
+/// are found, so a stream of any length takes the same memory. Whitespace
+/// around the stream and a byte order mark are ignored.
+pub fn read_markers(mut input: impl BufRead) -> Result<Markers, ParseError> {
+    let mut packet = MarkerSearch::new(4);
+    let mut message = MarkerSearch::new(14);
+
+    // Whitespace only belongs to the datastream if more of it follows
+    let mut whitespace = Vec::new();
+    let mut started = false;
+
+    // For reporting errors
+    let mut line = 1;
+    let mut column = 0;
+
+    let read_error = |line, err: std::io::Error| {
+        ParseError::new(
+            Day6::DAY,
+            line,
+            1,
+            "",
+            format!("Failed to read input: {}", err),
+        )
+    };
+
+    let buffer = input.fill_buf().map_err(|err| read_error(line, err))?;
+    if buffer.starts_with("\u{feff}".as_bytes()) {
+        input.consume("\u{feff}".len());
+    }
+
+    loop {
+        let buffer = input.fill_buf().map_err(|err| read_error(line, err))?;
+
+        if buffer.is_empty() {
             break;
         }
+
+        for &character in buffer {
+            if character == b'\n' {
+                line += 1;
+                column = 0;
+            } else {
+                column += 1;
+            }
+
+            if character.is_ascii_whitespace() {
+                if started {
+                    whitespace.push(character);
+                }
+
+                continue;
+            }
+
+            if !character.is_ascii() {
+                return Err(ParseError::new(
+                    Day6::DAY,
+                    line,
+                    column,
+                    "",
+                    "Datastream should only hold ASCII characters",
+                ));
+            }
+
+            started = true;
+
+            for character in whitespace.drain(..).chain([character]) {
+                if let (Some(packet), Some(message)) =
+                    (packet.push(character), message.push(character))
+                {
+                    return Ok(Markers { packet, message });
+                }
+            }
+        }
+
+        let consumed = buffer.len();
+        input.consume(consumed);
     }
 
-    index.expect("Did not find a match")
+    // Counted the way `lines()` would count them
+    let line_count = if column > 0 { line } else { line - 1 };
+
+    let missing = if packet.found.is_none() {
+        "start-of-packet"
+    } else {
+        "start-of-message"
+    };
+
+    Err(ParseError::end_of_input(
+        Day6::DAY,
+        line_count,
+        format!("Datastream has no {} marker", missing),
+    ))
 }
 
 #[cfg(test)]
 mod test {
+    use std::io::{self, Read};
+
     use super::*;
 
     // static TEST_INPUT: &str = r#"zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"#;
 
     #[test]
     fn test_find_packet_marker_4() {
-        let packet: usize = find_packet_marker(r#"bvwbjplbgvbhsrlpgdmjqwftvncz"#, 4);
+        let packet: usize = find_packet_marker(r#"bvwbjplbgvbhsrlpgdmjqwftvncz"#, 4).unwrap();
         assert_eq!(packet, 5);
 
-        let packet: usize = find_packet_marker(r#"nppdvjthqldpwncqszvftbrmjlhg"#, 4);
+        let packet: usize = find_packet_marker(r#"nppdvjthqldpwncqszvftbrmjlhg"#, 4).unwrap();
         assert_eq!(packet, 6);
 
-        let packet: usize = find_packet_marker(r#"nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"#, 4);
+        let packet: usize = find_packet_marker(r#"nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"#, 4).unwrap();
         assert_eq!(packet, 10);
 
-        let packet: usize = find_packet_marker(r#"zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"#, 4);
+        let packet: usize = find_packet_marker(r#"zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"#, 4).unwrap();
         assert_eq!(packet, 11);
     }
 
     #[test]
     fn test_find_packet_marker_14() {
-        let packet: usize = find_packet_marker(r#"mjqjpqmgbljsphdztnvjfqwrcgsmlb"#, 14);
+        let packet: usize = find_packet_marker(r#"mjqjpqmgbljsphdztnvjfqwrcgsmlb"#, 14).unwrap();
         assert_eq!(packet, 19);
 
-        let packet: usize = find_packet_marker(r#"bvwbjplbgvbhsrlpgdmjqwftvncz"#, 14);
+        let packet: usize = find_packet_marker(r#"bvwbjplbgvbhsrlpgdmjqwftvncz"#, 14).unwrap();
         assert_eq!(packet, 23);
 
-        let packet: usize = find_packet_marker(r#"nppdvjthqldpwncqszvftbrmjlhg"#, 14);
+        let packet: usize = find_packet_marker(r#"nppdvjthqldpwncqszvftbrmjlhg"#, 14).unwrap();
         assert_eq!(packet, 23);
 
-        let packet: usize = find_packet_marker(r#"nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"#, 14);
+        let packet: usize = find_packet_marker(r#"nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"#, 14).unwrap();
         assert_eq!(packet, 29);
 
-        let packet: usize = find_packet_marker(r#"zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"#, 14);
+        let packet: usize = find_packet_marker(r#"zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"#, 14).unwrap();
         assert_eq!(packet, 26);
     }
 
     #[test]
-    fn part_1() {}
+    fn test_missing_marker() {
+        let err = Day6.parse("abcabcabc\n").unwrap_err();
+
+        assert_eq!(err.message, "Datastream has no start-of-packet marker");
+
+        let err = Day6.parse("abcdabcdabcd\n").unwrap_err();
+
+        assert_eq!(err.message, "Datastream has no start-of-message marker");
+    }
+
+    #[test]
+    fn test_read_markers() {
+        let markers = Markers {
+            packet: 7,
+            message: 19,
+        };
+
+        assert_eq!(
+            read_markers("mjqjpqmgbljsphdztnvjfqwrcgsmlb\n".as_bytes()).unwrap(),
+            markers
+        );
+        assert_eq!(
+            read_markers("\u{feff}\r\n  mjqjpqmgbljsphdztnvjfqwrcgsmlb\r\n".as_bytes()).unwrap(),
+            markers
+        );
+
+        // Reading stops at the markers, so the rest of the stream is never read
+        let endless = "mjqjpqmgbljsphdztnvjfqwrcgsmlb"
+            .as_bytes()
+            .chain(io::repeat(b'a'));
+
+        assert_eq!(read_markers(io::BufReader::new(endless)).unwrap(), markers);
+    }
+
+    #[test]
+    fn test_trailing_whitespace_is_not_a_marker() {
+        // The last four characters would be different if the line ending counted
+        let err = read_markers("aabcabc\r\n".as_bytes()).unwrap_err();
+
+        assert_eq!(
+            err,
+            ParseError::new(6, 2, 1, "", "Datastream has no start-of-packet marker")
+        );
+
+        let err = read_markers("abcabc\u{e9}abc".as_bytes()).unwrap_err();
+
+        assert_eq!((err.line, err.column), (1, 7));
+    }
 
     #[test]
-    fn part_2() {}
+    fn test_parse_round_trip() {
+        crate::property::check(generate::generate, |datastream| {
+            let (stream, prefix_len) = datastream;
+            let Markers { packet, message } = Day6.parse(&generate::serialize(datastream)).unwrap();
+
+            assert_eq!(Some(packet), find_packet_marker(stream, 4));
+            assert_eq!(Some(message), find_packet_marker(stream, 14));
+
+            assert!(packet > *prefix_len && packet <= prefix_len + 14);
+            assert!(message >= packet && message <= prefix_len + 14);
+        });
+    }
 }
@@ -0,0 +1,88 @@
+use std::io::{self, Write};
+
+use super::DirectoryPath;
+use crate::rng::Rng;
+
+#[derive(Debug, Default)]
+pub struct Tree {
+    pub files: Vec<(String, i32)>,
+    pub directories: Vec<(String, Tree)>,
+}
+
+impl Tree {
+    /// Every directory with its path, written the way the parser keys them
+    pub fn walk<'a>(&'a self, path: DirectoryPath, found: &mut Vec<(DirectoryPath, &'a Tree)>) {
+        found.push((path.clone(), self));
+
+        for (name, directory) in &self.directories {
+            let mut child_path = path.clone();
+            child_path.push(name.clone());
+            child_path.push("/".to_string());
+
+            directory.walk(child_path, found);
+        }
+    }
+}
+
+pub fn generate(rng: &mut Rng) -> Tree {
+    generate_tree(rng, 0)
+}
+
+fn generate_tree(rng: &mut Rng, depth: usize) -> Tree {
+    let files = (0..rng.count(0..=4))
+        .map(|idx| (format!("f{}.txt", idx), rng.range(1..=300_000) as i32))
+        .collect();
+
+    let max_directories = if depth < 4 { 3 } else { 0 };
+    let directories = (0..rng.count(0..=max_directories))
+        .map(|idx| (format!("d{}", idx), generate_tree(rng, depth + 1)))
+        .collect();
+
+    Tree { files, directories }
+}
+
+/// The terminal session that lists every directory, going back up with `cd ..`
+pub fn serialize(tree: &Tree) -> String {
+    let mut lines = vec!["$ cd /".to_string()];
+    serialize_directory(tree, &mut lines);
+
+    lines.join("\n") + "\n"
+}
+
+fn serialize_directory(tree: &Tree, lines: &mut Vec<String>) {
+    lines.push("$ ls".to_string());
+
+    for (name, _) in &tree.directories {
+        lines.push(format!("dir {}", name));
+    }
+
+    for (name, size) in &tree.files {
+        lines.push(format!("{} {}", size, name));
+    }
+
+    for (name, directory) in &tree.directories {
+        lines.push(format!("$ cd {}", name));
+        serialize_directory(directory, lines);
+        lines.push("$ cd ..".to_string());
+    }
+}
+
+/// Directories nested `size` deep, each holding one file and the next
+/// directory. The whole tree is kept small enough to fit on the disk.
+pub fn stress(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let largest_file = (70_000_000 / size.max(1)).clamp(1, 300_000) as i64;
+
+    writeln!(out, "$ cd /")?;
+
+    for depth in 0..size {
+        writeln!(out, "$ ls")?;
+        writeln!(out, "{} f.txt", rng.range(1..=largest_file))?;
+
+        if depth + 1 < size {
+            writeln!(out, "dir d")?;
+            writeln!(out, "$ cd d")?;
+        }
+    }
+
+    Ok(())
+}
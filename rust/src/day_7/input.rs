@@ -1,4 +1,4 @@
-pub static INPUT_DAY_7: &'static str = r#"
+pub static INPUT_DAY_7: &str = r#"
 $ cd /
 $ ls
 dir ddpgzpc
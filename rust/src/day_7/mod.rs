@@ -1,72 +1,84 @@
-use std::collections::HashMap;
+//! Day 7: No Space Left On Device
 
+use std::{collections::HashMap, io::BufRead};
+
+use crate::answer::Answer;
+use crate::error::{FragmentError, ParseError};
+use crate::normalize::LineReader;
+use crate::solution::Solution;
+
+pub mod generate;
 pub mod input;
 
-pub fn day_7_part_1(input: &'static str) -> String {
-    let file_system = parse_input_to_file_system(input);
+const FILE_SYSTEM_SIZE: i32 = 70000000;
+const SPACE_REQUIRED: i32 = 30000000;
 
-    let mut directory_sizes = file_system
-        .values()
-        .map(|dir| dir.total_directory_size(&file_system))
-        .collect::<Vec<_>>();
+pub struct Day7;
 
-    directory_sizes.retain(|size| *size <= 100000);
+impl Solution for Day7 {
+    type Parsed = FileSystem;
 
-    let answer = directory_sizes.iter().sum::<i32>();
+    const DAY: u8 = 7;
+    const INPUT: &'static str = input::INPUT_DAY_7;
 
-    answer.to_string()
-}
+    fn read(&self, input: &mut dyn BufRead) -> Result<Self::Parsed, ParseError> {
+        parse_input_to_file_system(input)
+    }
 
-const FILE_SYSTEM_SIZE: i32 = 70000000;
-const SPACE_REQUIRED: i32 = 30000000;
+    fn part_1(&self, file_system: &Self::Parsed) -> Answer {
+        let mut directory_sizes = file_system
+            .values()
+            .map(|dir| dir.total_directory_size(file_system))
+            .collect::<Vec<_>>();
 
-pub fn day_7_part_2(input: &'static str) -> String {
-    let file_system = parse_input_to_file_system(input);
+        directory_sizes.retain(|size| *size <= 100000);
 
-    let root: DirectoryPath = vec!["/".to_string()];
+        let answer = directory_sizes.iter().sum::<i32>();
 
-    let root_dir = file_system.get(&root).expect("Root should exist");
+        answer.into()
+    }
 
-    let space_used = root_dir.total_directory_size(&file_system);
+    fn part_2(&self, file_system: &Self::Parsed) -> Answer {
+        let root: DirectoryPath = vec!["/".to_string()];
 
-    let space_available = FILE_SYSTEM_SIZE - space_used;
+        let root_dir = file_system.get(&root).expect("Root should exist");
 
-    let minimum_delete_size = SPACE_REQUIRED - space_available;
+        let space_used = root_dir.total_directory_size(file_system);
 
-    // dbg!(
-    //     "{} {} {}",
-    //     space_available,
-    //     SPACE_REQUIRED,
-    //     minimum_delete_size
-    // );
+        let space_available = FILE_SYSTEM_SIZE - space_used;
 
-    let mut valid_options = file_system
-        .values()
-        .filter_map(|dir| {
-            let dir_size = dir.total_directory_size(&file_system);
+        let minimum_delete_size = SPACE_REQUIRED - space_available;
 
-            if dir_size > minimum_delete_size {
-                Some(dir_size)
-            } else {
-                None
-            }
-        })
-        .collect::<Vec<_>>();
+        let mut valid_options = file_system
+            .values()
+            .filter_map(|dir| {
+                let dir_size = dir.total_directory_size(file_system);
 
-    valid_options.sort();
+                if dir_size > minimum_delete_size {
+                    Some(dir_size)
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
 
-    // dbg!("{:?}", &valid_options);
+        valid_options.sort();
 
-    valid_options
-        .get(0)
-        .expect("Should have valid answer")
-        .to_string()
+        valid_options
+            .first()
+            .copied()
+            .expect("Should have valid answer")
+            .into()
+    }
 }
 
-pub fn parse_input_to_file_system(input: &'static str) -> FileSystem {
+/// Replays a terminal session of `cd` and `ls` commands into the directories it
+/// saw, keyed by their path from the root
+pub fn parse_input_to_file_system(input: impl BufRead) -> Result<FileSystem, ParseError> {
     let mut file_system: FileSystem = HashMap::new();
 
     let mut current_path: DirectoryPath = vec!["/".to_string()];
+    let mut total_size: i32 = 0;
 
     file_system.insert(
         current_path.clone(),
@@ -78,18 +90,18 @@ pub fn parse_input_to_file_system(input: &'static str) -> FileSystem {
         },
     );
 
-    for line in input.lines() {
-        let trimmed_line = line.trim();
+    let mut lines = LineReader::new(Day7::DAY, input);
 
-        if trimmed_line == "" {
+    while let Some(line) = lines.next_line()? {
+        if line.is_blank() {
             continue;
         }
 
-        if is_command(trimmed_line) {
+        if is_command(line.text) {
             let Command {
                 command_type,
                 argument,
-            } = parse_command(trimmed_line);
+            } = parse_command(line.text).map_err(|err| err.at(Day7::DAY, line.index, line.raw))?;
             match command_type {
                 CommandType::Cd => match argument {
                     Some(arg) => match arg {
@@ -106,32 +118,48 @@ pub fn parse_input_to_file_system(input: &'static str) -> FileSystem {
                         }
                         Argument::Root => current_path = vec!["/".to_string()],
                     },
-                    None => unreachable!("Cd with no argument is invalid"),
+                    None => unreachable!("parse_command rejects cd with no argument"),
                 },
                 CommandType::Ls => {
                     // Do nothing
                 }
             };
-        } else if is_directory(trimmed_line) {
-            let directory = parse_directory(trimmed_line, current_path.clone());
+        } else if is_directory(line.text) {
+            let directory = parse_directory(line.text, current_path.clone());
 
             let mut new_dir_path = current_path.clone();
             new_dir_path.push(directory.name.clone());
             new_dir_path.push("/".to_string());
+
+            // Listing a directory twice mustn't count its contents twice
+            if file_system.contains_key(&new_dir_path) {
+                continue;
+            }
+
             file_system.insert(new_dir_path.clone(), directory);
 
             file_system
                 .entry(current_path.clone())
                 .and_modify(|dir| dir.directories.push(new_dir_path));
         } else {
-            let file = parse_file(trimmed_line);
+            let file =
+                parse_file(line.text).map_err(|err| err.at(Day7::DAY, line.index, line.raw))?;
+
+            // No directory holds more than every file, so this keeps all their sizes in range
+            total_size = total_size.checked_add(file.size).ok_or_else(|| {
+                FragmentError::new(line.text, "Total file size overflows").at(
+                    Day7::DAY,
+                    line.index,
+                    line.raw,
+                )
+            })?;
             file_system
                 .entry(current_path.clone())
                 .and_modify(|dir| dir.files.push(file));
         }
     }
 
-    file_system
+    Ok(file_system)
 }
 
 fn is_command(str: &str) -> bool {
@@ -157,17 +185,19 @@ enum Argument {
     Root,
 }
 
-fn parse_command(command: &str) -> Command {
-    let stripped_command = command.strip_prefix("$ ").expect("Not an actual command");
+fn parse_command(command: &str) -> Result<Command, FragmentError<'_>> {
+    let stripped_command = command
+        .strip_prefix("$ ")
+        .ok_or(FragmentError::new(command, "Not an actual command"))?;
 
     let mut command_split = stripped_command.split(" ");
 
-    let command_name = command_split.next().expect("Unable to parse command term");
+    let command_name = command_split.next().unwrap_or(stripped_command);
 
     let command_type: CommandType = match command_name {
         "cd" => CommandType::Cd,
         "ls" => CommandType::Ls,
-        _ => unreachable!(),
+        _ => return Err(FragmentError::new(command_name, "Unknown command")),
     };
 
     let command_argument_text = command_split.next();
@@ -175,19 +205,32 @@ fn parse_command(command: &str) -> Command {
     let argument = match (&command_type, command_argument_text) {
         (CommandType::Cd, Some("..")) => Some(Argument::Back),
         (CommandType::Cd, Some("/")) => Some(Argument::Root),
-        (CommandType::Cd, Some(directory)) => Some(Argument::Forward(directory.to_string())),
+        (CommandType::Cd, Some(directory)) if !directory.is_empty() => {
+            Some(Argument::Forward(directory.to_string()))
+        }
+        (CommandType::Cd, _) => {
+            return Err(FragmentError::new(
+                command,
+                "Cd with no argument is invalid",
+            ))
+        }
         (CommandType::Ls, None) => None,
-        _ => unreachable!(),
+        (CommandType::Ls, Some(argument)) => {
+            return Err(FragmentError::new(argument, "Ls does not take an argument"))
+        }
     };
 
-    let command = Command {
+    if let Some(extra) = command_split.next() {
+        return Err(FragmentError::new(extra, "Unexpected command argument"));
+    }
+
+    Ok(Command {
         command_type,
         argument,
-    };
-
-    command
+    })
 }
 
+/// A file listed by `ls`
 #[derive(Debug, Clone)]
 pub struct File {
     pub name: String,
@@ -198,15 +241,23 @@ fn is_directory(str: &str) -> bool {
     str.strip_prefix("dir ").is_some()
 }
 
-fn parse_file(str: &str) -> File {
-    let file_text = str.split_once(" ").expect("Failed to parse file");
+fn parse_file(str: &str) -> Result<File, FragmentError<'_>> {
+    let file_text = str
+        .split_once(" ")
+        .ok_or(FragmentError::new(str, "Failed to parse file"))?;
+
+    let size = match file_text.0.parse::<i32>() {
+        Ok(size) if size >= 0 => size,
+        _ => return Err(FragmentError::new(file_text.0, "Failed to parse file size")),
+    };
 
-    File {
-        size: i32::from_str_radix(file_text.0, 10).expect("Failed to parse file size"),
+    Ok(File {
+        size,
         name: file_text.1.to_string(),
-    }
+    })
 }
 
+/// A directory along with what `ls` found directly inside it
 #[derive(Debug, Clone)]
 pub struct Directory {
     pub name: String,
@@ -216,10 +267,12 @@ pub struct Directory {
 }
 
 impl Directory {
+    /// Size of the files directly in this directory
     pub fn total_file_size(&self) -> i32 {
         self.files.iter().fold(0, |acc, curr| acc + curr.size)
     }
 
+    /// Size of everything in this directory, including nested directories
     pub fn total_directory_size(&self, file_system: &FileSystem) -> i32 {
         let files_total_size = self.total_file_size();
         let nested_directory_size = self.directories.iter().fold(0, |acc, curr| {
@@ -245,9 +298,11 @@ fn parse_directory(str: &str, current_path: DirectoryPath) -> Directory {
     }
 }
 
-type FileSystem = HashMap<DirectoryPath, Directory>;
+/// Every directory seen, keyed by its path
+pub type FileSystem = HashMap<DirectoryPath, Directory>;
 
-type DirectoryPath = Vec<String>;
+/// Directory names from the root, starting with `/`
+pub type DirectoryPath = Vec<String>;
 
 #[cfg(test)]
 mod test {
@@ -282,15 +337,81 @@ mod test {
 
     #[test]
     fn part_1() {
-        let answer = day_7_part_1(TEST_INPUT);
+        let answer = Day7.solve_part_1(TEST_INPUT);
 
-        assert_eq!(answer, "95437".to_string());
+        assert_eq!(answer, Answer::from(95437));
     }
 
     #[test]
     fn part_2() {
-        let answer = day_7_part_2(TEST_INPUT);
+        let answer = Day7.solve_part_2(TEST_INPUT);
+
+        assert_eq!(answer, Answer::from(24933642));
+    }
+
+    #[test]
+    fn test_invalid_terminal_output() {
+        let err = parse_input_to_file_system("$ cd /\n$ rm -rf a\n".as_bytes()).unwrap_err();
+
+        assert_eq!(err, ParseError::new(7, 2, 3, "rm", "Unknown command"));
+
+        let err = parse_input_to_file_system("$ cd /\n$ ls\n  12k b.txt\n".as_bytes()).unwrap_err();
+
+        assert_eq!(
+            err,
+            ParseError::new(7, 3, 3, "12k", "Failed to parse file size")
+        );
+
+        let err = parse_input_to_file_system("$ cd\n".as_bytes()).unwrap_err();
+
+        assert_eq!(err.message, "Cd with no argument is invalid");
+
+        let err = parse_input_to_file_system("$ ls\n-5 b.txt\n".as_bytes()).unwrap_err();
+
+        assert_eq!(err.message, "Failed to parse file size");
+
+        let err =
+            parse_input_to_file_system("$ ls\n2147483647 b.txt\n1 c.txt\n".as_bytes()).unwrap_err();
+
+        assert_eq!(
+            err,
+            ParseError::new(7, 3, 1, "1 c.txt", "Total file size overflows")
+        );
+    }
 
-        assert_eq!(answer, "24933642".to_string());
+    #[test]
+    fn test_directory_listed_twice() {
+        let input = "$ cd /\n$ ls\ndir a\n$ cd a\n$ ls\n10 b.txt\n$ cd ..\n$ ls\ndir a\n";
+        let file_system = parse_input_to_file_system(input.as_bytes()).unwrap();
+
+        let root = &file_system[&vec!["/".to_string()]];
+
+        assert_eq!(root.directories.len(), 1);
+        assert_eq!(root.total_directory_size(&file_system), 10);
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        crate::property::check(generate::generate, |tree| {
+            let file_system = Day7.parse(&generate::serialize(tree)).unwrap();
+
+            let mut directories = Vec::new();
+            tree.walk(vec!["/".to_string()], &mut directories);
+
+            assert_eq!(file_system.len(), directories.len());
+
+            for (path, expected) in directories {
+                let directory = &file_system[&path];
+
+                let files = directory
+                    .files
+                    .iter()
+                    .map(|file| (file.name.clone(), file.size))
+                    .collect::<Vec<_>>();
+
+                assert_eq!(files, expected.files);
+                assert_eq!(directory.directories.len(), expected.directories.len());
+            }
+        });
     }
 }
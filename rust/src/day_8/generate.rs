@@ -0,0 +1,47 @@
+use std::io::{self, Write};
+
+use super::Forest;
+use crate::rng::Rng;
+
+pub fn generate(rng: &mut Rng) -> Forest {
+    let rows = rng.count(1..=20);
+    let columns = rng.count(1..=20);
+
+    let trees = (0..rows)
+        .map(|_| (0..columns).map(|_| rng.range(0..=9) as i32).collect())
+        .collect();
+
+    // The forest calls its row count the width
+    Forest {
+        width: rows,
+        height: columns,
+        trees,
+    }
+}
+
+pub fn serialize(forest: &Forest) -> String {
+    forest
+        .trees
+        .iter()
+        .map(|row| {
+            let digits = row.iter().map(i32::to_string).collect::<String>();
+            digits + "\n"
+        })
+        .collect()
+}
+
+/// A forest `size` trees on each side
+pub fn stress(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    let mut row = vec![b'0'; size + 1];
+    row[size] = b'\n';
+
+    for _ in 0..size {
+        for tree in &mut row[..size] {
+            *tree = b'0' + rng.count(0..=9) as u8;
+        }
+
+        out.write_all(&row)?;
+    }
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-pub static INPUT_DAY_8: &'static str = r#"
+pub static INPUT_DAY_8: &str = r#"
 313213123212200312011243203120214010202554420335045116203101005212525131015305511140012431022113113
 222021333112322244231542054023354511304431216312534453560105660253445311020244233130144313440030322
 200012220300132310233100025524032003355144536126232161330106115213441450552235514000243120210031130
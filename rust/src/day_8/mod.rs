@@ -1,155 +1,188 @@
+//! Day 8: Treetop Tree House
+
+use std::io::BufRead;
+
+use crate::answer::Answer;
+use crate::error::{FragmentError, ParseError};
+use crate::normalize::LineReader;
+use crate::solution::Solution;
+
+pub mod generate;
 pub mod input;
 
-pub fn day_8_part_1(input: &'static str) -> String {
-    let forest = parse_input(input);
+pub struct Day8;
 
-    let visible_trees = count_visible_trees(&forest);
+impl Solution for Day8 {
+    type Parsed = Forest;
 
-    visible_trees.to_string()
-}
+    const DAY: u8 = 8;
+    const INPUT: &'static str = input::INPUT_DAY_8;
+
+    fn read(&self, input: &mut dyn BufRead) -> Result<Self::Parsed, ParseError> {
+        parse_input(input)
+    }
 
-pub fn day_8_part_2(input: &'static str) -> String {
-    let forest = parse_input(input);
+    fn part_1(&self, forest: &Self::Parsed) -> Answer {
+        count_visible_trees(forest).into()
+    }
 
-    let mut scenic_score = get_scenic_scores(&forest);
+    fn part_2(&self, forest: &Self::Parsed) -> Answer {
+        let mut scenic_score = get_scenic_scores(forest);
 
-    scenic_score.sort();
+        scenic_score.sort();
 
-    let highest_score = scenic_score[scenic_score.len() - 1];
+        let highest_score = scenic_score[scenic_score.len() - 1];
 
-    highest_score.to_string()
+        highest_score.into()
+    }
 }
 
-type Tree = i32;
+/// The height of a tree, from 0 to 9
+pub type Tree = i32;
 
 type Trees = Vec<Vec<Tree>>;
 
-#[derive(Debug)]
+/// A rectangular grid of tree heights
+#[derive(Debug, PartialEq)]
 pub struct Forest {
     width: usize,
     height: usize,
     trees: Trees,
 }
 
-fn is_visible<'a>(
-    current_row_idx: usize,
-    current_column_idx: usize,
+impl Forest {
+    /// The tree heights, one row per line of input
+    pub fn trees(&self) -> &[Vec<Tree>] {
+        &self.trees
+    }
+}
+
+/// Walks from a tree towards the edge in `direction` while the trees are
+/// shorter, giving whether it reached the edge and how many trees it passed
+fn is_visible(
+    mut current_row_idx: usize,
+    mut current_column_idx: usize,
     forest: &Forest,
     tree: Tree,
     direction: Option<&'static str>,
     mut visible_trees: i32,
 ) -> (bool, i32) {
-    if current_row_idx == 0
-        || current_column_idx == 0
-        || current_row_idx == forest.width - 1
-        || current_column_idx == forest.height - 1
-    {
-        return (true, visible_trees);
-    }
+    loop {
+        if current_row_idx == 0
+            || current_column_idx == 0
+            || current_row_idx == forest.width - 1
+            || current_column_idx == forest.height - 1
+        {
+            return (true, visible_trees);
+        }
 
-    let left = current_row_idx - 1;
-    let right = current_row_idx + 1;
-    let up = current_column_idx - 1;
-    let down = current_column_idx + 1;
-
-    visible_trees += 1;
-
-    if forest.trees[left][current_column_idx] < tree && direction == Some("left") {
-        return is_visible(
-            left,
-            current_column_idx,
-            forest,
-            tree,
-            direction,
-            visible_trees,
-        );
-    } else if forest.trees[right][current_column_idx] < tree && direction == Some("right") {
-        return is_visible(
-            right,
-            current_column_idx,
-            forest,
-            tree,
-            direction,
-            visible_trees,
-        );
-    } else if forest.trees[current_row_idx][up] < tree && direction == Some("up") {
-        return is_visible(current_row_idx, up, forest, tree, direction, visible_trees);
-    } else if forest.trees[current_row_idx][down] < tree && direction == Some("down") {
-        return is_visible(
-            current_row_idx,
-            down,
-            forest,
-            tree,
-            direction,
-            visible_trees,
-        );
-    } else {
-        return (false, visible_trees);
-    };
+        visible_trees += 1;
+
+        let (next_row_idx, next_column_idx) = match direction {
+            Some("left") => (current_row_idx - 1, current_column_idx),
+            Some("right") => (current_row_idx + 1, current_column_idx),
+            Some("up") => (current_row_idx, current_column_idx - 1),
+            Some("down") => (current_row_idx, current_column_idx + 1),
+            _ => return (false, visible_trees),
+        };
+
+        if forest.trees[next_row_idx][next_column_idx] >= tree {
+            return (false, visible_trees);
+        }
+
+        current_row_idx = next_row_idx;
+        current_column_idx = next_column_idx;
+    }
 }
 
-pub fn count_visible_trees(forest: &Forest) -> String {
+/// How many trees can be seen from outside the forest
+pub fn count_visible_trees(forest: &Forest) -> i32 {
     let mut visibility_count: i32 = 0;
     for i in 0..forest.width {
         for j in 0..forest.height {
             let tree = forest.trees[i][j];
-            let (u, _) = is_visible(i, j, &forest, tree, Some("up"), 0);
-            let (r, _) = is_visible(i, j, &forest, tree, Some("right"), 0);
-            let (d, _) = is_visible(i, j, &forest, tree, Some("down"), 0);
-            let (l, _) = is_visible(i, j, &forest, tree, Some("left"), 0);
+            let (u, _) = is_visible(i, j, forest, tree, Some("up"), 0);
+            let (r, _) = is_visible(i, j, forest, tree, Some("right"), 0);
+            let (d, _) = is_visible(i, j, forest, tree, Some("down"), 0);
+            let (l, _) = is_visible(i, j, forest, tree, Some("left"), 0);
 
             if u || r || d || l {
                 visibility_count += 1;
             }
         }
     }
-    visibility_count.to_string()
+    visibility_count
 }
 
-pub fn get_scenic_scores(forest: &Forest) -> Vec<i32> {
-    let mut scenic_scores: Vec<i32> = vec![];
+/// The scenic score of every tree, row by row
+pub fn get_scenic_scores(forest: &Forest) -> Vec<i64> {
+    let mut scenic_scores: Vec<i64> = vec![];
     for i in 0..forest.width {
         for j in 0..forest.height {
             let tree = forest.trees[i][j];
-            let (_, up_score) = is_visible(i, j, &forest, tree, Some("up"), 0);
-            let (_, right_score) = is_visible(i, j, &forest, tree, Some("right"), 0);
-            let (_, down_score) = is_visible(i, j, &forest, tree, Some("down"), 0);
-            let (_, left_score) = is_visible(i, j, &forest, tree, Some("left"), 0);
-
-            scenic_scores.push(up_score * right_score * down_score * left_score);
+            let (_, up_score) = is_visible(i, j, forest, tree, Some("up"), 0);
+            let (_, right_score) = is_visible(i, j, forest, tree, Some("right"), 0);
+            let (_, down_score) = is_visible(i, j, forest, tree, Some("down"), 0);
+            let (_, left_score) = is_visible(i, j, forest, tree, Some("left"), 0);
+
+            // Four distances can multiply past an i32 in a forest a few hundred trees across
+            let scores = [up_score, right_score, down_score, left_score];
+            scenic_scores.push(scores.into_iter().map(i64::from).product());
         }
     }
     scenic_scores
 }
 
-pub fn parse_input(input: &str) -> Forest {
+/// Parses one row of digits per line; every row must be as long as the first
+pub fn parse_input(input: impl BufRead) -> Result<Forest, ParseError> {
     let mut trees: Trees = Vec::new();
 
-    for (row_idx, line) in input.lines().enumerate() {
-        let trimmed_line = line.trim();
+    let mut lines = LineReader::new(Day8::DAY, input);
 
-        if trimmed_line == "" {
+    while let Some(line) = lines.next_line()? {
+        if line.is_blank() {
             continue;
         }
 
-        trees.push(Vec::new());
-        for tree_char in trimmed_line.chars() {
-            trees[row_idx - 1].push(
-                i32::from_str_radix(&tree_char.to_string(), 10)
-                    .expect("Failed to parse tree digit"),
-            );
+        let mut row = Vec::new();
+        for (char_idx, tree_char) in line.text.char_indices() {
+            let tree = tree_char.to_digit(10).ok_or_else(|| {
+                let fragment = &line.text[char_idx..char_idx + tree_char.len_utf8()];
+                FragmentError::new(fragment, "Failed to parse tree digit").at(
+                    Day8::DAY,
+                    line.index,
+                    line.raw,
+                )
+            })?;
+
+            row.push(tree as Tree);
+        }
+
+        if let Some(first_row) = trees.first() {
+            if first_row.len() != row.len() {
+                return Err(FragmentError::new(
+                    line.text,
+                    "Every row of trees should be the same length",
+                )
+                .at(Day8::DAY, line.index, line.raw));
+            }
         }
+
+        trees.push(row);
     }
 
     let width = trees.len();
 
-    let height = trees[0].len();
+    let height = match trees.first() {
+        Some(first_row) => first_row.len(),
+        None => return Err(lines.end_of_input("No trees in the forest")),
+    };
 
-    Forest {
+    Ok(Forest {
         width,
         height,
         trees,
-    }
+    })
 }
 
 #[cfg(test)]
@@ -167,7 +200,7 @@ mod test {
 
     #[test]
     fn part_1() {
-        let forest = parse_input(TEST_INPUT);
+        let forest = parse_input(TEST_INPUT.as_bytes()).unwrap();
 
         assert_eq!(forest.width, 5);
 
@@ -177,12 +210,12 @@ mod test {
 
         let visible_trees = count_visible_trees(&forest);
 
-        assert_eq!(visible_trees, "21".to_string());
+        assert_eq!(visible_trees, 21);
     }
 
     #[test]
     fn part_2() {
-        let forest = parse_input(TEST_INPUT);
+        let forest = parse_input(TEST_INPUT.as_bytes()).unwrap();
 
         let mut scenic_score = get_scenic_scores(&forest);
 
@@ -196,4 +229,48 @@ mod test {
 
         assert_eq!(highest_score, 8);
     }
+
+    #[test]
+    fn test_invalid_forest() {
+        let err = parse_input("303\n2x5\n".as_bytes()).unwrap_err();
+
+        assert_eq!(
+            err,
+            ParseError::new(8, 2, 2, "x", "Failed to parse tree digit")
+        );
+
+        let err = parse_input("303\n25\n".as_bytes()).unwrap_err();
+
+        assert_eq!(err.message, "Every row of trees should be the same length");
+
+        let err = parse_input("\n\n".as_bytes()).unwrap_err();
+
+        assert_eq!(err, ParseError::new(8, 3, 1, "", "No trees in the forest"));
+    }
+
+    #[test]
+    fn test_long_views() {
+        // The tall tree sees along the whole of its row
+        let length = 100_000;
+        let edge = "0".repeat(length);
+        let middle = format!("09{}", "0".repeat(length - 2));
+        let input = format!("{}\n{}\n{}\n", edge, middle, edge);
+
+        let forest = parse_input(input.as_bytes()).unwrap();
+
+        assert_eq!(count_visible_trees(&forest), 2 * length as i32 + 3);
+        assert_eq!(
+            get_scenic_scores(&forest).into_iter().max(),
+            Some(length as i64 - 2)
+        );
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        crate::property::check(generate::generate, |forest| {
+            let parsed = Day8.parse(&generate::serialize(forest)).unwrap();
+
+            assert_eq!(&parsed, forest);
+        });
+    }
 }
@@ -0,0 +1,50 @@
+use std::io::{self, Write};
+
+use super::{Direction, MoveAction};
+use crate::rng::Rng;
+
+pub fn generate(rng: &mut Rng) -> Vec<MoveAction> {
+    (0..rng.count(1..=100))
+        .map(|_| {
+            let direction = match rng.count(0..=3) {
+                0 => Direction::East,
+                1 => Direction::North,
+                2 => Direction::West,
+                _ => Direction::South,
+            };
+
+            MoveAction {
+                direction,
+                quantity: rng.range(0..=20) as i32,
+            }
+        })
+        .collect()
+}
+
+pub fn serialize(actions: &[MoveAction]) -> String {
+    actions
+        .iter()
+        .map(|action| {
+            let letter = match action.direction {
+                Direction::East => "R",
+                Direction::North => "U",
+                Direction::West => "L",
+                Direction::South => "D",
+                _ => unreachable!("Only the four directions are generated"),
+            };
+
+            format!("{} {}\n", letter, action.quantity)
+        })
+        .collect()
+}
+
+/// `size` moves of up to 20 steps each
+pub fn stress(rng: &mut Rng, size: usize, out: &mut dyn Write) -> io::Result<()> {
+    for _ in 0..size {
+        let letter = rng.choose(&['R', 'U', 'L', 'D']);
+
+        writeln!(out, "{} {}", letter, rng.range(1..=20))?;
+    }
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-pub static INPUT_DAY_9: &'static str = r#"
+pub static INPUT_DAY_9: &str = r#"
 L 2
 D 2
 R 2
@@ -1,9 +1,18 @@
-use std::{char::ParseCharError, collections::HashSet, str::FromStr};
+//! Day 9: Rope Bridge
 
+use std::{collections::HashSet, io::BufRead, str::FromStr};
+
+use crate::answer::Answer;
+use crate::error::{FragmentError, ParseError};
+use crate::normalize::LineReader;
+use crate::solution::Solution;
+
+pub mod generate;
 pub mod input;
 
+/// A step of one square, including the diagonals a tail knot can take
 #[derive(Debug, PartialEq, Eq)]
-enum Direction {
+pub enum Direction {
     North,
     NorthEast,
     East,
@@ -74,8 +83,12 @@ impl Position {
     }
 }
 
+/// The letter wasn't one of `R`, `U`, `L` or `D`
+#[derive(Debug)]
+pub struct InvalidDirection;
+
 impl FromStr for Direction {
-    type Err = ParseCharError;
+    type Err = InvalidDirection;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
@@ -83,17 +96,30 @@ impl FromStr for Direction {
             "U" => Ok(Direction::North),
             "L" => Ok(Direction::West),
             "D" => Ok(Direction::South),
-            _ => unreachable!("Not a direction"),
+            _ => Err(InvalidDirection),
         }
     }
 }
 
+/// Move the head `quantity` steps in one direction
 #[derive(Debug, PartialEq, Eq)]
-struct MoveAction {
+pub struct MoveAction {
     direction: Direction,
     quantity: i32,
 }
 
+impl MoveAction {
+    /// Which way the head moves
+    pub fn direction(&self) -> &Direction {
+        &self.direction
+    }
+
+    /// How many steps the head takes
+    pub fn quantity(&self) -> i32 {
+        self.quantity
+    }
+}
+
 impl From<&Direction> for Delta {
     fn from(dir: &Direction) -> Self {
         match dir {
@@ -109,31 +135,66 @@ impl From<&Direction> for Delta {
     }
 }
 
-fn parse_input(str: &str) -> Vec<MoveAction> {
+fn parse_move_action(trimmed_line: &str) -> Result<MoveAction, FragmentError<'_>> {
+    let parsed_line = trimmed_line
+        .split_once(" ")
+        .ok_or(FragmentError::new(trimmed_line, "Failed to parse line"))?;
+
+    let direction = Direction::from_str(parsed_line.0)
+        .map_err(|_| FragmentError::new(parsed_line.0, "Invalid direction"))?;
+
+    let quantity = match parsed_line.1.parse::<i32>() {
+        Ok(quantity) if quantity >= 0 => quantity,
+        _ => return Err(FragmentError::new(parsed_line.1, "Invalid number of moves")),
+    };
+
+    Ok(MoveAction {
+        direction,
+        quantity,
+    })
+}
+
+/// The most steps of the head the fuzzers solve. Every step is simulated, so a
+/// move like `R 2147483647` is valid but takes hours; longer inputs are only
+/// parsed.
+pub const MAX_FUZZED_STEPS: i64 = 100_000;
+
+/// How many steps the head takes over every move
+pub fn total_steps(actions: &[MoveAction]) -> i64 {
+    actions.iter().map(|action| action.quantity() as i64).sum()
+}
+
+/// Parses one `<direction> <steps>` move per line
+pub fn parse_input(str: impl BufRead) -> Result<Vec<MoveAction>, ParseError> {
     let mut move_actions = Vec::new();
-    for line in str.lines() {
-        let trimmed_line = line.trim();
 
-        if trimmed_line == "" {
+    // The head moves one step at a time, so no knot can get further from the
+    // start than this, and keeping it in an i32 keeps every position in one
+    let mut total_moves: i32 = 0;
+
+    let mut lines = LineReader::new(Day9::DAY, str);
+
+    while let Some(line) = lines.next_line()? {
+        if line.is_blank() {
             continue;
         }
 
-        let parsed_line = trimmed_line.split_once(" ").expect("Failed to parse line");
+        let action =
+            parse_move_action(line.text).map_err(|err| err.at(Day9::DAY, line.index, line.raw))?;
 
-        let action = MoveAction {
-            direction: Direction::from_str(parsed_line.0).expect("Invalid direction"),
-            quantity: i32::from_str_radix(parsed_line.1, 10).expect("Invalid number of moves"),
-        };
+        total_moves = total_moves.checked_add(action.quantity).ok_or_else(|| {
+            FragmentError::new(line.text, "Too many moves").at(Day9::DAY, line.index, line.raw)
+        })?;
 
         move_actions.push(action);
     }
-    move_actions
+    Ok(move_actions)
 }
 
 fn run_move_action(
     action: &MoveAction,
     head: &mut Position,
-    tails: &mut Vec<Position>,
+    tails: &mut [Position],
     tail_positions_visited: &mut HashSet<Position>,
 ) {
     let head_move_delta: Delta = (&action.direction).into();
@@ -145,7 +206,7 @@ fn run_move_action(
         let is_last_tail = tail_idx == tails.len() - 1;
 
         let tail_delta = if is_first_tail {
-            tail.get_delta_to_head(&head)
+            tail.get_delta_to_head(head)
         } else {
             tail.get_delta_to_head(&tails[tail_idx - 1])
         };
@@ -164,9 +225,9 @@ fn run_move_action(
 }
 
 fn run_move_actions(
-    actions: &Vec<MoveAction>,
+    actions: &[MoveAction],
     head: &mut Position,
-    tails: &mut Vec<Position>,
+    tails: &mut [Position],
 ) -> HashSet<Position> {
     let mut tail_positions_visited: HashSet<Position> = HashSet::new();
 
@@ -174,45 +235,46 @@ fn run_move_actions(
 
     for action in actions {
         for _ in 0..action.quantity {
-            run_move_action(&action, head, tails, &mut tail_positions_visited);
+            run_move_action(action, head, tails, &mut tail_positions_visited);
         }
     }
 
     tail_positions_visited
 }
 
-pub fn day_9_part_1(input: &'static str) -> String {
-    let mut head = Position { x: 0, y: 0 };
-
-    let mut tails = vec![Position { x: 0, y: 0 }];
-
-    let actions = parse_input(input);
+/// How many squares the last of `tails` knots behind the head visits
+///
+/// # Panics
+///
+/// If `tails` is zero, since there is then no tail to follow
+pub fn count_tail_positions(actions: &[MoveAction], tails: usize) -> usize {
+    assert!(tails > 0, "The rope needs at least one tail knot");
 
-    let tail_visited = run_move_actions(&actions, &mut head, &mut tails);
+    let mut head = Position { x: 0, y: 0 };
+    let mut tails = vec![Position { x: 0, y: 0 }; tails];
 
-    tail_visited.len().to_string()
+    run_move_actions(actions, &mut head, &mut tails).len()
 }
 
-pub fn day_9_part_2(input: &'static str) -> String {
-    let mut head = Position { x: 0, y: 0 };
+pub struct Day9;
 
-    let mut tails = vec![
-        Position { x: 0, y: 0 },
-        Position { x: 0, y: 0 },
-        Position { x: 0, y: 0 },
-        Position { x: 0, y: 0 },
-        Position { x: 0, y: 0 },
-        Position { x: 0, y: 0 },
-        Position { x: 0, y: 0 },
-        Position { x: 0, y: 0 },
-        Position { x: 0, y: 0 },
-    ];
+impl Solution for Day9 {
+    type Parsed = Vec<MoveAction>;
 
-    let actions = parse_input(input);
+    const DAY: u8 = 9;
+    const INPUT: &'static str = input::INPUT_DAY_9;
 
-    let tail_visited = run_move_actions(&actions, &mut head, &mut tails);
+    fn read(&self, input: &mut dyn BufRead) -> Result<Self::Parsed, ParseError> {
+        parse_input(input)
+    }
 
-    tail_visited.len().to_string()
+    fn part_1(&self, actions: &Self::Parsed) -> Answer {
+        count_tail_positions(actions, 1).into()
+    }
+
+    fn part_2(&self, actions: &Self::Parsed) -> Answer {
+        count_tail_positions(actions, 9).into()
+    }
 }
 
 #[cfg(test)]
@@ -259,7 +321,7 @@ mod test {
 
     #[test]
     fn test_parse_input() {
-        let actions = parse_input(TEST_INPUT);
+        let actions = parse_input(TEST_INPUT.as_bytes()).unwrap();
 
         assert_eq!(
             actions[0],
@@ -279,18 +341,19 @@ mod test {
     }
 
     #[test]
+    #[allow(clippy::ifs_same_cond)]
     fn test_moves() {
         let mut head = Position { x: 0, y: 0 };
 
         let mut tails = vec![Position { x: 0, y: 0 }];
 
-        let actions = parse_input(TEST_INPUT);
+        let actions = parse_input(TEST_INPUT.as_bytes()).unwrap();
 
         let mut tail_positions_visited: HashSet<Position> = HashSet::new();
 
         for (idx, action) in actions.iter().enumerate() {
             for _ in 0..action.quantity {
-                run_move_action(&action, &mut head, &mut tails, &mut tail_positions_visited);
+                run_move_action(action, &mut head, &mut tails, &mut tail_positions_visited);
             }
 
             let tail = &tails[0];
@@ -319,7 +382,7 @@ mod test {
 
         let mut tails = vec![Position { x: 0, y: 0 }];
 
-        let actions = parse_input(TEST_INPUT);
+        let actions = parse_input(TEST_INPUT.as_bytes()).unwrap();
 
         let tail_visited = run_move_actions(&actions, &mut head, &mut tails);
 
@@ -339,7 +402,7 @@ mod test {
 
     #[test]
     fn test_parse_input_longer() {
-        let actions = parse_input(TEST_INPUT_LONGER_ROPE);
+        let actions = parse_input(TEST_INPUT_LONGER_ROPE.as_bytes()).unwrap();
 
         assert_eq!(
             actions[4],
@@ -374,10 +437,57 @@ mod test {
             Position { x: 0, y: 0 },
         ];
 
-        let actions = parse_input(TEST_INPUT_LONGER_ROPE);
+        let actions = parse_input(TEST_INPUT_LONGER_ROPE.as_bytes()).unwrap();
 
         let tail_visited = run_move_actions(&actions, &mut head, &mut tails);
 
         assert_eq!(tail_visited.len(), 36);
     }
+
+    #[test]
+    fn test_invalid_moves() {
+        let err = parse_input("R 4\n  X 2\n".as_bytes()).unwrap_err();
+
+        assert_eq!(err, ParseError::new(9, 2, 3, "X", "Invalid direction"));
+
+        let err = parse_input("R 4\nU -2\n".as_bytes()).unwrap_err();
+
+        assert_eq!(
+            err,
+            ParseError::new(9, 2, 3, "-2", "Invalid number of moves")
+        );
+
+        let err = parse_input("R4\n".as_bytes()).unwrap_err();
+
+        assert_eq!(err.message, "Failed to parse line");
+
+        let actions = parse_input("R 1000000\nL 1\n".as_bytes()).unwrap();
+
+        assert_eq!(actions.len(), 2);
+
+        let err = parse_input("R 2147483647\nL 1\n".as_bytes()).unwrap_err();
+
+        assert_eq!(err, ParseError::new(9, 2, 1, "L 1", "Too many moves"));
+    }
+
+    #[test]
+    fn test_million_moves() {
+        // Walks the same square a quarter of a million times
+        let input = "R 2\nU 2\nL 2\nD 2\n".repeat(250_000);
+        let actions = parse_input(input.as_bytes()).unwrap();
+
+        assert_eq!(actions.len(), 1_000_000);
+        assert_eq!(total_steps(&actions), 2_000_000);
+        assert_eq!(count_tail_positions(&actions, 1), 5);
+        assert_eq!(count_tail_positions(&actions, 9), 1);
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        crate::property::check(generate::generate, |actions| {
+            let parsed = Day9.parse(&generate::serialize(actions)).unwrap();
+
+            assert_eq!(&parsed, actions);
+        });
+    }
 }
@@ -0,0 +1,153 @@
+use std::fmt;
+
+/// The most characters of the offending text an error shows, so a bad line in
+/// a huge input doesn't flood the terminal. The full text stays in the error.
+const MAX_DISPLAYED_TEXT: usize = 40;
+
+/// A problem with the puzzle input, pointing at the offending text.
+/// Lines and columns are both one based, like an editor shows them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(
+        day: u8,
+        line: usize,
+        column: usize,
+        text: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            day,
+            line,
+            column,
+            text: text.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Error for the input running out part way through something, reported
+    /// on the line after the last one
+    pub fn end_of_input(day: u8, line_count: usize, message: impl Into<String>) -> Self {
+        Self::new(day, line_count + 1, 1, "", message)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {} line {} column {}: {}",
+            self.day, self.line, self.column, self.message
+        )?;
+
+        if !self.text.is_empty() {
+            match self.text.char_indices().nth(MAX_DISPLAYED_TEXT) {
+                Some((end, _)) => write!(f, " (`{}…`)", &self.text[..end])?,
+                None => write!(f, " (`{}`)", self.text)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// An error found while parsing a single line, before it is known which line
+/// that was. `fragment` must be a slice of the line so its column can be found.
+#[derive(Debug, PartialEq, Eq)]
+pub struct FragmentError<'a> {
+    pub fragment: &'a str,
+    pub message: String,
+}
+
+impl<'a> FragmentError<'a> {
+    pub fn new(fragment: &'a str, message: impl Into<String>) -> Self {
+        Self {
+            fragment,
+            message: message.into(),
+        }
+    }
+
+    /// `line_idx` is the zero based index as produced by `lines().enumerate()`
+    pub fn at(self, day: u8, line_idx: usize, line: &str) -> ParseError {
+        ParseError::new(
+            day,
+            line_idx + 1,
+            column_of(line, self.fragment),
+            self.fragment,
+            self.message,
+        )
+    }
+}
+
+/// One based column of `fragment` within `line`, or 1 if it is not a slice of it
+pub fn column_of(line: &str, fragment: &str) -> usize {
+    let line_start = line.as_ptr() as usize;
+    let fragment_start = fragment.as_ptr() as usize;
+
+    if fragment_start >= line_start && fragment_start <= line_start + line.len() {
+        line[..fragment_start - line_start].chars().count() + 1
+    } else {
+        1
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_column_of() {
+        let line = "    move 1 from 2 to 1";
+        let fragment = line.trim().split(' ').nth(3).unwrap();
+
+        assert_eq!(column_of(line, fragment), 17);
+
+        let elsewhere = String::from("not in the line");
+
+        assert_eq!(column_of(line, &elsewhere), 1);
+    }
+
+    #[test]
+    fn test_fragment_error_at() {
+        let line = "  addx five";
+        let fragment = &line[7..];
+
+        let err = FragmentError::new(fragment, "Invalid addx value").at(10, 4, line);
+
+        assert_eq!(err, ParseError::new(10, 5, 8, "five", "Invalid addx value"));
+
+        assert_eq!(
+            err.to_string(),
+            "day 10 line 5 column 8: Invalid addx value (`five`)"
+        );
+    }
+
+    #[test]
+    fn test_long_text_is_shortened() {
+        let row = "9".repeat(5000);
+        let err = ParseError::new(8, 2, 1, row.as_str(), "Row is the wrong length");
+
+        assert_eq!(err.text, row);
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "day 8 line 2 column 1: Row is the wrong length (`{}…`)",
+                "9".repeat(40)
+            )
+        );
+
+        let exact = "é".repeat(40);
+        let err = ParseError::new(8, 2, 1, exact.as_str(), "Invalid tree");
+
+        assert!(err.to_string().ends_with(&format!("(`{}`)", exact)));
+    }
+}
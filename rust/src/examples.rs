@@ -0,0 +1,51 @@
+//! Checks every solver against the worked examples from the puzzle text.
+//! `build.rs` generates a test per file in `examples/day_N/`, so adding an
+//! example only means adding `NAME.txt` and its answers in `NAME.toml`.
+
+use crate::verify::{check, parse_day_answers, Outcome};
+use advent::{registry, Part};
+
+fn check_example(day: u8, input: &str, answers: Option<&str>) {
+    let answers = answers.expect("Examples need a NAME.toml with their answers next to them");
+    let answers = parse_day_answers(answers, day).unwrap_or_else(|err| panic!("{}", err));
+
+    let solvers = registry();
+    let solver = solvers
+        .iter()
+        .find(|solver| solver.day() == day)
+        .unwrap_or_else(|| panic!("Day {} has an example but no solver", day));
+
+    let parsed = solver
+        .parse(input)
+        .unwrap_or_else(|err| panic!("Example should parse: {}", err));
+
+    // Parts without a recorded answer are only checked to parse
+    for part in Part::all() {
+        if !answers.contains_key(&(day, part)) {
+            continue;
+        }
+
+        let answer = solver.solve(parsed.as_ref(), part);
+
+        if let Outcome::Fail { expected } = check(&answers, day, part, &answer) {
+            panic!(
+                "Day {} part {} answered {} but the example expects {}",
+                day, part, answer, expected
+            );
+        }
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+
+#[test]
+fn test_every_solver_has_an_example() {
+    for solver in registry() {
+        assert!(
+            EXAMPLE_DAYS.contains(&solver.day()),
+            "Day {} has no examples in examples/day_{}",
+            solver.day(),
+            solver.day()
+        );
+    }
+}
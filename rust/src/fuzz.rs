@@ -0,0 +1,214 @@
+//! Feeds corrupted and random input to every day's parser and solver. Bad
+//! input must come back as a `ParseError`: nothing may panic, overflow or
+//! index out of bounds, whatever the input looks like.
+//!
+//! Each case is built from a seed, so a failure can be replayed. The number of
+//! cases per day defaults to [`DEFAULT_CASES`] and can be raised for a longer
+//! run with `ADVENT_FUZZ_CASES=100000 cargo test fuzz::`. Debug builds are
+//! used on purpose, since only they check for overflow. The `cargo fuzz`
+//! targets in `fuzz` do the same with libFuzzer guiding the inputs.
+
+use std::{
+    any::Any,
+    env, fs,
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+};
+
+use crate::day_9::{total_steps, MoveAction, MAX_FUZZED_STEPS};
+use crate::rng::Rng;
+use crate::solution::{registry, Part, Solver};
+
+const DEFAULT_CASES: u64 = 300;
+
+/// Fragments that tend to find edge cases when spliced into an input, shared
+/// with the `cargo fuzz` targets in `fuzz`
+const DICTIONARY: &str = include_str!("../fuzz/advent.dict");
+
+/// The fragments in a libFuzzer dictionary: one quoted string per line, after
+/// an optional name, with `\xNN` escapes for bytes
+fn dictionary(text: &str) -> Vec<String> {
+    text.lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let quoted = &line[line.find('"')? + 1..line.rfind('"')?];
+            let mut bytes = Vec::new();
+            let mut rest = quoted.as_bytes();
+
+            while let Some((&byte, tail)) = rest.split_first() {
+                rest = match (byte, tail) {
+                    (b'\\', [b'x', hi, lo, tail @ ..]) => {
+                        let hex = [*hi, *lo];
+                        bytes.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+                        tail
+                    }
+                    (b'\\', [escaped, tail @ ..]) => {
+                        bytes.push(*escaped);
+                        tail
+                    }
+                    _ => {
+                        bytes.push(byte);
+                        tail
+                    }
+                };
+            }
+
+            String::from_utf8(bytes).ok()
+        })
+        .collect()
+}
+
+fn cases() -> u64 {
+    env::var("ADVENT_FUZZ_CASES")
+        .ok()
+        .and_then(|cases| cases.parse().ok())
+        .unwrap_or(DEFAULT_CASES)
+}
+
+/// The day's worked examples, which mutations start from
+fn examples(day: u8) -> Vec<String> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("examples")
+        .join(format!("day_{}", day));
+
+    let mut paths = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| Some(entry.ok()?.path()))
+                .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    paths.sort();
+
+    paths
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .collect()
+}
+
+/// A char boundary at or before `idx`, so edits never split a character
+fn boundary(text: &str, mut idx: usize) -> usize {
+    while !text.is_char_boundary(idx) {
+        idx -= 1;
+    }
+    idx
+}
+
+/// Applies a few random edits to `input`
+fn mutate(rng: &mut Rng, fragments: &[String], input: &str) -> String {
+    let mut text = input.to_string();
+
+    for _ in 0..rng.count(1..=4) {
+        let start = boundary(&text, rng.count(0..=text.len()));
+        let end = boundary(&text, rng.count(start..=text.len().min(start + 8)));
+
+        let fragment = rng.choose(fragments).as_str();
+
+        match rng.count(0..=4) {
+            0 => text.replace_range(start..end, ""),
+            1 => text.replace_range(start..end, fragment),
+            2 => text.insert_str(start, fragment),
+            3 => {
+                let copy = text[start..end].to_string();
+                text.insert_str(end, &copy);
+            }
+            _ => text.truncate(start),
+        }
+    }
+
+    text
+}
+
+/// Printable noise with no structure at all
+fn random_text(rng: &mut Rng) -> String {
+    (0..rng.count(0..=64))
+        .map(|_| match rng.count(0..=9) {
+            0 => '\n',
+            1 => ' ',
+            _ => (b' ' + rng.count(0..=94) as u8) as char,
+        })
+        .collect()
+}
+
+/// Whether a parsed input can be solved in the time a test has
+fn quick_to_solve(parsed: &dyn Any) -> bool {
+    parsed
+        .downcast_ref::<Vec<MoveAction>>()
+        .is_none_or(|actions| total_steps(actions) <= MAX_FUZZED_STEPS)
+}
+
+/// Parses and, if that works, solves both parts, returning the panic message if anything panics.
+/// The panic hook is left alone, since it's shared by every test running at
+/// once; cargo test captures what it prints for this test's thread.
+fn run_case(solver: &dyn Solver, input: &str) -> Result<(), String> {
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        if let Ok(parsed) = solver.parse(input) {
+            if !quick_to_solve(parsed.as_ref()) {
+                return;
+            }
+
+            for part in Part::all() {
+                solver.solve(parsed.as_ref(), part);
+            }
+        }
+    }))
+    .map_err(|err| {
+        err.downcast_ref::<String>()
+            .cloned()
+            .or_else(|| {
+                err.downcast_ref::<&str>()
+                    .map(|message| message.to_string())
+            })
+            .unwrap_or_default()
+    })
+}
+
+#[test]
+fn test_solvers_never_panic() {
+    let fragments = dictionary(DICTIONARY);
+    let mut failures = Vec::new();
+
+    for solver in registry() {
+        let seeds = examples(solver.day());
+
+        for seed in 0..cases() {
+            let mut rng = Rng::new(seed);
+
+            let input = match seeds.is_empty() || rng.count(0..=9) == 0 {
+                true => random_text(&mut rng),
+                false => {
+                    let example = rng.choose(&seeds).clone();
+                    mutate(&mut rng, &fragments, &example)
+                }
+            };
+
+            if let Err(message) = run_case(solver.as_ref(), &input) {
+                failures.push(format!(
+                    "Day {} seed {} panicked with {:?} on {:?}",
+                    solver.day(),
+                    seed,
+                    message,
+                    input
+                ));
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn test_dictionary() {
+    let fragments = dictionary(DICTIONARY);
+
+    assert_eq!(
+        fragments.len(),
+        DICTIONARY.lines().filter(|line| line.contains('"')).count()
+    );
+    assert!(fragments.contains(&"\n\n".to_string()));
+    assert!(fragments.contains(&"\r\n".to_string()));
+    assert!(fragments.contains(&"é".to_string()));
+    assert!(fragments.contains(&"$ cd ..".to_string()));
+}
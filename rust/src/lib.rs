@@ -0,0 +1,55 @@
+//! Solutions to Advent of Code 2022, usable outside the `advent` binary.
+//!
+//! Each `day_N` module exposes its puzzle's domain types and the functions
+//! that parse and solve it, along with a `DayN` [`Solution`]. [`registry`]
+//! lists every solved day behind the type-erased [`Solver`] trait, which is
+//! what the command line runs.
+//!
+//! Parsers read from any [`BufRead`](std::io::BufRead), a line at a time, so an
+//! input never has to fit in memory. A string can be read with `as_bytes()`.
+//!
+//! Each day's `generate` module builds random inputs from a seeded
+//! [`rng::Rng`], and [`table`] lays out the reports some days print.
+//!
+//! ```no_run
+//! use advent::{day_7, registry, Part};
+//!
+//! let file_system = day_7::parse_input_to_file_system(
+//!     "$ cd /\n$ ls\n14848514 b.txt\n".as_bytes(),
+//! )?;
+//! let root = &file_system[&vec!["/".to_string()]];
+//! println!("/ holds {} bytes", root.total_directory_size(&file_system));
+//!
+//! for solver in registry() {
+//!     let parsed = solver.parse(solver.embedded_input())?;
+//!     println!("Day {}: {}", solver.day(), solver.solve(parsed.as_ref(), Part::One));
+//! }
+//! # Ok::<(), advent::ParseError>(())
+//! ```
+
+pub mod answer;
+pub mod day_1;
+pub mod day_10;
+pub mod day_11;
+pub mod day_2;
+pub mod day_3;
+pub mod day_4;
+pub mod day_5;
+pub mod day_6;
+pub mod day_7;
+pub mod day_8;
+pub mod day_9;
+pub mod error;
+#[cfg(test)]
+mod fuzz;
+pub mod loader;
+pub mod normalize;
+#[cfg(test)]
+mod property;
+pub mod rng;
+pub mod solution;
+pub mod table;
+
+pub use crate::answer::Answer;
+pub use crate::error::ParseError;
+pub use crate::solution::{registry, Part, Solution, Solver};
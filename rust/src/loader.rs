@@ -0,0 +1,177 @@
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, Read, Seek},
+    path::{Path, PathBuf},
+};
+
+use crate::solution::Solver;
+
+pub const INPUT_DIRECTORY: &str = "inputs";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputSource {
+    /// `inputs/day_N.txt` under the root if it exists, otherwise the input
+    /// compiled into the day's module
+    Default,
+    Path(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    pub fn from_arg(arg: &str) -> Self {
+        match arg {
+            "-" => InputSource::Stdin,
+            path => InputSource::Path(PathBuf::from(path)),
+        }
+    }
+}
+
+/// Where a day's input is looked for under `root`, the directory holding
+/// [`INPUT_DIRECTORY`]
+pub fn default_input_path(root: &Path, day: u8) -> PathBuf {
+    root.join(INPUT_DIRECTORY).join(format!("day_{}.txt", day))
+}
+
+/// An input ready to be read, without reading any of it yet
+pub enum Input {
+    Embedded(&'static str),
+    File(File),
+    /// Can only be read once
+    Stdin,
+    /// Kept in memory so it can be read again
+    Memory(String),
+}
+
+impl Input {
+    /// Reads the input from the start. A file is rewound, so it can be read
+    /// any number of times.
+    pub fn reader(&mut self) -> io::Result<Box<dyn BufRead + '_>> {
+        Ok(match self {
+            Input::Embedded(input) => Box::new(input.as_bytes()),
+            Input::File(file) => {
+                file.rewind()?;
+                Box::new(BufReader::new(&*file))
+            }
+            Input::Stdin => Box::new(io::stdin().lock()),
+            Input::Memory(input) => Box::new(input.as_bytes()),
+        })
+    }
+
+    pub fn read_to_string(&mut self) -> io::Result<String> {
+        let mut input = String::new();
+        self.reader()?.read_to_string(&mut input)?;
+
+        Ok(input)
+    }
+
+    /// An input that can be read more than once, which means keeping stdin
+    /// in memory
+    pub fn rereadable(mut self) -> io::Result<Input> {
+        match self {
+            Input::Stdin => Ok(Input::Memory(self.read_to_string()?)),
+            input => Ok(input),
+        }
+    }
+}
+
+/// Opens the input, so a missing file is reported before anything is parsed.
+/// The default input is looked for under `root`.
+pub fn open_input(root: &Path, solver: &dyn Solver, source: &InputSource) -> io::Result<Input> {
+    match source {
+        InputSource::Default => {
+            let path = default_input_path(root, solver.day());
+
+            if path.is_file() {
+                Ok(Input::File(File::open(path)?))
+            } else {
+                Ok(Input::Embedded(solver.embedded_input()))
+            }
+        }
+        InputSource::Path(path) => Ok(Input::File(File::open(path)?)),
+        InputSource::Stdin => Ok(Input::Stdin),
+    }
+}
+
+pub fn load_input(root: &Path, solver: &dyn Solver, source: &InputSource) -> io::Result<String> {
+    open_input(root, solver, source)?.read_to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::*;
+    use crate::day_1::Day1;
+    use crate::solution::Solution;
+
+    #[test]
+    fn test_input_source_from_arg() {
+        assert_eq!(InputSource::from_arg("-"), InputSource::Stdin);
+
+        assert_eq!(
+            InputSource::from_arg("inputs/day_1.txt"),
+            InputSource::Path(PathBuf::from("inputs/day_1.txt"))
+        );
+    }
+
+    #[test]
+    fn test_load_input_from_path() {
+        let path = std::env::temp_dir().join("advent_loader_test_day_1.txt");
+        fs::write(&path, "100\n200\n").unwrap();
+
+        let input = load_input(Path::new("."), &Day1, &InputSource::Path(path.clone())).unwrap();
+
+        assert_eq!(input, "100\n200\n");
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_file_can_be_read_again() {
+        let path = std::env::temp_dir().join("advent_loader_test_day_1_again.txt");
+        fs::write(&path, "100\n200\n").unwrap();
+
+        let mut input =
+            open_input(Path::new("."), &Day1, &InputSource::Path(path.clone())).unwrap();
+
+        for _ in 0..2 {
+            let elves = crate::day_1::input_to_elves(input.reader().unwrap()).unwrap();
+
+            assert_eq!(elves[0].calories, 300);
+        }
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_missing_path_is_an_error() {
+        let source = InputSource::Path(PathBuf::from("does/not/exist.txt"));
+
+        assert!(load_input(Path::new("."), &Day1, &source).is_err());
+    }
+
+    #[test]
+    fn test_default_input_under_root() {
+        let root = std::env::temp_dir().join(format!("advent-loader-{}", std::process::id()));
+        fs::create_dir_all(root.join(INPUT_DIRECTORY)).unwrap();
+
+        assert_eq!(
+            default_input_path(&root, 1),
+            root.join("inputs").join("day_1.txt")
+        );
+
+        // Without a file the input compiled into the day is used
+        let embedded = load_input(&root, &Day1, &InputSource::Default).unwrap();
+
+        assert_eq!(embedded, Day1::INPUT);
+
+        fs::write(default_input_path(&root, 1), "100\n").unwrap();
+
+        assert_eq!(
+            load_input(&root, &Day1, &InputSource::Default).unwrap(),
+            "100\n"
+        );
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -1,94 +1,447 @@
-mod day_1;
-mod day_10;
-mod day_11;
-mod day_2;
-mod day_3;
-mod day_4;
-mod day_5;
-mod day_6;
-mod day_7;
-mod day_8;
-mod day_9;
-
-use crate::day_1::{day_1_part_1, day_1_part_2, input::INPUT_DAY_1};
-use crate::day_10::{day_10_part_1, day_10_part_2, input::INPUT_DAY_10};
-use crate::day_11::{day_11_part_1, day_11_part_2, input::INPUT_DAY_11};
-use crate::day_2::{day_2_part_1, day_2_part_2, input::INPUT_DAY_2};
-use crate::day_3::{day_3_part_1, day_3_part_2, input::INPUT_DAY_3};
-use crate::day_4::{day_4_part_1, day_4_part_2, input::INPUT_DAY_4};
-use crate::day_5::{
-    day_5_part_1, day_5_part_2,
-    input::{INPUT_DAY_5_MOVES, INPUT_DAY_5_STACKS},
+mod cli;
+#[cfg(test)]
+mod examples;
+mod memory;
+mod output;
+mod parallel;
+mod parity;
+mod scaffold;
+mod stress;
+mod timing;
+mod verify;
+mod watch;
+
+use std::{
+    io::{self, BufRead, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::memory::PeakAllocator;
+use crate::output::{format_json, format_lines, Format, Record};
+use crate::parallel::map_parallel;
+use crate::parity::{compare_day, default_go_dir, format_comparisons, is_mismatch, GoProgram};
+use crate::stress::find_generator;
+use crate::timing::{bench_day, format_bench_results, format_timings, time, Timing};
+use crate::verify::{
+    check, default_answers_path, format_verifications, parse_answers, Outcome, Verification,
 };
-use crate::day_6::{day_6_part_1, day_6_part_2, input::INPUT_DAY_6};
-use crate::day_7::{day_7_part_1, day_7_part_2, input::INPUT_DAY_7};
-use crate::day_8::{day_8_part_1, day_8_part_2, input::INPUT_DAY_8};
-use crate::day_9::{day_9_part_1, day_9_part_2, input::INPUT_DAY_9};
+use advent::day_1::{input_to_elves, rebalance, stats, Day1};
+use advent::day_2::{decrypt, parse_input_part_1, strategy, Day2};
+use advent::loader::{default_input_path, load_input, open_input, Input, InputSource};
+use advent::{registry, ParseError, Part, Solver};
+
+use crate::cli::{Command, Invocation, Selection};
+
+/// Counts heap memory so `advent bench` can report each stage's peak
+#[global_allocator]
+static ALLOCATOR: PeakAllocator = PeakAllocator::new();
+
+/// A day picked out by the command line, along with which parts to solve and its input
+struct Job<'a> {
+    solver: &'a dyn Solver,
+    parts: Vec<Part>,
+    source: InputSource,
+    /// Where the default input is looked for
+    root: &'a Path,
+}
+
+fn select<'a>(
+    solvers: &'a [Box<dyn Solver>],
+    selection: Selection,
+    root: &'a Path,
+) -> Vec<Job<'a>> {
+    match selection {
+        Selection::All => solvers
+            .iter()
+            .map(|solver| Job {
+                solver: solver.as_ref(),
+                parts: Part::all().to_vec(),
+                source: InputSource::Default,
+                root,
+            })
+            .collect(),
+        Selection::Day { day, part, input } => {
+            let Some(solver) = solvers.iter().find(|solver| solver.day() == day) else {
+                eprintln!("Day {} has not been solved yet", day);
+                std::process::exit(1);
+            };
+
+            vec![Job {
+                solver: solver.as_ref(),
+                parts: part.map_or(Part::all().to_vec(), |part| vec![part]),
+                source: input,
+                root,
+            }]
+        }
+    }
+}
+
+fn read_input(job: &Job) -> Result<String, String> {
+    load_input(job.root, job.solver, &job.source)
+        .map_err(|err| format!("failed to read input: {}", err))
+}
+
+/// Solves the requested parts of one day, or explains why it could not be solved
+fn run_day(job: &Job) -> Result<Vec<Timing>, String> {
+    let solver = job.solver;
+    let mut input = open_input(job.root, solver, &job.source)
+        .map_err(|err| format!("failed to read input: {}", err))?;
+    let mut reader = input
+        .reader()
+        .map_err(|err| format!("failed to read input: {}", err))?;
+
+    let (parsed, parse) = time(|| solver.read(&mut reader));
+    let parsed = parsed.map_err(|err| format!("failed to parse input: {}", err))?;
+
+    let timings = job
+        .parts
+        .iter()
+        .map(|part| {
+            let (answer, solve) = time(|| solver.solve(parsed.as_ref(), *part));
+
+            Timing {
+                day: solver.day(),
+                part: *part,
+                answer,
+                parse,
+                solve,
+            }
+        })
+        .collect();
+
+    Ok(timings)
+}
+
+/// Reads a day's input with one of its own parsers, for the reports only that
+/// day has
+fn read_with<T>(
+    root: &Path,
+    solver: &dyn Solver,
+    source: &InputSource,
+    parse: impl FnOnce(&mut dyn BufRead) -> Result<T, ParseError>,
+) -> Result<T, String> {
+    let mut input =
+        open_input(root, solver, source).map_err(|err| format!("failed to read input: {}", err))?;
+    let mut reader = input
+        .reader()
+        .map_err(|err| format!("failed to read input: {}", err))?;
+
+    parse(&mut reader).map_err(|err| format!("failed to parse input: {}", err))
+}
+
+/// Writes a report to stdout
+fn print_report(report: &str) {
+    let mut out = BufWriter::new(io::stdout().lock());
+    let written = writeln!(out, "{}", report).and_then(|()| out.flush());
+
+    exit_if_unwritten(written, "output");
+}
+
+/// Piping into something like `head` closes stdout early, which is fine, so
+/// only other failures to write are errors
+fn exit_if_unwritten(written: io::Result<()>, what: &str) {
+    if let Err(err) = written {
+        if err.kind() != io::ErrorKind::BrokenPipe {
+            eprintln!("Failed to write {}: {}", what, err);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Flattens the results of each day into one record per part, in day order
+fn to_records(jobs: &[Job], results: Vec<Result<Vec<Timing>, String>>) -> Vec<Record> {
+    jobs.iter()
+        .zip(results)
+        .flat_map(|(job, result)| match result {
+            Ok(timings) => timings
+                .into_iter()
+                .map(|timing| Record {
+                    day: timing.day,
+                    part: timing.part,
+                    answer: Some(timing.answer),
+                    duration: Some(timing.solve),
+                    error: None,
+                })
+                .collect::<Vec<_>>(),
+            Err(err) => job
+                .parts
+                .iter()
+                .map(|part| Record {
+                    day: job.solver.day(),
+                    part: *part,
+                    answer: None,
+                    duration: None,
+                    error: Some(err.clone()),
+                })
+                .collect(),
+        })
+        .collect()
+}
 
 fn main() {
-    let d1p1 = day_1_part_1(INPUT_DAY_1);
-    let d1p2 = day_1_part_2(INPUT_DAY_1);
+    let Invocation { root, command } = match cli::parse_invocation(std::env::args().skip(1)) {
+        Ok(invocation) => invocation,
+        Err(err) => {
+            eprintln!("{}\n\n{}", err, cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    // Inputs, answers, the Go translations and the day sources are all found from here
+    let root = root.unwrap_or_else(|| PathBuf::from("."));
+    let root = root.as_path();
+
+    let solvers = registry();
+
+    match command {
+        Command::Run {
+            selection,
+            format,
+            jobs: threads,
+        } => {
+            let jobs = select(&solvers, selection, root);
+
+            // Keep going after a failure so one bad input doesn't hide the other days
+            let results = map_parallel(&jobs, threads, run_day);
+
+            let all_solved = results.iter().all(|result| result.is_ok());
+
+            match format {
+                Format::Text => {
+                    let mut timings = Vec::new();
+
+                    for (job, result) in jobs.iter().zip(results) {
+                        match result {
+                            Ok(day_timings) => timings.extend(day_timings),
+                            Err(err) => eprintln!("Day {}: {}", job.solver.day(), err),
+                        }
+                    }
+
+                    print_report(&format_timings(&timings));
+                }
+                Format::Json => print_report(&format_json(&to_records(&jobs, results))),
+                Format::Lines => print_report(&format_lines(&to_records(&jobs, results))),
+            }
+
+            if !all_solved {
+                std::process::exit(1);
+            }
+        }
+        Command::Bench { selection, runs } => {
+            let mut results = Vec::new();
+            let mut all_solved = true;
+
+            for job in select(&solvers, selection, root) {
+                // Every run reads the input again, so stdin is kept in memory
+                let input = open_input(root, job.solver, &job.source).and_then(Input::rereadable);
+
+                let mut input = match input {
+                    Ok(input) => input,
+                    Err(err) => {
+                        eprintln!("Day {}: failed to read input: {}", job.solver.day(), err);
+                        all_solved = false;
+                        continue;
+                    }
+                };
+
+                match bench_day(job.solver, &mut input, &job.parts, runs, &ALLOCATOR) {
+                    Ok(day_results) => results.extend(day_results),
+                    Err(err) => {
+                        eprintln!("Day {}: {}", job.solver.day(), err);
+                        all_solved = false;
+                    }
+                }
+            }
+
+            print_report(&format_bench_results(&results));
+
+            if !all_solved {
+                std::process::exit(1);
+            }
+        }
+        Command::Watch { day, input } => {
+            // The watcher runs cargo from the crate's directory, so paths given
+            // relative to here have to be made absolute first
+            let input = match input {
+                InputSource::Default => {
+                    Some(default_input_path(root, day)).filter(|path| path.is_file())
+                }
+                InputSource::Path(path) => Some(path),
+                InputSource::Stdin => unreachable!("Rejected when parsing arguments"),
+            };
+
+            let input = input.map(|path| {
+                std::env::current_dir()
+                    .map(|dir| dir.join(&path))
+                    .unwrap_or(path)
+            });
+
+            watch::watch(root, day, input.as_deref())
+        }
+        Command::Verify { answers } => {
+            let answers = answers.unwrap_or_else(|| default_answers_path(root));
 
-    dbg!("{}", d1p1);
-    dbg!("{}", d1p2);
+            let answers = match std::fs::read_to_string(&answers) {
+                Ok(text) => parse_answers(&text),
+                Err(err) => {
+                    eprintln!("Failed to read {}: {}", answers.display(), err);
+                    std::process::exit(1);
+                }
+            };
 
-    let d2p1 = day_2_part_1(INPUT_DAY_2);
-    let d2p2 = day_2_part_2(INPUT_DAY_2);
+            let answers = answers.unwrap_or_else(|err| {
+                eprintln!("{}", err);
+                std::process::exit(1);
+            });
 
-    dbg!("{}", d2p1);
-    dbg!("{}", d2p2);
+            let mut verifications = Vec::new();
+            let mut all_solved = true;
 
-    let d3p1 = day_3_part_1(INPUT_DAY_3);
-    let d3p2 = day_3_part_2(INPUT_DAY_3);
+            for job in select(&solvers, Selection::All, root) {
+                let timings = match run_day(&job) {
+                    Ok(timings) => timings,
+                    Err(err) => {
+                        eprintln!("Day {}: {}", job.solver.day(), err);
+                        all_solved = false;
+                        continue;
+                    }
+                };
 
-    dbg!("{}", d3p1);
-    dbg!("{}", d3p2);
+                verifications.extend(timings.into_iter().map(|timing| Verification {
+                    outcome: check(&answers, timing.day, timing.part, &timing.answer),
+                    day: timing.day,
+                    part: timing.part,
+                    answer: timing.answer,
+                }));
+            }
 
-    let d4p1 = day_4_part_1(INPUT_DAY_4);
-    let d4p2 = day_4_part_2(INPUT_DAY_4);
+            print_report(&format_verifications(&verifications));
 
-    dbg!("{}", d4p1);
-    dbg!("{}", d4p2);
+            let any_failed = verifications
+                .iter()
+                .any(|verification| matches!(verification.outcome, Outcome::Fail { .. }));
 
-    let d5p1 = day_5_part_1(INPUT_DAY_5_STACKS, INPUT_DAY_5_MOVES);
-    let d5p2 = day_5_part_2(INPUT_DAY_5_STACKS, INPUT_DAY_5_MOVES);
+            if any_failed || !all_solved {
+                std::process::exit(1);
+            }
+        }
+        Command::Parity { selection, go } => {
+            let go_dir = go.unwrap_or_else(|| default_go_dir(root));
 
-    dbg!("{}", d5p1);
-    dbg!("{}", d5p2);
+            let go = GoProgram::build(&go_dir).unwrap_or_else(|err| {
+                eprintln!("{}", err);
+                std::process::exit(1);
+            });
 
-    let d6p1 = day_6_part_1(INPUT_DAY_6);
-    let d6p2 = day_6_part_2(INPUT_DAY_6);
+            let mut comparisons = Vec::new();
+            let mut all_solved = true;
 
-    dbg!("{}", d6p1);
-    dbg!("{}", d6p2);
+            for job in select(&solvers, selection, root) {
+                let compared = read_input(&job)
+                    .and_then(|input| compare_day(&go, job.solver, &input, &job.parts));
 
-    let d7p1 = day_7_part_1(INPUT_DAY_7);
-    let d7p2 = day_7_part_2(INPUT_DAY_7);
+                match compared {
+                    Ok(day_comparisons) => comparisons.extend(day_comparisons),
+                    Err(err) => {
+                        eprintln!("Day {}: {}", job.solver.day(), err);
+                        all_solved = false;
+                    }
+                }
+            }
 
-    dbg!("{}", d7p1);
-    dbg!("{}", d7p2);
+            print_report(&format_comparisons(&comparisons));
 
-    let d8p1 = day_8_part_1(INPUT_DAY_8);
-    let d8p2 = day_8_part_2(INPUT_DAY_8);
+            // Exiting skips destructors, so the built program is removed first
+            drop(go);
 
-    dbg!("{}", d8p1);
-    dbg!("{}", d8p2);
+            if comparisons.iter().any(is_mismatch) || !all_solved {
+                std::process::exit(1);
+            }
+        }
+        Command::Gen { day, size, seed } => {
+            let Some(generator) = find_generator(day) else {
+                eprintln!("Day {} has no input generator", day);
+                std::process::exit(1);
+            };
 
-    let d9p1 = day_9_part_1(INPUT_DAY_9);
-    let d9p2 = day_9_part_2(INPUT_DAY_9);
+            let mut out = BufWriter::new(io::stdout().lock());
+            let written = generator
+                .write(size, seed, &mut out)
+                .and_then(|()| out.flush());
 
-    dbg!("{}", d9p1);
-    dbg!("{}", d9p2);
+            exit_if_unwritten(written, "input");
+        }
+        Command::Day1Stats { input, buckets } => {
+            match read_with(root, &Day1, &input, |reader| input_to_elves(reader)) {
+                Ok(elves) => print_report(&stats::format_report(&elves, buckets)),
+                Err(err) => {
+                    eprintln!("Day 1: {}", err);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Command::Day1Rebalance { input, elves } => {
+            match read_with(root, &Day1, &input, |reader| input_to_elves(reader)) {
+                Ok(carrying) => {
+                    let plan = rebalance::rebalance(&carrying, elves.unwrap_or(carrying.len()));
 
-    let d10p1 = day_10_part_1(INPUT_DAY_10);
-    let d10p2 = day_10_part_2(INPUT_DAY_10);
+                    print_report(&rebalance::format_plan(&plan));
+                }
+                Err(err) => {
+                    eprintln!("Day 1: {}", err);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Command::Day2Tournament {
+            input,
+            rounds,
+            seed,
+        } => match read_with(root, &Day2, &input, |reader| parse_input_part_1(reader)) {
+            Ok(guide) => {
+                let moves = guide.iter().map(|(_, mine)| *mine).collect::<Vec<_>>();
+                let entrants = strategy::default_entrants(&moves, seed);
 
-    dbg!("{}", d10p1);
-    dbg!("{}", d10p2);
+                print_report(&strategy::format_tournament(&strategy::round_robin(
+                    &entrants, rounds,
+                )));
+            }
+            Err(err) => {
+                eprintln!("Day 2: {}", err);
+                std::process::exit(1);
+            }
+        },
+        Command::Day2Decrypt { input } => {
+            match read_with(root, &Day2, &input, |reader| decrypt::search(reader)) {
+                Ok(scores) => print_report(&decrypt::format_search(&scores)),
+                Err(err) => {
+                    eprintln!("Day 2: {}", err);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Command::New(day) => match scaffold::create_day(root, day) {
+            Ok(paths) => {
+                let written = paths
+                    .iter()
+                    .map(|path| format!("Wrote {}", path.display()))
+                    .collect::<Vec<_>>();
 
-    let d11p1 = day_11_part_1(INPUT_DAY_11);
-    let d11p2 = day_11_part_2(INPUT_DAY_11);
+                print_report(&written.join("\n"));
+            }
+            Err(err) => {
+                eprintln!("{}", err);
+                std::process::exit(1);
+            }
+        },
+        Command::List => {
+            let days = solvers
+                .iter()
+                .map(|solver| format!("Day {}", solver.day()))
+                .collect::<Vec<_>>();
 
-    dbg!("{}", d11p1);
-    dbg!("{}", d11p2);
+            print_report(&days.join("\n"));
+        }
+        Command::Help => print_report(cli::USAGE),
+    }
 }
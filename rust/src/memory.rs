@@ -0,0 +1,143 @@
+//! Counts the heap memory in use so `advent bench` can report how much each
+//! day needs at its peak. The binary installs a [`PeakAllocator`] as its global
+//! allocator; one that isn't installed never sees an allocation and reports 0.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// The system allocator, counting the bytes in use and the most that have
+/// been in use at once
+pub struct PeakAllocator {
+    current: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+impl PeakAllocator {
+    pub const fn new() -> Self {
+        Self {
+            current: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+        }
+    }
+
+    /// Bytes allocated and not yet freed
+    pub fn current(&self) -> usize {
+        self.current.load(Ordering::Relaxed)
+    }
+
+    /// The most bytes in use at once since the last [`PeakAllocator::reset_peak`]
+    pub fn peak(&self) -> usize {
+        self.peak.load(Ordering::Relaxed)
+    }
+
+    /// Starts measuring a new peak from what is in use now
+    pub fn reset_peak(&self) {
+        self.peak.store(self.current(), Ordering::Relaxed);
+    }
+
+    /// The most bytes `f` had in use at once on top of what was already in
+    /// use, along with its result. Allocations on other threads count too.
+    pub fn measure<T>(&self, f: impl FnOnce() -> T) -> (T, usize) {
+        let start = self.current();
+        self.reset_peak();
+
+        let result = f();
+
+        (result, self.peak().saturating_sub(start))
+    }
+
+    fn grow(&self, size: usize) {
+        let current = self.current.fetch_add(size, Ordering::Relaxed) + size;
+        self.peak.fetch_max(current, Ordering::Relaxed);
+    }
+
+    fn shrink(&self, size: usize) {
+        self.current.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+impl Default for PeakAllocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Every call goes straight to the system allocator, so its guarantees hold
+unsafe impl GlobalAlloc for PeakAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+
+        if !ptr.is_null() {
+            self.grow(layout.size());
+        }
+
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+
+        if !ptr.is_null() {
+            self.grow(layout.size());
+        }
+
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+
+        self.shrink(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+
+        if !new_ptr.is_null() {
+            if new_size > layout.size() {
+                self.grow(new_size - layout.size());
+            } else {
+                self.shrink(layout.size() - new_size);
+            }
+        }
+
+        new_ptr
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_peak_allocator() {
+        let allocator = PeakAllocator::new();
+        let layout = Layout::from_size_align(1000, 8).unwrap();
+
+        let ((), peak) = allocator.measure(|| unsafe {
+            let first = allocator.alloc(layout);
+            let second = allocator.alloc_zeroed(layout);
+            allocator.dealloc(first, layout);
+
+            let second = allocator.realloc(second, layout, 3000);
+            allocator.dealloc(second, Layout::from_size_align(3000, 8).unwrap());
+        });
+
+        assert_eq!(peak, 3000);
+        assert_eq!(allocator.current(), 0);
+
+        // Only what's in use on top of the start counts towards a peak
+        let kept = unsafe { allocator.alloc(layout) };
+        let ((), peak) = allocator.measure(|| unsafe {
+            let extra = allocator.alloc(layout);
+            allocator.dealloc(extra, layout);
+        });
+
+        assert_eq!(peak, 1000);
+        assert_eq!(allocator.current(), 1000);
+
+        unsafe { allocator.dealloc(kept, layout) };
+    }
+}
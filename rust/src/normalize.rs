@@ -0,0 +1,324 @@
+//! Input can come from a file saved on any platform, from a pasted download or
+//! from an indented string literal in a test. Every line based parser reads it
+//! through [`LineReader`] so they all agree on what a line is: a byte order mark is
+//! dropped, CRLF endings are accepted, trailing whitespace is ignored and so
+//! are blank lines before and after the puzzle. Blank lines in the middle are
+//! kept, since some puzzles use them to separate groups.
+//!
+//! Input is read one line at a time from any [`BufRead`], so a parser never
+//! needs the whole of it in memory. Lines keep their index in the original
+//! input, so a [`FragmentError`](crate::error::FragmentError) found in one still
+//! reports the line and column an editor shows.
+
+use std::io::BufRead;
+
+use crate::error::ParseError;
+
+/// One line of input after normalizing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line<'a> {
+    /// Zero based, as produced by `lines().enumerate()`
+    pub index: usize,
+    /// The whole line without its line ending, for reporting columns
+    pub raw: &'a str,
+    /// What the parser should read, a slice of `raw`
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    pub fn is_blank(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// Removes `width` columns of indentation, or gives back `None` if the
+    /// line isn't indented that far. Blank lines are always indented enough.
+    pub fn dedent(self, width: usize) -> Option<Line<'a>> {
+        if self.is_blank() {
+            return Some(self);
+        }
+
+        let indent = self.text.len() - self.text.trim_start_matches(' ').len();
+
+        (indent >= width).then(|| Line {
+            text: &self.text[width..],
+            ..self
+        })
+    }
+}
+
+/// Hands out the lines of an input one at a time, reusing a single buffer
+pub struct LineReader<R> {
+    day: u8,
+    reader: R,
+    buffer: String,
+    /// Lines read from `reader` so far
+    read: usize,
+    /// Whether a line with something on it has been read yet
+    started: bool,
+    /// Blank lines read since the last line with something on it. They are
+    /// only handed out once another such line shows they aren't trailing.
+    blank: usize,
+    /// Whether `buffer` holds a line that is waiting for the blank lines
+    /// before it to be handed out
+    held: bool,
+}
+
+impl<R: BufRead> LineReader<R> {
+    /// `day` is only used to report errors reading the input
+    pub fn new(day: u8, reader: R) -> Self {
+        Self {
+            day,
+            reader,
+            buffer: String::new(),
+            read: 0,
+            started: false,
+            blank: 0,
+            held: false,
+        }
+    }
+
+    /// The next line with surrounding whitespace removed, for puzzles where
+    /// indentation means nothing
+    pub fn next_line(&mut self) -> Result<Option<Line<'_>>, ParseError> {
+        self.next_with(str::trim)
+    }
+
+    /// The next line with its indentation left alone, for puzzles laid out in
+    /// columns. A test literal indents the whole layout, so the caller has to
+    /// decide how much of that indentation belongs to the puzzle and
+    /// [`Line::dedent`] the rest.
+    pub fn next_layout_line(&mut self) -> Result<Option<Line<'_>>, ParseError> {
+        self.next_with(str::trim_end)
+    }
+
+    /// The next line with something on it, skipping any blank lines, trimmed
+    /// like [`LineReader::next_line`]
+    pub fn next_content_line(&mut self) -> Result<Option<Line<'_>>, ParseError> {
+        if !self.held && !self.read_content()? {
+            return Ok(None);
+        }
+
+        self.blank = 0;
+
+        self.next_line()
+    }
+
+    /// Whether there are no more lines with something on them, reading ahead
+    /// to find out
+    pub fn at_end(&mut self) -> Result<bool, ParseError> {
+        Ok(!self.held && !self.read_content()?)
+    }
+
+    /// Error for the input running out part way through something, reported
+    /// on the line after the last one
+    pub fn end_of_input(&self, message: impl Into<String>) -> ParseError {
+        ParseError::end_of_input(self.day, self.read, message)
+    }
+
+    fn next_with(&mut self, trim: fn(&str) -> &str) -> Result<Option<Line<'_>>, ParseError> {
+        if !self.held && !self.read_content()? {
+            return Ok(None);
+        }
+
+        if self.blank > 0 {
+            let index = self.read - 1 - self.blank;
+            self.blank -= 1;
+
+            return Ok(Some(Line {
+                index,
+                raw: "",
+                text: "",
+            }));
+        }
+
+        self.held = false;
+
+        let raw = self.buffer.strip_suffix('\n').unwrap_or(&self.buffer);
+        let raw = raw.strip_suffix('\r').unwrap_or(raw);
+
+        Ok(Some(Line {
+            index: self.read - 1,
+            raw,
+            text: trim(raw),
+        }))
+    }
+
+    /// Reads up to the next line with something on it, counting the blank
+    /// lines on the way. Returns false once the input runs out.
+    fn read_content(&mut self) -> Result<bool, ParseError> {
+        loop {
+            self.buffer.clear();
+
+            let bytes = self.reader.read_line(&mut self.buffer).map_err(|err| {
+                ParseError::new(
+                    self.day,
+                    self.read + 1,
+                    1,
+                    "",
+                    format!("Failed to read input: {}", err),
+                )
+            })?;
+
+            if bytes == 0 {
+                // Any blank lines still counted were trailing
+                return Ok(false);
+            }
+
+            if self.read == 0 && self.buffer.starts_with('\u{feff}') {
+                self.buffer.replace_range(..'\u{feff}'.len_utf8(), "");
+            }
+
+            self.read += 1;
+
+            if !self.buffer.trim().is_empty() {
+                self.started = true;
+                self.held = true;
+
+                return Ok(true);
+            }
+
+            if self.started {
+                self.blank += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn texts(input: &str) -> Vec<(usize, String)> {
+        let mut lines = LineReader::new(1, input.as_bytes());
+        let mut texts = Vec::new();
+
+        while let Some(line) = lines.next_line().unwrap() {
+            texts.push((line.index, line.text.to_string()));
+        }
+
+        texts
+    }
+
+    #[test]
+    fn test_next_line() {
+        let input = "\n\n  1000\r\n2000  \r\n\r\n \n  3000\n   \n";
+
+        assert_eq!(
+            texts(input),
+            vec![
+                (2, "1000".to_string()),
+                (3, "2000".to_string()),
+                (4, "".to_string()),
+                (5, "".to_string()),
+                (6, "3000".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_byte_order_mark() {
+        let input = "\u{feff}mjqjpqmgbljsphdztnvjfqwrcgsmlb\r\n";
+        let mut lines = LineReader::new(6, input.as_bytes());
+        let line = lines.next_line().unwrap().unwrap();
+
+        assert_eq!(line.raw, "mjqjpqmgbljsphdztnvjfqwrcgsmlb");
+        assert_eq!(line.text, "mjqjpqmgbljsphdztnvjfqwrcgsmlb");
+        assert_eq!(lines.next_line().unwrap(), None);
+    }
+
+    #[test]
+    fn test_next_layout_line() {
+        let input = "\n        [D]    \r\n    [N] [C]\n     1   2 \n    ";
+        let mut lines = LineReader::new(5, input.as_bytes());
+        let mut texts = Vec::new();
+
+        while let Some(line) = lines.next_layout_line().unwrap() {
+            texts.push((line.index, line.text.to_string()));
+        }
+
+        assert_eq!(
+            texts,
+            vec![
+                (1, "        [D]".to_string()),
+                (2, "    [N] [C]".to_string()),
+                (3, "     1   2".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dedent() {
+        let line = |raw| Line {
+            index: 0,
+            raw,
+            text: raw,
+        };
+
+        assert_eq!(
+            line("    [N] [C]").dedent(4).map(|line| line.text),
+            Some("[N] [C]")
+        );
+        assert_eq!(
+            line("        [M]").dedent(4).map(|line| line.text),
+            Some("    [M]")
+        );
+        assert_eq!(line("").dedent(4), Some(line("")));
+        assert_eq!(line("  oops").dedent(4), None);
+    }
+
+    #[test]
+    fn test_text_is_a_slice_of_the_line() {
+        let mut lines = LineReader::new(10, "  addx five\r\n".as_bytes());
+        let line = lines.next_line().unwrap().unwrap();
+
+        assert_eq!(crate::error::column_of(line.raw, line.text), 3);
+    }
+
+    #[test]
+    fn test_next_content_line() {
+        let mut lines = LineReader::new(11, "Monkey 0:\n\n  Starting items: 79\n\n".as_bytes());
+
+        assert!(!lines.at_end().unwrap());
+        assert_eq!(
+            lines.next_content_line().unwrap().unwrap().text,
+            "Monkey 0:"
+        );
+
+        let line = lines.next_content_line().unwrap().unwrap();
+
+        assert_eq!((line.index, line.text), (2, "Starting items: 79"));
+        assert!(lines.at_end().unwrap());
+        assert_eq!(lines.next_content_line().unwrap(), None);
+    }
+
+    #[test]
+    fn test_end_of_input() {
+        let mut lines = LineReader::new(11, "Monkey 0:\n\n\n".as_bytes());
+
+        while lines.next_line().unwrap().is_some() {}
+
+        assert_eq!(
+            lines.end_of_input("Expected more"),
+            ParseError::new(11, 4, 1, "", "Expected more")
+        );
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        let mut lines = LineReader::new(1, &b"1000\n\xff\xfe\n"[..]);
+
+        assert!(lines.next_line().unwrap().is_some());
+
+        let err = lines.next_line().unwrap_err();
+
+        assert_eq!(err.line, 2);
+        assert!(err.message.starts_with("Failed to read input"));
+    }
+
+    #[test]
+    fn test_empty() {
+        assert!(texts("").is_empty());
+        assert!(texts("\n  \r\n\n").is_empty());
+        assert!(texts("\u{feff}\n").is_empty());
+    }
+}
@@ -0,0 +1,293 @@
+use std::time::Duration;
+
+use advent::answer::Answer;
+use advent::Part;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// A table for people to read
+    Text,
+    /// An array of records for other tools to read
+    Json,
+    /// Each part's answer or error as plain lines, for `advent watch`
+    Lines,
+}
+
+impl Format {
+    pub fn from_arg(arg: &str) -> Option<Self> {
+        match arg {
+            "text" => Some(Format::Text),
+            "json" => Some(Format::Json),
+            "lines" => Some(Format::Lines),
+            _ => None,
+        }
+    }
+}
+
+/// The result of one part of one day. A day that could not be solved has an
+/// error on each of its parts instead of an answer.
+///
+/// In JSON the answer is written as text, with its `kind` saying how to read
+/// it back. A grid is its rows joined by newlines, and a part that isn't
+/// implemented has no answer text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub day: u8,
+    pub part: Part,
+    pub answer: Option<Answer>,
+    /// How long solving the part took, not including parsing
+    pub duration: Option<Duration>,
+    pub error: Option<String>,
+}
+
+pub fn format_json(records: &[Record]) -> String {
+    if records.is_empty() {
+        return "[]".to_string();
+    }
+
+    let lines = records
+        .iter()
+        .map(|record| {
+            format!(
+                "  {{\"day\": {}, \"part\": {}, \"answer\": {}, \"kind\": {}, \"duration_ns\": {}, \"error\": {}}}",
+                record.day,
+                record.part,
+                json_or_null(answer_text(record.answer.as_ref()).as_deref().map(json_string)),
+                json_or_null(record.answer.as_ref().map(|answer| json_string(answer.kind()))),
+                json_or_null(record.duration.map(|duration| duration.as_nanos().to_string())),
+                json_or_null(record.error.as_deref().map(json_string)),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    format!("[\n{}\n]", lines.join(",\n"))
+}
+
+fn answer_text(answer: Option<&Answer>) -> Option<String> {
+    match answer? {
+        Answer::NotImplemented => None,
+        answer => Some(answer.to_string()),
+    }
+}
+
+fn json_or_null(value: Option<String>) -> String {
+    value.unwrap_or_else(|| "null".to_string())
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped.push('"');
+    escaped
+}
+
+/// One record after another, each a line `<day> <part> answer|error <lines>`
+/// followed by that many lines of its answer or error. Nothing is escaped, so
+/// it can be read back without a parser, which is how `advent watch` gets the
+/// answers of the build it runs. A part that isn't implemented has no lines.
+pub fn format_lines(records: &[Record]) -> String {
+    records
+        .iter()
+        .map(|record| {
+            let (kind, text) = match (&record.answer, &record.error) {
+                (Some(answer), _) => ("answer", answer_text(Some(answer))),
+                (None, error) => ("error", error.clone()),
+            };
+
+            let lines = text
+                .as_deref()
+                .map(|text| text.lines().collect::<Vec<_>>())
+                .unwrap_or_default();
+
+            let mut record = format!("{} {} {} {}", record.day, record.part, kind, lines.len());
+
+            for line in lines {
+                record.push('\n');
+                record.push_str(line);
+            }
+
+            record
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Reads back what [`format_lines`] writes. Answers come back as text, or as a
+/// grid if they take more than one line, which is all that's needed to show
+/// them again; durations aren't written, so they come back empty.
+pub fn parse_lines(text: &str) -> Result<Vec<Record>, String> {
+    let mut lines = text.lines();
+    let mut records = Vec::new();
+
+    while let Some(header) = lines.next() {
+        let fields = header.split(' ').collect::<Vec<_>>();
+        let invalid = || format!("Invalid record {:?}", header);
+
+        let [day, part, kind, count] = fields[..] else {
+            return Err(invalid());
+        };
+
+        let day = day.parse().map_err(|_| invalid())?;
+        let part = match part {
+            "1" => Part::One,
+            "2" => Part::Two,
+            _ => return Err(invalid()),
+        };
+        let count = count.parse::<usize>().map_err(|_| invalid())?;
+
+        let text = lines
+            .by_ref()
+            .take(count)
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+
+        if text.len() < count {
+            return Err(format!("Record {:?} is missing lines", header));
+        }
+
+        let (answer, error) = match (kind, text.len()) {
+            ("answer", 0) => (Some(Answer::NotImplemented), None),
+            ("answer", 1) => (Some(Answer::Text(text[0].clone())), None),
+            ("answer", _) => (Some(Answer::Grid(text)), None),
+            ("error", _) => (None, Some(text.join("\n"))),
+            _ => return Err(invalid()),
+        };
+
+        records.push(Record {
+            day,
+            part,
+            answer,
+            duration: None,
+            error,
+        });
+    }
+
+    Ok(records)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!(json_string("VCTFTJQCG"), "\"VCTFTJQCG\"");
+        assert_eq!(
+            json_string("line \"1\"\n\\ \u{1}"),
+            "\"line \\\"1\\\"\\n\\\\ \\u0001\""
+        );
+    }
+
+    #[test]
+    fn test_format_json() {
+        let records = vec![
+            Record {
+                day: 1,
+                part: Part::One,
+                answer: Some(Answer::from(72602)),
+                duration: Some(Duration::from_nanos(1500)),
+                error: None,
+            },
+            Record {
+                day: 9,
+                part: Part::Two,
+                answer: None,
+                duration: None,
+                error: Some("Invalid direction (`Q`)".to_string()),
+            },
+        ];
+
+        assert_eq!(
+            format_json(&records),
+            [
+                "[",
+                "  {\"day\": 1, \"part\": 1, \"answer\": \"72602\", \"kind\": \"integer\", \"duration_ns\": 1500, \"error\": null},",
+                "  {\"day\": 9, \"part\": 2, \"answer\": null, \"kind\": null, \"duration_ns\": null, \"error\": \"Invalid direction (`Q`)\"}",
+                "]",
+            ]
+            .join("\n")
+        );
+
+        assert_eq!(format_json(&[]), "[]");
+    }
+
+    #[test]
+    fn test_format_lines() {
+        let records = vec![
+            Record {
+                day: 10,
+                part: Part::Two,
+                answer: Some(Answer::Grid(vec![
+                    "###..#".to_string(),
+                    "#.....".to_string(),
+                ])),
+                duration: Some(Duration::from_nanos(123456789)),
+                error: None,
+            },
+            Record {
+                day: 12,
+                part: Part::One,
+                answer: Some(Answer::NotImplemented),
+                duration: Some(Duration::from_nanos(2)),
+                error: None,
+            },
+            Record {
+                day: 9,
+                part: Part::One,
+                answer: None,
+                duration: None,
+                error: Some("Invalid direction (`Q`)".to_string()),
+            },
+        ];
+
+        let text = format_lines(&records);
+
+        assert_eq!(
+            text,
+            [
+                "10 2 answer 2",
+                "###..#",
+                "#.....",
+                "12 1 answer 0",
+                "9 1 error 1",
+                "Invalid direction (`Q`)",
+            ]
+            .join("\n")
+        );
+
+        let read = parse_lines(&text).unwrap();
+
+        assert_eq!(read[0].answer, records[0].answer);
+        assert_eq!(read[1].answer, Some(Answer::NotImplemented));
+        assert_eq!(read[2].error, records[2].error);
+        assert_eq!(read[2].duration, None);
+
+        assert_eq!(
+            parse_lines("1 1 answer 1\n72602").unwrap()[0].answer,
+            Some(Answer::from("72602"))
+        );
+        assert_eq!(parse_lines(""), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_parse_invalid_lines() {
+        assert!(parse_lines("1 3 answer 1\n5").is_err());
+        assert!(parse_lines("one 1 answer 1\n5").is_err());
+        assert!(parse_lines("1 1 answer 2\n5").is_err());
+        assert!(parse_lines("1 1 result 1\n5").is_err());
+        assert!(parse_lines("1 1 answer").is_err());
+    }
+}
@@ -0,0 +1,86 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+};
+
+/// Calls `f` on every item using up to `jobs` threads. Each thread takes the
+/// next item as soon as it is free, so one slow item doesn't hold up the rest,
+/// and results come back in the same order as `items` however they finished.
+pub fn map_parallel<T, R, F>(items: &[T], jobs: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let threads = jobs.min(items.len());
+
+    if threads <= 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let next_item = AtomicUsize::new(0);
+    let results = items.iter().map(|_| Mutex::new(None)).collect::<Vec<_>>();
+
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| loop {
+                let idx = next_item.fetch_add(1, Ordering::Relaxed);
+
+                let Some(item) = items.get(idx) else {
+                    break;
+                };
+
+                let result = f(item);
+                *results[idx].lock().expect("Result lock poisoned") = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_iter()
+        .map(|result| {
+            result
+                .into_inner()
+                .expect("Result lock poisoned")
+                .expect("Every item is handled before the threads finish")
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_map_parallel_keeps_order() {
+        let items = (0..20).collect::<Vec<u64>>();
+
+        // Earlier items finish last, so results arrive out of order
+        let results = map_parallel(&items, 4, |item| {
+            thread::sleep(Duration::from_millis(20 - item));
+            item * 2
+        });
+
+        assert_eq!(results, (0..20).map(|item| item * 2).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_map_parallel_uses_threads() {
+        let items = vec![(); 4];
+
+        let thread_ids = map_parallel(&items, 4, |_| {
+            thread::sleep(Duration::from_millis(50));
+            thread::current().id()
+        });
+
+        assert!(thread_ids.iter().any(|id| *id != thread_ids[0]));
+
+        let thread_ids = map_parallel(&items, 1, |_| thread::current().id());
+
+        assert!(thread_ids.iter().all(|id| *id == thread::current().id()));
+    }
+}
@@ -0,0 +1,316 @@
+//! Runs the Go translations in `go/` on the same input as the Rust solvers and
+//! compares what they print, so the two implementations can't quietly drift
+//! apart. The Go program is run as `advent-go <DAY> <PART> <INPUT PATH>` and
+//! prints its answer the way [`Answer`] displays, or `not implemented` for a
+//! part that hasn't been ported.
+
+use std::{
+    env, fmt, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use advent::answer::{format_grids, Answer};
+use advent::table::{format_table, Align};
+use advent::{Part, Solver};
+
+/// Where the Go translations live, next to the crate at `root`
+pub fn default_go_dir(root: &Path) -> PathBuf {
+    root.join("..").join("go")
+}
+
+/// The Go program, built once so every comparison doesn't recompile it
+pub struct GoProgram {
+    binary: PathBuf,
+}
+
+impl GoProgram {
+    pub fn build(go_dir: &Path) -> Result<Self, String> {
+        let binary = env::temp_dir().join(format!("advent-go-{}", std::process::id()));
+
+        let output = Command::new("go")
+            .arg("build")
+            .arg("-o")
+            .arg(&binary)
+            .arg(".")
+            .current_dir(go_dir)
+            .output()
+            .map_err(|err| format!("failed to run go: {}", err))?;
+
+        if !output.status.success() {
+            return Err(format!(
+                "failed to build {}: {}",
+                go_dir.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        Ok(Self { binary })
+    }
+
+    /// What the Go program prints for one part, or what it printed on stderr
+    /// if it failed
+    pub fn solve(&self, day: u8, part: Part, input: &Path) -> Result<String, String> {
+        let output = Command::new(&self.binary)
+            .arg(day.to_string())
+            .arg(part.to_string())
+            .arg(input)
+            .output()
+            .map_err(|err| format!("failed to run the Go program: {}", err))?;
+
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .trim_end_matches(['\n', '\r'])
+            .to_string())
+    }
+}
+
+impl Drop for GoProgram {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.binary);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Agree,
+    Differ {
+        go: String,
+    },
+    /// The Go program has no translation of this part yet
+    NotPorted,
+    GoFailed {
+        error: String,
+    },
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Agree => write!(f, "agree"),
+            Outcome::Differ { .. } => write!(f, "DIFFER"),
+            Outcome::NotPorted => write!(f, "not ported"),
+            Outcome::GoFailed { .. } => write!(f, "GO FAILED"),
+        }
+    }
+}
+
+pub fn compare(rust: &Answer, go: Result<String, String>) -> Outcome {
+    match go {
+        Err(error) => Outcome::GoFailed { error },
+        Ok(go) if go == Answer::NotImplemented.to_string() => Outcome::NotPorted,
+        Ok(go) if go == rust.to_string() => Outcome::Agree,
+        Ok(go) => Outcome::Differ { go },
+    }
+}
+
+/// One row of the table printed by `advent parity`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub day: u8,
+    pub part: Part,
+    pub rust: Answer,
+    pub outcome: Outcome,
+}
+
+/// Solves the parts with both implementations. The Go program reads its input
+/// from a file, so the input is written to one first, whichever way it came in.
+pub fn compare_day(
+    go: &GoProgram,
+    solver: &dyn Solver,
+    input: &str,
+    parts: &[Part],
+) -> Result<Vec<Comparison>, String> {
+    let parsed = solver
+        .parse(input)
+        .map_err(|err| format!("failed to parse input: {}", err))?;
+
+    let path = env::temp_dir().join(format!(
+        "advent-parity-{}-day_{}.txt",
+        std::process::id(),
+        solver.day()
+    ));
+
+    fs::write(&path, input).map_err(|err| format!("failed to write input for Go: {}", err))?;
+
+    let comparisons = parts
+        .iter()
+        .map(|part| {
+            let rust = solver.solve(parsed.as_ref(), *part);
+            let outcome = compare(&rust, go.solve(solver.day(), *part, &path));
+
+            Comparison {
+                day: solver.day(),
+                part: *part,
+                rust,
+                outcome,
+            }
+        })
+        .collect();
+
+    let _ = fs::remove_file(&path);
+
+    Ok(comparisons)
+}
+
+/// Whether the implementations disagree or Go couldn't answer a part it has
+pub fn is_mismatch(comparison: &Comparison) -> bool {
+    matches!(
+        comparison.outcome,
+        Outcome::Differ { .. } | Outcome::GoFailed { .. }
+    )
+}
+
+pub fn format_comparisons(comparisons: &[Comparison]) -> String {
+    let rows = comparisons
+        .iter()
+        .map(|comparison| {
+            let go = match &comparison.outcome {
+                Outcome::Agree => comparison.rust.single_line(),
+                Outcome::Differ { go } => go.lines().next().unwrap_or_default().to_string(),
+                Outcome::NotPorted => String::new(),
+                Outcome::GoFailed { error } => error.clone(),
+            };
+
+            vec![
+                comparison.day.to_string(),
+                comparison.part.to_string(),
+                comparison.outcome.to_string(),
+                comparison.rust.single_line(),
+                go,
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let count = |outcome: fn(&Outcome) -> bool| {
+        comparisons
+            .iter()
+            .filter(|comparison| outcome(&comparison.outcome))
+            .count()
+    };
+
+    let grids = format_grids(
+        comparisons
+            .iter()
+            .map(|comparison| (comparison.day, comparison.part, &comparison.rust)),
+    );
+
+    format!(
+        "{}{}\n\n{} agree, {} differ, {} not ported",
+        format_table(
+            &[
+                ("Day", Align::Right),
+                ("Part", Align::Right),
+                ("Result", Align::Left),
+                ("Rust", Align::Left),
+                ("Go", Align::Left),
+            ],
+            &rows,
+        ),
+        grids,
+        count(|outcome| *outcome == Outcome::Agree),
+        count(|outcome| matches!(outcome, Outcome::Differ { .. } | Outcome::GoFailed { .. })),
+        count(|outcome| *outcome == Outcome::NotPorted),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use advent::registry;
+
+    #[test]
+    fn test_compare() {
+        let rust = Answer::from(24000);
+
+        assert_eq!(compare(&rust, Ok("24000".to_string())), Outcome::Agree);
+        assert_eq!(
+            compare(&rust, Ok("23999".to_string())),
+            Outcome::Differ {
+                go: "23999".to_string()
+            }
+        );
+        assert_eq!(
+            compare(&rust, Ok("not implemented".to_string())),
+            Outcome::NotPorted
+        );
+        assert_eq!(
+            compare(&rust, Err("Cannot open input file".to_string())),
+            Outcome::GoFailed {
+                error: "Cannot open input file".to_string()
+            }
+        );
+
+        let grid = Answer::Grid(vec!["#.".to_string(), ".#".to_string()]);
+
+        assert_eq!(compare(&grid, Ok("#.\n.#".to_string())), Outcome::Agree);
+    }
+
+    #[test]
+    fn test_format_comparisons() {
+        let comparisons = vec![
+            Comparison {
+                day: 1,
+                part: Part::One,
+                rust: Answer::from(72602),
+                outcome: Outcome::Agree,
+            },
+            Comparison {
+                day: 1,
+                part: Part::Two,
+                rust: Answer::from(207410),
+                outcome: Outcome::Differ {
+                    go: "72602".to_string(),
+                },
+            },
+            Comparison {
+                day: 2,
+                part: Part::Two,
+                rust: Answer::from(12111),
+                outcome: Outcome::NotPorted,
+            },
+        ];
+
+        assert_eq!(
+            format_comparisons(&comparisons),
+            [
+                "Day  Part  Result      Rust    Go",
+                "  1     1  agree       72602   72602",
+                "  1     2  DIFFER      207410  72602",
+                "  2     2  not ported  12111",
+                "",
+                "1 agree, 1 differ, 1 not ported",
+            ]
+            .join("\n")
+        );
+
+        assert!(!is_mismatch(&comparisons[0]));
+        assert!(is_mismatch(&comparisons[1]));
+        assert!(!is_mismatch(&comparisons[2]));
+    }
+
+    #[test]
+    #[ignore = "needs a Go toolchain"]
+    fn test_go_agrees_with_rust() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let go = GoProgram::build(&default_go_dir(root)).unwrap();
+
+        for solver in registry() {
+            let comparisons =
+                compare_day(&go, solver.as_ref(), solver.embedded_input(), &Part::all()).unwrap();
+
+            for comparison in comparisons {
+                assert!(
+                    !is_mismatch(&comparison),
+                    "Day {} part {}: {:?}",
+                    comparison.day,
+                    comparison.part,
+                    comparison.outcome
+                );
+            }
+        }
+    }
+}
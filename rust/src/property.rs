@@ -0,0 +1,23 @@
+//! Checks a property against many randomly generated cases. Each case is built
+//! from its own seed, so a failure reports the seed needed to reproduce it.
+
+use std::{
+    fmt::Debug,
+    panic::{self, AssertUnwindSafe},
+};
+
+use crate::rng::Rng;
+
+/// How many cases each property is checked against
+pub const CASES: u64 = 200;
+
+pub fn check<T: Debug>(generate: impl Fn(&mut Rng) -> T, property: impl Fn(&T)) {
+    for seed in 0..CASES {
+        let case = generate(&mut Rng::new(seed));
+
+        if let Err(err) = panic::catch_unwind(AssertUnwindSafe(|| property(&case))) {
+            eprintln!("Property failed for seed {}: {:?}", seed, case);
+            panic::resume_unwind(err);
+        }
+    }
+}
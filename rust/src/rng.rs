@@ -0,0 +1,120 @@
+//! The random numbers behind each day's `generate` module. Every generator
+//! takes an [`Rng`], so the same seed always builds the same input.
+
+use std::ops::RangeInclusive;
+
+/// Small seeded random number generator (SplitMix64), so generated inputs can
+/// be reproduced from their seed without pulling in a dependency
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Any seed is fine, including 0
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// The next value in the sequence, spread over every u64
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Any value in `range`, including both ends
+    ///
+    /// # Panics
+    ///
+    /// If `range` is empty
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "Empty range {}..={}", start, end);
+
+        let span = end.abs_diff(start).wrapping_add(1);
+
+        // A span of 0 means the range covers every i64
+        match span {
+            0 => self.next_u64() as i64,
+            span => start.wrapping_add((self.next_u64() % span) as i64),
+        }
+    }
+
+    /// A count or size in `range`, including both ends
+    pub fn count(&mut self, range: RangeInclusive<usize>) -> usize {
+        let (start, end) = range.into_inner();
+
+        self.range(start as i64..=end as i64) as usize
+    }
+
+    /// True or false, as often as each other
+    pub fn bool(&mut self) -> bool {
+        self.next_u64() & 1 == 1
+    }
+
+    /// Any one of `items`
+    ///
+    /// # Panics
+    ///
+    /// If `items` is empty
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.count(0..=items.len() - 1)]
+    }
+
+    /// Puts `items` in a random order, every order being equally likely
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for idx in (1..items.len()).rev() {
+            items.swap(idx, self.count(0..=idx));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_values() {
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+
+        let values_a = (0..10).map(|_| a.next_u64()).collect::<Vec<_>>();
+        let values_b = (0..10).map(|_| b.next_u64()).collect::<Vec<_>>();
+
+        assert_eq!(values_a, values_b);
+        assert_ne!(Rng::new(8).next_u64(), values_a[0]);
+    }
+
+    #[test]
+    fn test_range_stays_in_bounds() {
+        let mut rng = Rng::new(1);
+
+        for _ in 0..1000 {
+            assert!((-3..=3).contains(&rng.range(-3..=3)));
+            assert_eq!(rng.range(5..=5), 5);
+        }
+
+        let seen = (0..1000).map(|_| rng.range(-1..=1)).collect::<Vec<_>>();
+
+        assert!([-1, 0, 1].iter().all(|value| seen.contains(value)));
+
+        rng.range(i64::MIN..=i64::MAX);
+    }
+
+    #[test]
+    fn test_shuffle_keeps_items() {
+        let mut items = (0..20).collect::<Vec<_>>();
+
+        Rng::new(3).shuffle(&mut items);
+
+        assert_ne!(items, (0..20).collect::<Vec<_>>());
+
+        items.sort();
+
+        assert_eq!(items, (0..20).collect::<Vec<_>>());
+    }
+}
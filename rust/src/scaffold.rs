@@ -0,0 +1,250 @@
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+/// The template is compiled in so `advent new` works from anywhere, and it
+/// lives in `src/template` so it can be edited like any other day
+const TEMPLATE_MOD: &str = include_str!("template/mod.rs");
+const TEMPLATE_INPUT: &str = include_str!("template/input.rs");
+
+/// Answers for the example the scaffold creates, to be filled in from the puzzle
+const EXAMPLE_ANSWERS: &str = "# Answers to the example in example.txt, e.g.\n# part_1 = 24000\n";
+
+/// Replaced with the day number everywhere it appears in the template
+const DAY_PLACEHOLDER: &str = "__DAY__";
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    AlreadyExists(u8),
+    /// lib.rs or solution.rs no longer look the way the generator expects
+    MissingAnchor(PathBuf, &'static str),
+    Io(io::Error),
+}
+
+impl fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScaffoldError::AlreadyExists(day) => write!(f, "Day {} already exists", day),
+            ScaffoldError::MissingAnchor(path, anchor) => {
+                write!(f, "Could not find `{}` in {}", anchor, path.display())
+            }
+            ScaffoldError::Io(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl From<io::Error> for ScaffoldError {
+    fn from(err: io::Error) -> Self {
+        ScaffoldError::Io(err)
+    }
+}
+
+pub fn render(template: &str, day: u8) -> String {
+    template.replace(DAY_PLACEHOLDER, &day.to_string())
+}
+
+/// Creates `src/day_N` from the template under the crate at `root` and
+/// registers it, returning the files that were written or changed
+pub fn create_day(root: &Path, day: u8) -> Result<Vec<PathBuf>, ScaffoldError> {
+    let src = root.join("src");
+    let day_dir = src.join(format!("day_{}", day));
+    let example_dir = root.join("examples").join(format!("day_{}", day));
+    let lib_path = src.join("lib.rs");
+    let solution_path = src.join("solution.rs");
+
+    // Work out every edit before touching the disk so a failure leaves nothing half done
+    let lib = fs::read_to_string(&lib_path)?;
+    let solution = fs::read_to_string(&solution_path)?;
+
+    if day_dir.exists() || example_dir.exists() || lib.lines().any(|line| line == mod_line(day)) {
+        return Err(ScaffoldError::AlreadyExists(day));
+    }
+
+    let lib = add_mod_declaration(&lib, day).ok_or(ScaffoldError::MissingAnchor(
+        lib_path.clone(),
+        "pub mod day_",
+    ))?;
+    let solution = add_to_registry(&solution, day).ok_or(ScaffoldError::MissingAnchor(
+        solution_path.clone(),
+        "Box::new(crate::day_",
+    ))?;
+
+    fs::create_dir(&day_dir)?;
+    fs::create_dir_all(&example_dir)?;
+
+    let mod_path = day_dir.join("mod.rs");
+    let input_path = day_dir.join("input.rs");
+    let example_path = example_dir.join("example.txt");
+    let example_answers_path = example_dir.join("example.toml");
+
+    fs::write(&mod_path, render(TEMPLATE_MOD, day))?;
+    fs::write(&input_path, render(TEMPLATE_INPUT, day))?;
+    fs::write(&example_path, "")?;
+    fs::write(&example_answers_path, EXAMPLE_ANSWERS)?;
+    fs::write(&lib_path, lib)?;
+    fs::write(&solution_path, solution)?;
+
+    Ok(vec![
+        mod_path,
+        input_path,
+        example_path,
+        example_answers_path,
+        lib_path,
+        solution_path,
+    ])
+}
+
+fn mod_line(day: u8) -> String {
+    format!("pub mod day_{};", day)
+}
+
+/// Adds the module among the other days' `mod` lines, keeping the order rustfmt uses
+fn add_mod_declaration(lib: &str, day: u8) -> Option<String> {
+    let lines = lib.lines().collect::<Vec<_>>();
+
+    let is_day = |line: &str| line.starts_with("pub mod day_");
+
+    let first = lines.iter().position(|line| is_day(line))?;
+    let count = lines[first..]
+        .iter()
+        .take_while(|line| is_day(line))
+        .count();
+
+    let new_line = mod_line(day);
+    let mut mods = lines[first..first + count].to_vec();
+    mods.push(&new_line);
+    mods.sort_by_key(|line| line.trim_end_matches(';'));
+
+    let updated = lines[..first]
+        .iter()
+        .chain(&mods)
+        .chain(&lines[first + count..])
+        .copied()
+        .collect::<Vec<_>>();
+
+    Some(updated.join("\n") + "\n")
+}
+
+/// Adds the day to `registry()`, before the first day that comes after it
+fn add_to_registry(solution: &str, day: u8) -> Option<String> {
+    let lines = solution.lines().collect::<Vec<_>>();
+
+    let entries = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, line)| Some((idx, registry_entry_day(line)?)))
+        .collect::<Vec<_>>();
+
+    let (last_idx, _) = *entries.last()?;
+    let insert_idx = entries
+        .iter()
+        .find(|(_, entry_day)| *entry_day > day)
+        .map_or(last_idx + 1, |(idx, _)| *idx);
+
+    let indent = &lines[last_idx][..lines[last_idx].len() - lines[last_idx].trim_start().len()];
+    let new_line = format!("{}Box::new(crate::day_{}::Day{}),", indent, day, day);
+
+    let mut updated = lines;
+    updated.insert(insert_idx, &new_line);
+
+    Some(updated.join("\n") + "\n")
+}
+
+fn registry_entry_day(line: &str) -> Option<u8> {
+    line.trim()
+        .strip_prefix("Box::new(crate::day_")?
+        .split_once("::")?
+        .0
+        .parse::<u8>()
+        .ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static LIB: &str = "pub mod day_1;\npub mod day_2;\n#[cfg(test)]\nmod examples;\npub mod solution;\n\npub use crate::solution::Part;\n";
+
+    static SOLUTION: &str = r#"pub fn registry() -> Vec<Box<dyn Solver>> {
+    vec![
+        Box::new(crate::day_1::Day1),
+        Box::new(crate::day_9::Day9),
+    ]
+}
+"#;
+
+    #[test]
+    fn test_render() {
+        let rendered = render(TEMPLATE_MOD, 12);
+
+        assert!(rendered.contains("pub struct Day12;"));
+        assert!(rendered.contains("const DAY: u8 = 12;"));
+        assert!(rendered.contains("input::INPUT_DAY_12;"));
+        assert!(!rendered.contains(DAY_PLACEHOLDER));
+
+        assert_eq!(
+            render(TEMPLATE_INPUT, 12),
+            "pub static INPUT_DAY_12: &str = r#\"\"#;\n"
+        );
+    }
+
+    #[test]
+    fn test_add_mod_declaration() {
+        assert_eq!(
+            add_mod_declaration(LIB, 12).unwrap(),
+            "pub mod day_1;\npub mod day_12;\npub mod day_2;\n#[cfg(test)]\nmod examples;\npub mod solution;\n\npub use crate::solution::Part;\n"
+        );
+
+        assert_eq!(
+            add_mod_declaration("pub use crate::solution::Part;\n", 12),
+            None
+        );
+    }
+
+    #[test]
+    fn test_add_to_registry() {
+        let updated = add_to_registry(SOLUTION, 5).unwrap();
+
+        assert!(updated.contains(
+            "        Box::new(crate::day_1::Day1),\n        Box::new(crate::day_5::Day5),\n        Box::new(crate::day_9::Day9),\n"
+        ));
+
+        let updated = add_to_registry(SOLUTION, 12).unwrap();
+
+        assert!(updated.contains(
+            "        Box::new(crate::day_9::Day9),\n        Box::new(crate::day_12::Day12),\n    ]"
+        ));
+    }
+
+    #[test]
+    fn test_create_day() {
+        let root = std::env::temp_dir().join(format!("advent-scaffold-{}", std::process::id()));
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/lib.rs"), LIB).unwrap();
+        fs::write(root.join("src/solution.rs"), SOLUTION).unwrap();
+
+        let written = create_day(&root, 12).unwrap();
+
+        assert_eq!(written.len(), 6);
+        assert!(root.join("src/day_12/mod.rs").is_file());
+        assert!(root.join("examples/day_12/example.toml").is_file());
+        assert!(fs::read_to_string(root.join("src/lib.rs"))
+            .unwrap()
+            .contains("pub mod day_12;"));
+
+        // A second attempt must not clobber the first
+        assert!(matches!(
+            create_day(&root, 12),
+            Err(ScaffoldError::AlreadyExists(12))
+        ));
+
+        // Nor can a day that is already declared but lives somewhere else
+        assert!(matches!(
+            create_day(&root, 2),
+            Err(ScaffoldError::AlreadyExists(2))
+        ));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,133 @@
+use std::{any::Any, fmt, io::BufRead};
+
+use crate::answer::Answer;
+use crate::error::ParseError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn all() -> [Part; 2] {
+        [Part::One, Part::Two]
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// A single day of the puzzle. The input is parsed once and both parts are
+/// solved from the parsed value, so parts that need to mutate it clone first.
+pub trait Solution {
+    type Parsed: 'static;
+
+    const DAY: u8;
+    /// Puzzle input compiled into the binary, used when no input file is found
+    const INPUT: &'static str;
+
+    /// Parses the input as it is read, so it never has to be held in memory
+    fn read(&self, input: &mut dyn BufRead) -> Result<Self::Parsed, ParseError>;
+
+    fn parse(&self, input: &str) -> Result<Self::Parsed, ParseError> {
+        self.read(&mut input.as_bytes())
+    }
+
+    fn part_1(&self, parsed: &Self::Parsed) -> Answer;
+
+    fn part_2(&self, parsed: &Self::Parsed) -> Answer;
+
+    #[cfg(test)]
+    fn solve_part_1(&self, input: &str) -> Answer {
+        self.part_1(&self.parse(input).expect("Test input should parse"))
+    }
+
+    #[cfg(test)]
+    fn solve_part_2(&self, input: &str) -> Answer {
+        self.part_2(&self.parse(input).expect("Test input should parse"))
+    }
+}
+
+/// Type erased view of a [`Solution`] so every day can live in one registry.
+/// Solvers are shared between threads when days are run in parallel.
+pub trait Solver: Sync {
+    fn day(&self) -> u8;
+
+    fn embedded_input(&self) -> &'static str;
+
+    fn read(&self, input: &mut dyn BufRead) -> Result<Box<dyn Any>, ParseError>;
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, ParseError> {
+        self.read(&mut input.as_bytes())
+    }
+
+    fn solve(&self, parsed: &dyn Any, part: Part) -> Answer;
+}
+
+impl<S: Solution + Sync> Solver for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn embedded_input(&self) -> &'static str {
+        S::INPUT
+    }
+
+    fn read(&self, input: &mut dyn BufRead) -> Result<Box<dyn Any>, ParseError> {
+        let parsed = Solution::read(self, input)?;
+
+        Ok(Box::new(parsed))
+    }
+
+    fn solve(&self, parsed: &dyn Any, part: Part) -> Answer {
+        let parsed = parsed
+            .downcast_ref::<S::Parsed>()
+            .expect("Parsed input belongs to a different day");
+
+        match part {
+            Part::One => self.part_1(parsed),
+            Part::Two => self.part_2(parsed),
+        }
+    }
+}
+
+/// Every solved day, in order. Adding a day means adding it here, which
+/// `advent new` does automatically.
+pub fn registry() -> Vec<Box<dyn Solver>> {
+    vec![
+        Box::new(crate::day_1::Day1),
+        Box::new(crate::day_2::Day2),
+        Box::new(crate::day_3::Day3),
+        Box::new(crate::day_4::Day4),
+        Box::new(crate::day_5::Day5),
+        Box::new(crate::day_6::Day6),
+        Box::new(crate::day_7::Day7),
+        Box::new(crate::day_8::Day8),
+        Box::new(crate::day_9::Day9),
+        Box::new(crate::day_10::Day10),
+        Box::new(crate::day_11::Day11),
+    ]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_registry_is_ordered_by_day() {
+        let days = registry()
+            .iter()
+            .map(|solver| solver.day())
+            .collect::<Vec<_>>();
+
+        assert!(days.starts_with(&(1..=11).collect::<Vec<_>>()));
+        assert!(days.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+}
@@ -0,0 +1,156 @@
+//! Synthetic puzzle inputs far larger than the real ones, for seeing how each
+//! solver scales with its input. Inputs are written out as they're generated,
+//! so even ones too large to hold in memory can be piped into `advent run`.
+
+use std::io::{self, Write};
+
+use advent::rng::Rng;
+
+/// Writes an input of the given size
+type Generate = fn(&mut Rng, usize, &mut dyn Write) -> io::Result<()>;
+
+pub struct StressGenerator {
+    pub day: u8,
+    generate: Generate,
+}
+
+impl StressGenerator {
+    /// The same size and seed always give the same input
+    pub fn write(&self, size: usize, seed: u64, out: &mut dyn Write) -> io::Result<()> {
+        (self.generate)(&mut Rng::new(seed), size, out)
+    }
+}
+
+/// Every day that can generate a stress input, in order
+pub const GENERATORS: &[StressGenerator] = &[
+    // Sized by the number of elves
+    StressGenerator {
+        day: 1,
+        generate: advent::day_1::generate::stress,
+    },
+    // Sized by the number of rounds
+    StressGenerator {
+        day: 2,
+        generate: advent::day_2::generate::stress,
+    },
+    // Sized by the number of groups of three rucksacks
+    StressGenerator {
+        day: 3,
+        generate: advent::day_3::generate::stress,
+    },
+    // Sized by the number of pairs
+    StressGenerator {
+        day: 4,
+        generate: advent::day_4::generate::stress,
+    },
+    // Sized by the number of crates and moves
+    StressGenerator {
+        day: 5,
+        generate: advent::day_5::generate::stress,
+    },
+    // Sized by the number of characters
+    StressGenerator {
+        day: 6,
+        generate: advent::day_6::generate::stress,
+    },
+    // Sized by the number of nested directories
+    StressGenerator {
+        day: 7,
+        generate: advent::day_7::generate::stress,
+    },
+    // Sized by the number of trees along each side
+    StressGenerator {
+        day: 8,
+        generate: advent::day_8::generate::stress,
+    },
+    // Sized by the number of moves
+    StressGenerator {
+        day: 9,
+        generate: advent::day_9::generate::stress,
+    },
+    // Sized by the number of instructions
+    StressGenerator {
+        day: 10,
+        generate: advent::day_10::generate::stress,
+    },
+    // Sized by the number of monkeys
+    StressGenerator {
+        day: 11,
+        generate: advent::day_11::generate::stress,
+    },
+];
+
+pub fn find_generator(day: u8) -> Option<&'static StressGenerator> {
+    GENERATORS.iter().find(|generator| generator.day == day)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use advent::{registry, Part};
+
+    fn generate(day: u8, size: usize, seed: u64) -> String {
+        let mut out = Vec::new();
+
+        find_generator(day)
+            .unwrap()
+            .write(size, seed, &mut out)
+            .unwrap();
+
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_every_input_solves() {
+        let solvers = registry();
+
+        for generator in GENERATORS {
+            let solver = solvers
+                .iter()
+                .find(|solver| solver.day() == generator.day)
+                .unwrap();
+
+            for size in [1, 2, 50] {
+                let input = generate(generator.day, size, 7);
+                let parsed = solver
+                    .parse(&input)
+                    .unwrap_or_else(|err| panic!("Day {} at size {}: {}", solver.day(), size, err));
+
+                for part in Part::all() {
+                    solver.solve(parsed.as_ref(), part);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_same_seed_same_input() {
+        for generator in GENERATORS {
+            assert_eq!(
+                generate(generator.day, 20, 3),
+                generate(generator.day, 20, 3)
+            );
+        }
+
+        assert_ne!(generate(1, 20, 3), generate(1, 20, 4));
+    }
+
+    #[test]
+    fn test_size() {
+        let elves = generate(1, 1000, 1);
+
+        assert_eq!(elves.split("\n\n").count(), 1000);
+
+        let forest = generate(8, 30, 1);
+
+        assert_eq!(forest.lines().count(), 30);
+        assert!(forest.lines().all(|row| row.len() == 30));
+
+        let file_system = generate(7, 100, 1);
+
+        assert_eq!(file_system.matches("$ cd d").count(), 99);
+
+        assert_eq!(generate(6, 5000, 1).trim_end().len(), 5000);
+        assert_eq!(generate(11, 40, 1).matches("Monkey").count(), 40);
+    }
+}
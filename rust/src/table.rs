@@ -0,0 +1,52 @@
+//! Plain text tables, used by the reports days like 1 and 2 print and by the
+//! `advent` binary for its own output.
+
+/// Which side of its column a cell's text is pushed to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Right,
+}
+
+/// Lines up `rows` under the column headings, two spaces apart. Trailing
+/// spaces are trimmed from every line.
+///
+/// # Panics
+///
+/// If a row has fewer cells than there are columns
+pub fn format_table(columns: &[(&str, Align)], rows: &[Vec<String>]) -> String {
+    let widths = columns
+        .iter()
+        .enumerate()
+        .map(|(idx, (heading, _))| {
+            rows.iter()
+                .map(|row| row[idx].chars().count())
+                .chain(std::iter::once(heading.chars().count()))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+
+    let headings = columns
+        .iter()
+        .map(|(heading, _)| heading.to_string())
+        .collect::<Vec<_>>();
+
+    std::iter::once(&headings)
+        .chain(rows)
+        .map(|row| {
+            row.iter()
+                .zip(columns)
+                .zip(&widths)
+                .map(|((cell, (_, align)), width)| match align {
+                    Align::Left => format!("{:<width$}", cell, width = width),
+                    Align::Right => format!("{:>width$}", cell, width = width),
+                })
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -1 +1 @@
-pub static INPUT_DAY_11: &'static str = r#""#;
+pub static INPUT_DAY___DAY__: &str = r#""#;
@@ -1,52 +1,65 @@
+//! Day __DAY__
+
+use std::io::BufRead;
+
+use crate::error::ParseError;
+use crate::normalize::LineReader;
+use crate::answer::Answer;
+use crate::solution::Solution;
+
 pub mod input;
 
 #[derive(Debug, PartialEq)]
-struct OutputToken;
-
-impl OutputToken {
-    pub fn new() -> Self {
-        Self
-    }
-}
+pub struct OutputToken;
 
-type ProcessedInput = Vec<OutputToken>;
+pub type ProcessedInput = Vec<OutputToken>;
 
-fn parse_input(input: &str) -> ProcessedInput {
+/// Parses one token per non-empty line
+pub fn parse_input(input: impl BufRead) -> Result<ProcessedInput, ParseError> {
     let mut processed_output = Vec::new();
 
-    for line in input.lines() {
-        let trimmed_line = line.trim();
+    let mut lines = LineReader::new(Day__DAY__::DAY, input);
 
-        if trimmed_line == "" {
+    while let Some(line) = lines.next_line()? {
+        if line.is_blank() {
             continue;
         }
 
-        let token = OutputToken::new();
+        let token = OutputToken;
 
         processed_output.push(token);
     }
 
-    processed_output
+    Ok(processed_output)
 }
 
-fn handle_processed_input(input: &mut ProcessedInput) {
+fn handle_processed_input(_input: &ProcessedInput) {
     // Do something
 }
 
-pub fn day_11_part_1(input: &'static str) -> String {
-    let mut processed_input = parse_input(input);
+pub struct Day__DAY__;
 
-    handle_processed_input(&mut processed_input);
+impl Solution for Day__DAY__ {
+    type Parsed = ProcessedInput;
 
-    "TBA".to_string()
-}
+    const DAY: u8 = __DAY__;
+    const INPUT: &'static str = input::INPUT_DAY___DAY__;
+
+    fn read(&self, input: &mut dyn BufRead) -> Result<Self::Parsed, ParseError> {
+        parse_input(input)
+    }
 
-pub fn day_11_part_2(input: &'static str) -> String {
-    let mut processed_input = parse_input(input);
+    fn part_1(&self, processed_input: &Self::Parsed) -> Answer {
+        handle_processed_input(processed_input);
 
-    handle_processed_input(&mut processed_input);
+        Answer::NotImplemented
+    }
+
+    fn part_2(&self, processed_input: &Self::Parsed) -> Answer {
+        handle_processed_input(processed_input);
 
-    "TBA".to_string()
+        Answer::NotImplemented
+    }
 }
 
 #[cfg(test)]
@@ -58,7 +71,7 @@ mod test {
 
     #[test]
     fn test_parse_input() {
-        let processed_input = parse_input(TEST_INPUT);
+        let processed_input = parse_input(TEST_INPUT.as_bytes()).unwrap();
 
         assert_eq!(processed_input, vec![]);
     }
@@ -0,0 +1,339 @@
+use std::{
+    any::Any,
+    time::{Duration, Instant},
+};
+
+use crate::memory::PeakAllocator;
+use advent::answer::{format_grids, Answer};
+use advent::loader::Input;
+use advent::table::{format_table, Align};
+use advent::{Part, Solver};
+
+/// Runs `f` once, returning its result along with how long it took
+pub fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+
+    (result, start.elapsed())
+}
+
+/// One row of the table printed by `advent run`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Timing {
+    pub day: u8,
+    pub part: Part,
+    pub answer: Answer,
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+pub fn format_timings(timings: &[Timing]) -> String {
+    let rows = timings
+        .iter()
+        .map(|timing| {
+            vec![
+                timing.day.to_string(),
+                timing.part.to_string(),
+                timing.answer.single_line(),
+                timing.parse.as_micros().to_string(),
+                timing.solve.as_micros().to_string(),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let table = format_table(
+        &[
+            ("Day", Align::Right),
+            ("Part", Align::Right),
+            ("Answer", Align::Left),
+            ("Parse µs", Align::Right),
+            ("Solve µs", Align::Right),
+        ],
+        &rows,
+    );
+
+    let grids = format_grids(
+        timings
+            .iter()
+            .map(|timing| (timing.day, timing.part, &timing.answer)),
+    );
+
+    table + &grids
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Summary {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Summary {
+    /// For an even number of samples the upper of the two middle values is used
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        Some(Self {
+            min: *sorted.first()?,
+            median: sorted[sorted.len() / 2],
+            max: *sorted.last()?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    Parse,
+    Solve(Part),
+}
+
+impl std::fmt::Display for Stage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Stage::Parse => write!(f, "parse"),
+            Stage::Solve(part) => write!(f, "part {}", part),
+        }
+    }
+}
+
+/// One row of the table printed by `advent bench`
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchResult {
+    pub day: u8,
+    pub stage: Stage,
+    pub summary: Summary,
+    /// The most heap memory the stage had in use at once in any run, in bytes,
+    /// on top of what was in use when it started. Parsing includes reading.
+    pub peak_memory: usize,
+}
+
+/// Reads, parses and solves the input `runs` times, timing every stage
+/// separately and measuring its memory with `memory`. That only counts
+/// anything if it is the global allocator.
+pub fn bench_day(
+    solver: &dyn Solver,
+    input: &mut Input,
+    parts: &[Part],
+    runs: usize,
+    memory: &PeakAllocator,
+) -> Result<Vec<BenchResult>, String> {
+    let mut parse_samples = Vec::with_capacity(runs);
+    let mut solve_samples = vec![Vec::with_capacity(runs); parts.len()];
+
+    let mut parse_peak = 0;
+    let mut solve_peaks = vec![0; parts.len()];
+
+    for _ in 0..runs {
+        let read = || -> Result<Box<dyn Any>, String> {
+            let mut reader = input
+                .reader()
+                .map_err(|err| format!("failed to read input: {}", err))?;
+
+            solver
+                .read(&mut reader)
+                .map_err(|err| format!("failed to parse input: {}", err))
+        };
+
+        let ((parsed, parse_time), peak) = memory.measure(|| time(read));
+        let parsed = parsed?;
+        parse_samples.push(parse_time);
+        parse_peak = parse_peak.max(peak);
+
+        for ((samples, solve_peak), part) in
+            solve_samples.iter_mut().zip(&mut solve_peaks).zip(parts)
+        {
+            let ((_, solve_time), peak) =
+                memory.measure(|| time(|| solver.solve(parsed.as_ref(), *part)));
+            samples.push(solve_time);
+            *solve_peak = (*solve_peak).max(peak);
+        }
+    }
+
+    let stages = std::iter::once((Stage::Parse, parse_samples, parse_peak)).chain(
+        parts
+            .iter()
+            .zip(solve_samples)
+            .zip(solve_peaks)
+            .map(|((part, samples), peak)| (Stage::Solve(*part), samples, peak)),
+    );
+
+    Ok(stages
+        .filter_map(|(stage, samples, peak_memory)| {
+            Some(BenchResult {
+                day: solver.day(),
+                stage,
+                summary: Summary::from_samples(&samples)?,
+                peak_memory,
+            })
+        })
+        .collect())
+}
+
+pub fn format_bench_results(results: &[BenchResult]) -> String {
+    let rows = results
+        .iter()
+        .map(|result| {
+            vec![
+                result.day.to_string(),
+                result.stage.to_string(),
+                result.summary.min.as_micros().to_string(),
+                result.summary.median.as_micros().to_string(),
+                result.summary.max.as_micros().to_string(),
+                result.peak_memory.div_ceil(1024).to_string(),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    format_table(
+        &[
+            ("Day", Align::Right),
+            ("Stage", Align::Left),
+            ("Min µs", Align::Right),
+            ("Median µs", Align::Right),
+            ("Max µs", Align::Right),
+            ("Peak KiB", Align::Right),
+        ],
+        &rows,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use advent::day_1::Day1;
+
+    #[test]
+    fn test_summary_from_samples() {
+        let samples = [5, 1, 4, 2, 3].map(Duration::from_micros);
+
+        assert_eq!(
+            Summary::from_samples(&samples),
+            Some(Summary {
+                min: Duration::from_micros(1),
+                median: Duration::from_micros(3),
+                max: Duration::from_micros(5),
+            })
+        );
+
+        assert_eq!(Summary::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn test_format_timings() {
+        let timings = vec![
+            Timing {
+                day: 5,
+                part: Part::One,
+                answer: Answer::from("VCTFTJQCG"),
+                parse: Duration::from_micros(120),
+                solve: Duration::from_micros(8),
+            },
+            Timing {
+                day: 11,
+                part: Part::Two,
+                answer: Answer::from(1),
+                parse: Duration::from_micros(3),
+                solve: Duration::from_micros(45000),
+            },
+            Timing {
+                day: 10,
+                part: Part::Two,
+                answer: Answer::Grid(vec!["#.#".to_string(), ".#.".to_string()]),
+                parse: Duration::from_micros(10),
+                solve: Duration::from_micros(20),
+            },
+        ];
+
+        assert_eq!(
+            format_timings(&timings),
+            [
+                "Day  Part  Answer               Parse µs  Solve µs",
+                "  5     1  VCTFTJQCG                 120         8",
+                " 11     2  1                           3     45000",
+                " 10     2  (2 rows, see below)        10        20",
+                "",
+                "Day 10 part 2:",
+                "#.#",
+                ".#.",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_bench_day() {
+        let memory = PeakAllocator::new();
+
+        let results = bench_day(
+            &Day1,
+            &mut Input::Embedded("1000\n\n2000\n"),
+            &Part::all(),
+            3,
+            &memory,
+        )
+        .unwrap();
+
+        let stages = results
+            .iter()
+            .map(|result| result.stage)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            stages,
+            vec![
+                Stage::Parse,
+                Stage::Solve(Part::One),
+                Stage::Solve(Part::Two)
+            ]
+        );
+
+        // Nothing is counted by an allocator that isn't installed
+        assert!(results.iter().all(|result| result.peak_memory == 0));
+
+        let err = bench_day(
+            &Day1,
+            &mut Input::Embedded("1000\nabc\n"),
+            &Part::all(),
+            3,
+            &memory,
+        )
+        .unwrap_err();
+
+        assert!(err.starts_with("failed to parse input"));
+    }
+
+    #[test]
+    fn test_format_bench_results() {
+        let summary = Summary {
+            min: Duration::from_micros(90),
+            median: Duration::from_micros(100),
+            max: Duration::from_micros(250),
+        };
+
+        let results = vec![
+            BenchResult {
+                day: 9,
+                stage: Stage::Parse,
+                summary,
+                peak_memory: 300_000,
+            },
+            BenchResult {
+                day: 9,
+                stage: Stage::Solve(Part::Two),
+                summary,
+                peak_memory: 1,
+            },
+        ];
+
+        assert_eq!(
+            format_bench_results(&results),
+            [
+                "Day  Stage   Min µs  Median µs  Max µs  Peak KiB",
+                "  9  parse       90        100     250       293",
+                "  9  part 2      90        100     250         1",
+            ]
+            .join("\n")
+        );
+    }
+}
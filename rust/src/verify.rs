@@ -0,0 +1,370 @@
+use std::{
+    collections::BTreeMap,
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use advent::answer::{format_grids, Answer};
+use advent::table::{format_table, Align};
+use advent::Part;
+
+/// The answers file `advent verify` reads unless given another
+pub fn default_answers_path(root: &Path) -> PathBuf {
+    root.join("answers.toml")
+}
+
+/// Accepted answers keyed by day and part
+pub type Answers = BTreeMap<(u8, Part), Answer>;
+
+#[derive(Debug, PartialEq)]
+pub struct AnswersError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for AnswersError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "answers line {}: {}", self.line, self.message)
+    }
+}
+
+/// Reads the small subset of TOML the answers file uses:
+///
+/// ```toml
+/// [day_5]
+/// part_1 = "VCTFTJQCG"
+/// part_2 = 1234
+///
+/// [day_10]
+/// part_2 = [
+///     "#..#",
+///     ".##.",
+/// ]
+/// ```
+///
+/// An array of strings is a grid, one string per row.
+pub fn parse_answers(text: &str) -> Result<Answers, AnswersError> {
+    parse_sections(text, None)
+}
+
+/// Reads answers that all belong to one day, so no `[day_N]` section is needed
+#[cfg(test)]
+pub fn parse_day_answers(text: &str, day: u8) -> Result<Answers, AnswersError> {
+    parse_sections(text, Some(day))
+}
+
+fn parse_sections(text: &str, mut day: Option<u8>) -> Result<Answers, AnswersError> {
+    let mut answers = Answers::new();
+
+    let mut lines = text.lines().enumerate();
+
+    while let Some((line_idx, line)) = lines.next() {
+        let error = |message: &str| AnswersError {
+            line: line_idx + 1,
+            message: message.to_string(),
+        };
+
+        let trimmed_line = line.trim();
+
+        if trimmed_line.is_empty() || trimmed_line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(section) = trimmed_line.strip_prefix('[') {
+            let day_number = section
+                .strip_suffix(']')
+                .and_then(|section| section.trim().strip_prefix("day_"))
+                .and_then(|number| number.parse::<u8>().ok())
+                .ok_or_else(|| error("Sections should look like [day_N]"))?;
+
+            day = Some(day_number);
+            continue;
+        }
+
+        let day = day.ok_or_else(|| error("Answers must come after a [day_N] section"))?;
+
+        let (key, value) = trimmed_line
+            .split_once('=')
+            .ok_or_else(|| error("Expected part_N = answer"))?;
+
+        let part = match key.trim() {
+            "part_1" => Part::One,
+            "part_2" => Part::Two,
+            _ => return Err(error("Keys should be part_1 or part_2")),
+        };
+
+        let mut value = value.trim().to_string();
+
+        // A grid's rows can go on lines of their own, up to the closing bracket
+        if value.starts_with('[') {
+            while !value.ends_with(']') {
+                let (_, next_line) = lines.next().ok_or_else(|| error("Array is never closed"))?;
+
+                value.push_str(next_line.trim());
+            }
+        }
+
+        let value = parse_value(&value).ok_or_else(|| error("Invalid answer"))?;
+
+        if answers.insert((day, part), value).is_some() {
+            return Err(error("Answer is recorded twice"));
+        }
+    }
+
+    Ok(answers)
+}
+
+/// Strings are quoted, numbers may be left bare and grids are arrays of strings
+fn parse_value(value: &str) -> Option<Answer> {
+    if let Some(items) = value.strip_prefix('[') {
+        // A trailing comma leaves an empty item at the end
+        let mut items = items.strip_suffix(']')?.split(',').collect::<Vec<_>>();
+        if items.last().is_some_and(|item| item.trim().is_empty()) {
+            items.pop();
+        }
+
+        let rows = items
+            .into_iter()
+            .map(|item| parse_string(item.trim()))
+            .collect::<Option<Vec<_>>>()?;
+
+        (!rows.is_empty()).then_some(Answer::Grid(rows))
+    } else if value.starts_with('"') {
+        parse_string(value).map(Answer::Text)
+    } else if !value.is_empty() && value.chars().all(|c| c.is_ascii_digit() || c == '-') {
+        value.parse().ok().map(Answer::Integer)
+    } else {
+        None
+    }
+}
+
+fn parse_string(value: &str) -> Option<String> {
+    let unquoted = value.strip_prefix('"')?.strip_suffix('"')?;
+
+    // Escapes aren't supported, so a quote can only be at the ends
+    if unquoted.contains('"') || unquoted.contains('\\') {
+        return None;
+    }
+
+    Some(unquoted.to_string())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Pass,
+    Fail { expected: Answer },
+    Missing,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Pass => write!(f, "pass"),
+            Outcome::Fail { .. } => write!(f, "FAIL"),
+            Outcome::Missing => write!(f, "missing"),
+        }
+    }
+}
+
+/// Answers match when they read the same, so a number recorded in quotes still
+/// passes. A part that isn't implemented never does.
+pub fn check(answers: &Answers, day: u8, part: Part, actual: &Answer) -> Outcome {
+    match answers.get(&(day, part)) {
+        Some(expected)
+            if *actual != Answer::NotImplemented && expected.to_string() == actual.to_string() =>
+        {
+            Outcome::Pass
+        }
+        Some(expected) => Outcome::Fail {
+            expected: expected.clone(),
+        },
+        None => Outcome::Missing,
+    }
+}
+
+/// One row of the table printed by `advent verify`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Verification {
+    pub day: u8,
+    pub part: Part,
+    pub answer: Answer,
+    pub outcome: Outcome,
+}
+
+pub fn format_verifications(verifications: &[Verification]) -> String {
+    let rows = verifications
+        .iter()
+        .map(|verification| {
+            let expected = match &verification.outcome {
+                Outcome::Fail { expected } => expected.single_line(),
+                _ => String::new(),
+            };
+
+            vec![
+                verification.day.to_string(),
+                verification.part.to_string(),
+                verification.outcome.to_string(),
+                verification.answer.single_line(),
+                expected,
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let count = |outcome: fn(&Outcome) -> bool| {
+        verifications
+            .iter()
+            .filter(|verification| outcome(&verification.outcome))
+            .count()
+    };
+
+    let grids = format_grids(
+        verifications
+            .iter()
+            .map(|verification| (verification.day, verification.part, &verification.answer)),
+    );
+
+    let expected_grids = verifications
+        .iter()
+        .filter_map(|verification| match &verification.outcome {
+            Outcome::Fail {
+                expected: expected @ Answer::Grid(_),
+            } => Some(format!(
+                "\n\nDay {} part {} expected:\n{}",
+                verification.day, verification.part, expected
+            )),
+            _ => None,
+        })
+        .collect::<String>();
+
+    format!(
+        "{}{}{}\n\n{} passed, {} failed, {} missing",
+        format_table(
+            &[
+                ("Day", Align::Right),
+                ("Part", Align::Right),
+                ("Result", Align::Left),
+                ("Answer", Align::Left),
+                ("Expected", Align::Left),
+            ],
+            &rows,
+        ),
+        grids,
+        expected_grids,
+        count(|outcome| *outcome == Outcome::Pass),
+        count(|outcome| matches!(outcome, Outcome::Fail { .. })),
+        count(|outcome| *outcome == Outcome::Missing),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    static ANSWERS: &str = r##"
+# Accepted answers
+[day_1]
+part_1 = 72602
+part_2 = "207410"
+
+[day_5]
+part_1 = "VCTFTJQCG"
+
+[day_10]
+part_2 = [
+    "#..#",
+    ".##.",
+]
+"##;
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = parse_answers(ANSWERS).unwrap();
+
+        assert_eq!(answers.len(), 4);
+        assert_eq!(answers[&(1, Part::One)], Answer::from(72602));
+        assert_eq!(answers[&(1, Part::Two)], Answer::from("207410"));
+        assert_eq!(answers[&(5, Part::One)], Answer::from("VCTFTJQCG"));
+        assert_eq!(
+            answers[&(10, Part::Two)],
+            Answer::Grid(vec!["#..#".to_string(), ".##.".to_string()])
+        );
+
+        let answers = parse_answers("[day_10]\npart_2 = [\"#.\", \".#\"]\n").unwrap();
+
+        assert_eq!(
+            answers[&(10, Part::Two)],
+            Answer::Grid(vec!["#.".to_string(), ".#".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_answers() {
+        let err = parse_answers("part_1 = 1\n").unwrap_err();
+
+        assert_eq!(err.line, 1);
+
+        let err = parse_answers("[day_1]\npart_3 = 1\n").unwrap_err();
+
+        assert_eq!(
+            err,
+            AnswersError {
+                line: 2,
+                message: "Keys should be part_1 or part_2".to_string()
+            }
+        );
+
+        assert!(parse_answers("[day_one]\n").is_err());
+        assert!(parse_answers("[day_1]\npart_1 = \"open\n").is_err());
+        assert!(parse_answers("[day_1]\npart_1 = bare words\n").is_err());
+        assert!(parse_answers("[day_1]\npart_1 = 1\npart_1 = 2\n").is_err());
+        assert!(parse_answers("[day_1]\npart_1 = []\n").is_err());
+        assert!(parse_answers("[day_1]\npart_1 = [1, 2]\n").is_err());
+
+        let err = parse_answers("[day_1]\npart_1 = [\n    \"#.\",\n").unwrap_err();
+
+        assert_eq!(err.line, 2);
+        assert_eq!(err.message, "Array is never closed");
+    }
+
+    #[test]
+    fn test_parse_day_answers() {
+        let answers = parse_day_answers("part_1 = 7\npart_2 = 19\n", 6).unwrap();
+
+        assert_eq!(answers[&(6, Part::One)], Answer::from(7));
+        assert_eq!(answers[&(6, Part::Two)], Answer::from(19));
+    }
+
+    #[test]
+    fn test_check() {
+        let answers = parse_answers(ANSWERS).unwrap();
+
+        assert_eq!(
+            check(&answers, 1, Part::One, &Answer::from(72602)),
+            Outcome::Pass
+        );
+        assert_eq!(
+            check(&answers, 1, Part::Two, &Answer::from(207410)),
+            Outcome::Pass
+        );
+        assert_eq!(
+            check(&answers, 1, Part::Two, &Answer::from(1)),
+            Outcome::Fail {
+                expected: Answer::from("207410")
+            }
+        );
+        assert_eq!(
+            check(&answers, 5, Part::One, &Answer::NotImplemented),
+            Outcome::Fail {
+                expected: Answer::from("VCTFTJQCG")
+            }
+        );
+        assert_eq!(
+            check(&answers, 10, Part::Two, &answers[&(10, Part::Two)]),
+            Outcome::Pass
+        );
+        assert_eq!(
+            check(&answers, 5, Part::Two, &Answer::from("GCFGLDNJZ")),
+            Outcome::Missing
+        );
+    }
+}
@@ -0,0 +1,247 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::output::{parse_lines, Record};
+use advent::answer::Answer;
+
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The day's source, its examples and its input, if it comes from a file. Any
+/// of them may not exist yet.
+pub fn watched_paths(root: &Path, day: u8, input: Option<&Path>) -> Vec<PathBuf> {
+    let mut paths = vec![
+        root.join("src").join(format!("day_{}", day)),
+        root.join("examples").join(format!("day_{}", day)),
+    ];
+
+    paths.extend(input.map(Path::to_path_buf));
+    paths
+}
+
+/// When each watched file was last changed. Directories are looked inside,
+/// so adding or removing a file counts as a change too. A file that doesn't
+/// exist has no time, so creating it is a change as well.
+fn modified_times(paths: &[PathBuf]) -> Vec<(PathBuf, Option<SystemTime>)> {
+    let mut files = Vec::new();
+
+    for path in paths {
+        match path.is_dir() {
+            true => {
+                let entries = fs::read_dir(path).into_iter().flatten();
+                files.extend(entries.filter_map(|entry| Some(entry.ok()?.path())));
+            }
+            false => files.push(path.clone()),
+        }
+    }
+
+    files.sort();
+
+    files
+        .into_iter()
+        .map(|file| {
+            let modified = fs::metadata(&file).and_then(|metadata| metadata.modified());
+            (file, modified.ok())
+        })
+        .collect()
+}
+
+fn describe(record: &Record) -> String {
+    match (&record.answer, &record.error) {
+        // Starting a grid on its own line keeps its rows lined up
+        (Some(answer @ Answer::Grid(_)), _) => format!("\n{}", answer),
+        (Some(answer), _) => answer.to_string(),
+        (None, Some(error)) => format!("error: {}", error),
+        (None, None) => "no answer".to_string(),
+    }
+}
+
+/// One line per part saying how its answer compares to the previous run
+pub fn diff_answers(previous: Option<&[Record]>, current: &[Record]) -> Vec<String> {
+    current
+        .iter()
+        .map(|record| {
+            let now = describe(record);
+
+            let before = previous.and_then(|previous| {
+                previous
+                    .iter()
+                    .find(|old| old.day == record.day && old.part == record.part)
+            });
+
+            match before.map(describe) {
+                None => format!("Part {}: {}", record.part, now),
+                Some(before) if before == now => {
+                    format!("Part {}: {} (unchanged)", record.part, now)
+                }
+                Some(before) => format!("Part {}: {} -> {}", record.part, before, now),
+            }
+        })
+        .collect()
+}
+
+fn cargo(root: &Path) -> Command {
+    // Set by cargo when it runs us, so the same toolchain is used again
+    let mut command = Command::new(env::var_os("CARGO").unwrap_or_else(|| "cargo".into()));
+    command.current_dir(root);
+    command
+}
+
+/// Rebuilds and runs the day in a new process, so source changes are picked up
+fn run_day(root: &Path, day: u8, input: Option<&Path>) -> Result<Vec<Record>, String> {
+    let mut command = cargo(root);
+    command.args(["run", "--quiet", "--", "run", "--day", &day.to_string()]);
+    command.args(["--format", "lines"]);
+
+    if let Some(input) = input {
+        command.arg("--input").arg(input);
+    }
+
+    // Build errors go straight to the terminal, only the answers are captured
+    let output = command
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|err| format!("failed to run cargo: {}", err))?;
+
+    // A day that fails to solve still prints its errors, so only a run that
+    // printed nothing at all means the build or the binary itself failed
+    if !output.status.success() && output.stdout.is_empty() {
+        return Err(format!("cargo run failed ({})", output.status));
+    }
+
+    parse_lines(&String::from_utf8_lossy(&output.stdout))
+        .map_err(|err| format!("could not read the answers: {}", err))
+}
+
+/// Runs the day's unit tests and the tests generated from its examples
+fn run_tests(root: &Path, day: u8) -> bool {
+    let status = cargo(root)
+        .args(["test", "--quiet", "--"])
+        .arg(format!("day_{}::", day))
+        .arg(format!("examples::day_{}_", day))
+        .status();
+
+    status.is_ok_and(|status| status.success())
+}
+
+pub fn watch(root: &Path, day: u8, input: Option<&Path>) -> ! {
+    let paths = watched_paths(root, day, input);
+
+    let mut previous: Option<Vec<Record>> = None;
+    let mut last_seen = None;
+
+    loop {
+        let seen = modified_times(&paths);
+
+        if last_seen.as_ref() != Some(&seen) {
+            last_seen = Some(seen);
+
+            println!("Running day {}", day);
+
+            match run_day(root, day, input) {
+                Ok(records) => {
+                    for line in diff_answers(previous.as_deref(), &records) {
+                        println!("{}", line);
+                    }
+
+                    previous = Some(records);
+                }
+                Err(err) => eprintln!("Day {}: {}", day, err),
+            }
+
+            match run_tests(root, day) {
+                true => println!("Tests passed"),
+                false => println!("Tests failed"),
+            }
+
+            println!("Watching for changes");
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use advent::Part;
+
+    fn record(part: Part, answer: Option<i32>, error: Option<&str>) -> Record {
+        Record {
+            day: 1,
+            part,
+            answer: answer.map(Answer::from),
+            duration: None,
+            error: error.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_diff_answers() {
+        let first = vec![
+            record(Part::One, Some(24000), None),
+            record(Part::Two, Some(45000), None),
+        ];
+
+        assert_eq!(
+            diff_answers(None, &first),
+            vec!["Part 1: 24000", "Part 2: 45000"]
+        );
+
+        let second = vec![
+            record(Part::One, Some(24000), None),
+            record(Part::Two, None, Some("failed to parse input")),
+        ];
+
+        assert_eq!(
+            diff_answers(Some(&first), &second),
+            vec![
+                "Part 1: 24000 (unchanged)",
+                "Part 2: 45000 -> error: failed to parse input"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_modified_times_sees_new_files() {
+        let root = env::temp_dir().join(format!("advent-watch-{}", std::process::id()));
+        let day_dir = root.join("src/day_3");
+        fs::create_dir_all(&day_dir).unwrap();
+        fs::write(day_dir.join("mod.rs"), "").unwrap();
+
+        let paths = watched_paths(&root, 3, Some(&root.join("day_3.txt")));
+        let before = modified_times(&paths);
+
+        fs::write(day_dir.join("input.rs"), "").unwrap();
+
+        assert_ne!(modified_times(&paths), before);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_modified_times_sees_created_input() {
+        let root = env::temp_dir().join(format!("advent-watch-input-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+
+        let input = root.join("day_3.txt");
+        let paths = watched_paths(&root, 3, Some(&input));
+        let before = modified_times(&paths);
+
+        assert!(before.contains(&(input.clone(), None)));
+
+        fs::write(&input, "vJrwpWtwJgWrhcsFMMfFFhFp\n").unwrap();
+        let after = modified_times(&paths);
+
+        assert_ne!(after, before);
+        assert!(after
+            .iter()
+            .any(|(path, time)| *path == input && time.is_some()));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}